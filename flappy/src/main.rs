@@ -14,9 +14,13 @@
 //! 2. **拍打机制**: 按空格键给予玩家向上的速度
 //! 3. **障碍物生成**: 随机生成带有缺口的管道，缺口大小随分数增加而减小
 //! 4. **碰撞检测**: 检测玩家是否撞到障碍物或超出屏幕边界
-//! 5. **状态机**: 游戏在菜单、游戏中、结束三种状态间切换
+//! 5. **状态机**: 游戏在菜单、游戏中、结束、回放四种状态间切换
+//! 6. **回放**: 记录随机种子和每次拍打所在的逻辑帧，结束后可以原样重放
 
 use bracket_lib::prelude::*;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // 游戏常量配置
@@ -35,6 +39,301 @@ const SCREEN_HEIGHT: i32 = 50;
 /// 75ms 约等于 13 FPS 的游戏逻辑更新速度
 const FRAME_DURATION: f32 = 75.0;
 
+/// 最快的帧持续时间（毫秒），分数再高也不会比这更快
+const MIN_FRAME_DURATION: f32 = 40.0;
+
+/// 背景配色每隔多少分切换一次
+const PALETTE_STEP: i32 = 10;
+
+/// 背景配色循环：深蓝 → 紫色 → 栗色
+const PALETTE: [(u8, u8, u8); 3] = [NAVY, PURPLE, MAROON];
+
+/// 达到该分数后开始出现移动管道
+const MOVING_OBSTACLE_SCORE: i32 = 5;
+
+/// 移动管道每个逻辑帧额外向左漂移的格数
+const MOVING_OBSTACLE_SPEED: i32 = 1;
+
+/// 达到该分数后开始出现风区 / 重力区（见 `Zone`）
+const ZONE_SCORE: i32 = 10;
+
+/// 达到 `ZONE_SCORE` 后，每生成一根新管道时出现区域的概率为 1 / ZONE_CHANCE
+const ZONE_CHANCE: i32 = 3;
+
+/// 障碍物间距的下限（字符单位）
+/// 无论选哪个间距档位、得了多少分，新管道离玩家都不会比这更近
+const MIN_OBSTACLE_SPACING: i32 = 20;
+
+/// 连击倍数上限
+/// 连续穿过管道时得分依次 x1、x2、x3，之后保持 x3
+const MAX_COMBO: i32 = 3;
+
+/// 连击时间窗口（毫秒）
+/// 距离上次得分超过这个时间再穿过管道，连击从 x1 重新开始
+/// 时间按逻辑帧累计（每帧加当前难度的帧持续时间），保证回放时连击完全一致
+const COMBO_WINDOW_MS: f32 = 8000.0;
+
+/// 连击高度阈值
+/// 穿过管道时玩家低于这条线（y 更大）说明在贴地挣扎，连击中断
+const COMBO_FLOOR_Y: i32 = SCREEN_HEIGHT - 10;
+
+/// 擦边奖励阈值
+/// 穿过管道时距离缺口上/下边缘不超过这么多格，额外奖励 1 分
+const NEAR_MISS_DISTANCE: i32 = 1;
+
+/// 擦边奖励后 "NICE!" 提示的显示时长（毫秒）
+const NICE_FLASH_MS: f32 = 1000.0;
+
+/// 隧道模式：通道初始高度（字符单位）
+const TUNNEL_START_GAP: i32 = 24;
+
+/// 隧道模式：通道最小高度，收窄到这里后不再变窄
+const TUNNEL_MIN_GAP: i32 = 8;
+
+/// 隧道模式：每前进这么多格通道收窄 1 格
+const TUNNEL_SHRINK_DISTANCE: i32 = 40;
+
+/// 隧道模式：通道中线上下摆动的最大幅度（字符单位）
+const TUNNEL_MAX_AMPLITUDE: f32 = 12.0;
+
+/// 隧道模式：每前进这么多格得 1 分
+const TUNNEL_SCORE_DISTANCE: i32 = 10;
+
+/// 主菜单动画的周期（毫秒）：外观字符上下浮动一个来回，"(P) Play" 闪烁一次
+const TITLE_ANIMATION_PERIOD_MS: f32 = 1200.0;
+
+/// 主菜单外观字符上下浮动的幅度（字符单位）
+const TITLE_BOB_AMPLITUDE: f32 = 1.5;
+
+/// 视差背景层的滚动速度（相对玩家前进速度的比例）
+/// 下标 0 是远处的星星，1 是近处的云；越远越慢，形成景深
+const PARALLAX_SPEEDS: [f32; 2] = [0.25, 0.5];
+
+/// 远景星星的位置 (x, y)
+const PARALLAX_STARS: [(i32, i32); 8] = [
+    (3, 4),
+    (15, 10),
+    (27, 6),
+    (38, 14),
+    (50, 3),
+    (61, 12),
+    (70, 8),
+    (77, 16),
+];
+
+/// 近景云朵的位置 (x, y)
+const PARALLAX_CLOUDS: [(i32, i32); 4] = [(8, 20), (30, 28), (52, 22), (72, 32)];
+
+/// 存档文件（位于当前工作目录），保存玩家的偏好设置
+///
+/// 格式为每行一个 `key=value`，目前只有 `skin=<下标>`
+const SAVE_FILE: &str = "flappy_save.txt";
+
+/// 按键配置文件（位于当前工作目录，可选）
+///
+/// 格式为每行一个 `动作=键名`，目前只有 `flap=<键名>`，例如 `flap=Up`
+const KEYS_FILE: &str = "keys.txt";
+
+/// 显示选项文件（位于当前工作目录），在选项界面切换后写入
+///
+/// 格式为每行一个 `选项=on|off`，例如 `parallax=off`
+const OPTIONS_FILE: &str = "options.txt";
+
+/// 技能评估文件（位于当前工作目录），每局（管道模式）结束后写入
+///
+/// 格式为一行 `skill=<数值>`，数值范围 [-SKILL_LIMIT, SKILL_LIMIT]
+const SKILL_FILE: &str = "skill.txt";
+
+/// 技能评估的上下限：-1.0 表示屡屡早早撞死，1.0 表示每局都很顺畅，0.0 为中性
+const SKILL_LIMIT: f32 = 1.0;
+
+/// 每局结束后技能评估向本局表现靠拢的比例（越大越看重最近几局）
+const SKILL_LEARNING_RATE: f32 = 0.25;
+
+/// 一局得分达到这个值视为"顺畅"（表现记为 1.0），0 分记为 -1.0，一半为中性
+const SKILL_SMOOTH_SCORE: i32 = 20;
+
+/// 技能评估为 ±SKILL_LIMIT 时，缺口最多放宽 / 收窄的格数
+const SKILL_GAP_ADJUST: i32 = 3;
+
+/// 技能评估为 ±SKILL_LIMIT 时，管道间距最多拉长 / 缩短的格数
+const SKILL_SPACING_ADJUST: i32 = 8;
+
+/// 分数上报地址的环境变量，例如 `http://127.0.0.1:3339/scores`
+///
+/// 设置后每局（管道模式）结束时把 `{name, score}` POST 过去，不设置就不上报
+const SCORE_ENDPOINT_VAR: &str = "SCORE_ENDPOINT";
+
+/// 上报时使用的玩家名称的环境变量，不设置时为 `DEFAULT_PLAYER_NAME`
+const PLAYER_NAME_VAR: &str = "PLAYER_NAME";
+
+/// 默认玩家名称
+const DEFAULT_PLAYER_NAME: &str = "player";
+
+/// 上报分数的超时时间（秒），服务没启动时也不会让后台线程挂太久
+const SCORE_SUBMIT_TIMEOUT_SECS: u64 = 2;
+
+/// 可以绑定为拍打键的键名（不区分大小写）
+///
+/// Esc 不在表中：游戏中它用于返回菜单 / 快进回放
+const KEY_NAMES: [(&str, VirtualKeyCode); 32] = [
+    ("Space", VirtualKeyCode::Space),
+    ("Return", VirtualKeyCode::Return),
+    ("Up", VirtualKeyCode::Up),
+    ("Down", VirtualKeyCode::Down),
+    ("Left", VirtualKeyCode::Left),
+    ("Right", VirtualKeyCode::Right),
+    ("A", VirtualKeyCode::A),
+    ("B", VirtualKeyCode::B),
+    ("C", VirtualKeyCode::C),
+    ("D", VirtualKeyCode::D),
+    ("E", VirtualKeyCode::E),
+    ("F", VirtualKeyCode::F),
+    ("G", VirtualKeyCode::G),
+    ("H", VirtualKeyCode::H),
+    ("I", VirtualKeyCode::I),
+    ("J", VirtualKeyCode::J),
+    ("K", VirtualKeyCode::K),
+    ("L", VirtualKeyCode::L),
+    ("M", VirtualKeyCode::M),
+    ("N", VirtualKeyCode::N),
+    ("O", VirtualKeyCode::O),
+    ("P", VirtualKeyCode::P),
+    ("Q", VirtualKeyCode::Q),
+    ("R", VirtualKeyCode::R),
+    ("S", VirtualKeyCode::S),
+    ("T", VirtualKeyCode::T),
+    ("U", VirtualKeyCode::U),
+    ("V", VirtualKeyCode::V),
+    ("W", VirtualKeyCode::W),
+    ("X", VirtualKeyCode::X),
+    ("Y", VirtualKeyCode::Y),
+    ("Z", VirtualKeyCode::Z),
+];
+
+// ============================================================================
+// 玩家外观
+// ============================================================================
+
+/// 玩家外观：显示的字符、颜色和菜单里展示的名字
+struct Skin {
+    /// 显示的字符
+    glyph: char,
+    /// 前景色
+    color: (u8, u8, u8),
+    /// 菜单中显示的名字
+    name: &'static str,
+}
+
+/// 可选外观，菜单中按 S 依次切换；下标 0 是默认的黄色 '@'
+const SKINS: [Skin; 3] = [
+    Skin {
+        glyph: '@',
+        color: YELLOW,
+        name: "Dragon",
+    },
+    Skin {
+        glyph: '>',
+        color: ORANGE,
+        name: "Arrow",
+    },
+    Skin {
+        glyph: '^',
+        color: CYAN,
+        name: "Rocket",
+    },
+];
+
+// ============================================================================
+// 障碍物间距档位
+// ============================================================================
+
+/// 障碍物间距档位（主菜单中按 D 切换）
+///
+/// 在难度曲线给出的基础间距上按百分比缩放：
+/// 间距越小，留给玩家调整高度的时间越短，游戏越难
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Spacing {
+    /// 75%：管道更密集
+    Close,
+    /// 100%：与难度曲线一致
+    Normal,
+    /// 150%：管道更稀疏
+    Far,
+}
+
+impl Spacing {
+    /// 档位对应的缩放百分比
+    fn percent(self) -> i32 {
+        match self {
+            Spacing::Close => 75,
+            Spacing::Normal => 100,
+            Spacing::Far => 150,
+        }
+    }
+
+    /// 主菜单中显示的名称
+    fn name(self) -> &'static str {
+        match self {
+            Spacing::Close => "Close",
+            Spacing::Normal => "Normal",
+            Spacing::Far => "Far",
+        }
+    }
+
+    /// 主菜单中按 D 后的下一个档位（循环）
+    fn next(self) -> Self {
+        match self {
+            Spacing::Close => Spacing::Normal,
+            Spacing::Normal => Spacing::Far,
+            Spacing::Far => Spacing::Close,
+        }
+    }
+
+    /// 把难度曲线给出的基础间距按档位缩放，并保证不小于 `MIN_OBSTACLE_SPACING`
+    fn apply(self, base_spacing: i32) -> i32 {
+        i32::max(MIN_OBSTACLE_SPACING, base_spacing * self.percent() / 100)
+    }
+}
+
+// ============================================================================
+// 显示选项
+// ============================================================================
+
+/// 显示相关的开关，集中在一处由 `play` / 渲染函数查询
+///
+/// 只影响显示，不影响模拟：关掉任何一项，同样的种子和拍打仍然得到同样的一局
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Options {
+    /// 擦边时闪烁 "NICE!" 提示
+    screen_flash: bool,
+    /// 右上角显示帧率
+    show_fps: bool,
+    /// 视差滚动背景
+    parallax: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            screen_flash: true,
+            show_fps: false,
+            parallax: true,
+        }
+    }
+}
+
+impl Options {
+    /// 选项在文件和选项界面中的名字及对应字段，顺序即界面中的顺序
+    fn entries(&mut self) -> [(&'static str, &mut bool); 3] {
+        [
+            ("screen_flash", &mut self.screen_flash),
+            ("show_fps", &mut self.show_fps),
+            ("parallax", &mut self.parallax),
+        ]
+    }
+}
+
 // ============================================================================
 // 游戏状态枚举
 // ============================================================================
@@ -43,15 +342,68 @@ const FRAME_DURATION: f32 = 75.0;
 ///
 /// 使用状态机模式管理游戏的不同阶段：
 /// - Menu: 主菜单界面，等待玩家开始游戏
+/// - Options: 选项界面，切换显示选项
 /// - Playing: 游戏进行中，处理玩家输入和游戏逻辑
 /// - End: 游戏结束界面，显示分数并等待重新开始
+/// - Replay: 只读回放上一局，按录制的拍打帧驱动玩家
 enum GameMode {
     /// 主菜单状态
     Menu,
+    /// 选项界面（Esc 返回主菜单）
+    Options,
     /// 游戏进行中状态
     Playing,
     /// 游戏结束状态
     End,
+    /// 回放状态（不响应拍打，Esc 直接跳到结尾）
+    Replay,
+}
+
+/// 一局结束的原因，在 `check_progress` 判定死亡的那一帧记下，结束界面据此给出提示
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DeathCause {
+    /// 掉出屏幕底部
+    FellOff,
+    /// 撞到管道
+    HitPipe,
+    /// 硬核模式下碰到屏幕顶部
+    HitCeiling,
+    /// 隧道模式下碰到通道墙壁（隧道里没有管道）
+    HitTunnelWall,
+}
+
+impl DeathCause {
+    /// 结束界面上的说明
+    ///
+    /// `pipe` 是出事时正在面对的管道序号（从 1 开始，等于已穿过的管道数 + 1）
+    fn describe(self, pipe: i32) -> String {
+        match self {
+            DeathCause::FellOff => format!("You fell off the screen at pipe #{}", pipe),
+            DeathCause::HitPipe => format!("You hit pipe #{}", pipe),
+            DeathCause::HitCeiling => format!("You hit the ceiling at pipe #{}", pipe),
+            DeathCause::HitTunnelWall => "You hit the tunnel wall".to_string(),
+        }
+    }
+}
+
+// ============================================================================
+// 回放录像
+// ============================================================================
+
+/// 一局游戏的录像
+///
+/// ## 为什么只需要这两样
+///
+/// 障碍物完全由随机种子决定，玩家运动完全由逻辑帧和拍打决定，
+/// 所以用同一个种子重新开局，在相同的逻辑帧拍打，就能复现完全相同的一局
+#[derive(Clone, Default)]
+struct Recording {
+    /// 本局随机数生成器的种子
+    seed: u64,
+    /// 每次拍打发生时已经执行过的逻辑帧数（非递减，同一帧只记一次）
+    flaps: Vec<u64>,
+    /// 开局时的技能评估（决定缺口和间距的微调），回放时沿用它而不是局后更新过的值
+    skill: f32,
 }
 
 // ============================================================================
@@ -65,7 +417,22 @@ enum GameMode {
 /// - frame_time: 帧时间累加器，用于控制游戏逻辑更新频率
 /// - mode: 当前游戏模式
 /// - obstacle: 当前障碍物对象
+/// - zone: 玩家与当前障碍物之间的风区 / 重力区（可能没有）
 /// - score: 玩家得分
+/// - hardcore: 硬核模式开关
+/// - practice: 练习模式开关
+/// - tunnel / tunnel_phase: 隧道模式开关及本局通道波形的相位
+/// - combo / last_score_time_ms: 连击倍数及距上次得分的时间
+/// - bonus / nice_flash_ms: 擦边奖励累计分数及 "NICE!" 提示剩余时间
+/// - tick_count / recording / replay_cursor: 逻辑帧计数、本局录像及回放进度
+/// - parallax_offsets: 视差背景各层的滚动偏移
+/// - spacing: 障碍物间距档位
+/// - skin: 玩家外观在 `SKINS` 中的下标
+/// - flap_key: 拍打键（读取自 `KEYS_FILE`，默认空格）
+/// - options: 显示选项（读取自 `OPTIONS_FILE`）
+/// - score_endpoint: 分数上报地址（读取自 `SCORE_ENDPOINT`，没有时不上报）
+/// - menu_time_ms: 主菜单动画的相位
+/// - rng: 生成障碍物用的随机数生成器
 struct State {
     /// 玩家对象
     player: Player,
@@ -76,8 +443,54 @@ struct State {
     mode: GameMode,
     /// 当前障碍物
     obstacle: Obstacle,
+    /// 当前的环境区域，随新管道一起生成，`None` 表示这一段没有
+    zone: Option<Zone>,
     /// 玩家得分
     score: i32,
+    /// 本局已经穿过的管道数（和得分不同：不含连击倍数和擦边奖励）
+    pipes_passed: i32,
+    /// 本局的死亡原因，还活着时为 `None`
+    death_cause: Option<DeathCause>,
+    /// 硬核模式：碰到屏幕顶部也算死亡（普通模式下顶部只是一堵墙）
+    hardcore: bool,
+    /// 练习模式：不会死亡也不计分，用于熟悉操作
+    practice: bool,
+    /// 隧道模式：没有管道，改为在上下起伏、逐渐收窄的通道里飞行，按距离计分
+    tunnel: bool,
+    /// 隧道通道波形的相位，每局由种子决定（回放时得到同样的通道）
+    tunnel_phase: f32,
+    /// 当前连击倍数（0 表示还没得过分）
+    combo: i32,
+    /// 距离上次得分经过的时间（毫秒）
+    last_score_time_ms: f32,
+    /// 本局擦边奖励的分数（已计入 score，单独记录用于结束界面展示）
+    bonus: i32,
+    /// "NICE!" 提示剩余显示时间（毫秒），0 表示不显示
+    nice_flash_ms: f32,
+    /// 本局已经执行的逻辑帧数
+    tick_count: u64,
+    /// 本局录像（开局时记下种子，游戏中记录拍打）
+    recording: Recording,
+    /// 回放时下一个要应用的拍打在 `recording.flaps` 中的下标
+    replay_cursor: usize,
+    /// 视差背景各层的水平偏移（字符单位，范围 [0, SCREEN_WIDTH)）
+    parallax_offsets: [f32; 2],
+    /// 障碍物间距档位（和硬核模式一样，跨局保留）
+    spacing: Spacing,
+    /// 玩家外观在 `SKINS` 中的下标，切换后写入存档
+    skin: usize,
+    /// 拍打键，启动时读取自按键配置文件
+    flap_key: VirtualKeyCode,
+    /// 显示选项，在选项界面切换后写入选项文件
+    options: Options,
+    /// 对玩家水平的评估（见 `next_skill_estimate`），每局结束后更新并写入技能评估文件
+    skill_estimate: f32,
+    /// 分数上报地址，每局（管道模式）结束后把分数 POST 过去；None 表示不上报
+    score_endpoint: Option<String>,
+    /// 主菜单动画的相位（毫秒，范围 [0, TITLE_ANIMATION_PERIOD_MS)），只在主菜单中推进
+    menu_time_ms: f32,
+    /// 随机数生成器（游戏中使用系统随机种子，测试中使用固定种子）
+    rng: RandomNumberGenerator,
 }
 
 // ============================================================================
@@ -93,6 +506,7 @@ struct State {
 /// 障碍物使用世界坐标系统（x 随玩家移动而相对变化），
 /// 渲染时转换为屏幕坐标。缺口位置随机生成，
 /// 缺口大小随游戏进行（分数增加）而逐渐减小，增加难度。
+/// 分数较高时部分管道会主动向左移动（`speed > 0`），比玩家前进得更快地逼近。
 struct Obstacle {
     /// 障碍物的世界 x 坐标
     x: i32,
@@ -100,6 +514,8 @@ struct Obstacle {
     gap_y: i32,
     /// 缺口大小（半径的2倍）
     size: i32,
+    /// 每个逻辑帧额外向左移动的格数，0 表示静止的管道
+    speed: i32,
 }
 
 impl Obstacle {
@@ -109,6 +525,7 @@ impl Obstacle {
     ///
     /// * `x` - 障碍物的初始 x 坐标（世界坐标）
     /// * `score` - 当前分数，用于计算缺口大小
+    /// * `random` - 随机数生成器，由调用方持有（测试中可传入固定种子的生成器）
     ///
     /// # 返回值
     ///
@@ -117,16 +534,43 @@ impl Obstacle {
     /// # 算法说明
     ///
     /// - 缺口 y 位置：在 10-50 范围内随机生成
-    /// - 缺口大小：max(2, 20 - score)，最小为2，随分数增加而减小
-    fn new(x: i32, score: i32) -> Self {
-        let mut random = RandomNumberGenerator::new();
+    /// - 缺口大小：由 `difficulty_for(score).gap_size` 决定，随分数增加而减小
+    /// - 移动速度：分数达到 `MOVING_OBSTACLE_SCORE` 后，有一半概率生成移动管道
+    fn new(x: i32, score: i32, random: &mut RandomNumberGenerator) -> Self {
+        let gap_y = random.range(10, 50);
+        let speed = if score >= MOVING_OBSTACLE_SCORE && random.range(0, 2) == 1 {
+            MOVING_OBSTACLE_SPEED
+        } else {
+            0
+        };
         Obstacle {
             x,
-            gap_y: random.range(10, 50),
-            size: i32::max(2, 20 - score),
+            gap_y,
+            size: difficulty_for(score).gap_size,
+            speed,
         }
     }
 
+    /// 推进一个逻辑帧：移动管道向左漂移 `speed` 格
+    fn advance(&mut self) {
+        self.x -= self.speed;
+    }
+
+    /// 障碍物在屏幕上的 x 坐标
+    ///
+    /// 玩家每帧前进 1 格、移动管道每帧后退 `speed` 格，两者都已体现在世界坐标中，
+    /// 所以屏幕坐标就是两者之差
+    fn screen_x(&self, player_x: i32) -> i32 {
+        self.x - player_x
+    }
+
+    /// 玩家是否已经完全越过障碍物
+    ///
+    /// 移动管道在一帧内扫过 `[x, x + speed]` 这段区间，玩家越过整段区间才算通过
+    fn passed_by(&self, player: &Player) -> bool {
+        player.x > self.x + self.speed
+    }
+
     /// 渲染障碍物到屏幕
     ///
     /// # 参数
@@ -136,13 +580,13 @@ impl Obstacle {
     ///
     /// # 渲染原理
     ///
-    /// 1. 计算屏幕坐标：screen_x = obstacle.x - player_x
+    /// 1. 计算屏幕坐标：screen_x = obstacle.x - player_x（见 `screen_x`）
     /// 2. 绘制上半部分管道：从 y=0 到 gap_y - half_size
     /// 3. 绘制下半部分管道：从 gap_y + half_size 到屏幕底部
     /// 4. 使用红色 '|' 字符表示管道
     fn render(&mut self, ctx: &mut BTerm, player_x: i32) {
         // 将世界坐标转换为屏幕坐标
-        let screen_x = self.x - player_x;
+        let screen_x = self.screen_x(player_x);
         let half_size = self.size / 2;
 
         // 绘制上半部分管道（从顶部到缺口上边缘）
@@ -169,12 +613,15 @@ impl Obstacle {
     /// # 碰撞检测原理
     ///
     /// 碰撞发生的条件（必须同时满足）：
-    /// 1. 玩家 x 坐标等于障碍物 x 坐标（水平重叠）
+    /// 1. 玩家 x 坐标落在障碍物本帧扫过的区间 `[x, x + speed]` 内（水平重叠）
     /// 2. 玩家 y 坐标在缺口范围之外（在缺口上方或下方）
+    ///
+    /// 静止管道的区间就是 `x` 本身；移动管道与玩家每帧相对移动 `1 + speed` 格，
+    /// 只检查 `x` 单点可能会让玩家直接"穿过"管道，因此需要检查整段区间
     fn hit_obstacle(&self, player: &Player) -> bool {
         let half_size = self.size / 2;
         // 检查 x 坐标是否重叠
-        let does_x_match = player.x == self.x;
+        let does_x_match = (self.x..=self.x + self.speed).contains(&player.x);
         // 检查玩家是否在缺口上方
         let player_above_gap = player.y < self.gap_y - half_size;
         // 检查玩家是否在缺口下方
@@ -182,6 +629,149 @@ impl Obstacle {
 
         does_x_match && (player_above_gap || player_below_gap)
     }
+
+    /// 玩家离缺口上/下边缘的最小垂直距离
+    ///
+    /// # 返回值
+    ///
+    /// - 正好在边缘那一行返回 0，往缺口中心每走一格加 1
+    /// - 在缺口之外返回负数（离缺口越远越小）
+    ///
+    /// 缺口的安全区间与 `hit_obstacle` 一致：`[gap_y - half_size, gap_y + half_size]`
+    fn gap_edge_distance(&self, player_y: i32) -> i32 {
+        let half_size = self.size / 2;
+        let to_top = player_y - (self.gap_y - half_size);
+        let to_bottom = (self.gap_y + half_size) - player_y;
+        i32::min(to_top, to_bottom)
+    }
+
+    /// 是否擦边通过：在缺口内，且离边缘不超过 `NEAR_MISS_DISTANCE` 格
+    fn is_near_miss(&self, player_y: i32) -> bool {
+        (0..=NEAR_MISS_DISTANCE).contains(&self.gap_edge_distance(player_y))
+    }
+}
+
+// ============================================================================
+// 环境区域（风区 / 重力区）
+// ============================================================================
+
+/// 区域效果
+///
+/// 每种效果都换算成每个逻辑帧额外加到玩家速度上的作用力（正值向下），
+/// 由 `Player::gravity_and_move` 和普通重力一起结算
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ZoneEffect {
+    /// 重力区：重力加倍，下落更快
+    HeavyGravity,
+    /// 上升气流：抵消重力并持续把玩家往上推
+    Updraft,
+    /// 下沉气流：持续把玩家往下压
+    Downdraft,
+}
+
+impl ZoneEffect {
+    /// 所有效果，生成区域时从中随机挑选
+    const ALL: [ZoneEffect; 3] = [
+        ZoneEffect::HeavyGravity,
+        ZoneEffect::Updraft,
+        ZoneEffect::Downdraft,
+    ];
+
+    /// 每个逻辑帧额外施加的作用力（普通重力为 0.2）
+    fn force(self) -> f32 {
+        match self {
+            ZoneEffect::HeavyGravity => 0.2,
+            ZoneEffect::Updraft => -0.3,
+            ZoneEffect::Downdraft => 0.1,
+        }
+    }
+
+    /// 区域列的背景色调
+    fn tint(self) -> (u8, u8, u8) {
+        match self {
+            ZoneEffect::HeavyGravity => (90, 20, 20),
+            ZoneEffect::Updraft => (20, 80, 90),
+            ZoneEffect::Downdraft => (60, 40, 90),
+        }
+    }
+
+    /// 区域里显示的方向符号
+    fn glyph(self) -> char {
+        match self {
+            ZoneEffect::HeavyGravity => '▼',
+            ZoneEffect::Updraft => '↑',
+            ZoneEffect::Downdraft => '↓',
+        }
+    }
+}
+
+/// 环境区域
+///
+/// 占据世界坐标 `[x, x + width)` 的几列（整屏高度），玩家在其中时每帧受到 `effect` 的作用力。
+/// 和障碍物一样使用世界坐标，渲染时换算成屏幕坐标
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Zone {
+    /// 区域起始的世界 x 坐标
+    x: i32,
+    /// 区域宽度（列数）
+    width: i32,
+    /// 区域效果
+    effect: ZoneEffect,
+}
+
+impl Zone {
+    /// 在玩家和下一根管道之间随机生成一个区域
+    ///
+    /// # 参数
+    ///
+    /// * `player_x` - 玩家当前的世界 x 坐标
+    /// * `spacing` - 下一根管道与玩家的距离
+    /// * `score` - 当前分数，低于 `ZONE_SCORE` 时不生成
+    /// * `random` - 随机数生成器（低于阈值时不消耗随机数，前期的障碍物序列不受影响）
+    ///
+    /// 区域占据两者之间的中间一半，给玩家进出区域后留出调整的空间
+    fn spawn(
+        player_x: i32,
+        spacing: i32,
+        score: i32,
+        random: &mut RandomNumberGenerator,
+    ) -> Option<Zone> {
+        if score < ZONE_SCORE || random.range(0, ZONE_CHANCE) != 0 {
+            return None;
+        }
+        let effect = ZoneEffect::ALL[random.range(0, ZoneEffect::ALL.len() as i32) as usize];
+        Some(Zone {
+            x: player_x + spacing / 4,
+            width: spacing / 2,
+            effect,
+        })
+    }
+
+    /// 世界坐标 `world_x` 这一列是否在区域内
+    fn contains(&self, world_x: i32) -> bool {
+        (self.x..self.x + self.width).contains(&world_x)
+    }
+
+    /// 渲染区域：把覆盖的列整列染色，每隔几行画一个方向符号
+    ///
+    /// 只改背景色（`set_bg`），之前画上去的视差背景字符仍然可见
+    fn render(&self, ctx: &mut BTerm, player_x: i32) {
+        let tint = self.effect.tint();
+        for world_x in self.x..self.x + self.width {
+            let screen_x = world_x - player_x;
+            if !(0..SCREEN_WIDTH).contains(&screen_x) {
+                continue;
+            }
+            for y in 0..SCREEN_HEIGHT {
+                ctx.set_bg(screen_x, y, tint);
+            }
+            if world_x % 4 == 0 {
+                for y in (5..SCREEN_HEIGHT).step_by(8) {
+                    ctx.set(screen_x, y, GRAY, tint, to_cp437(self.effect.glyph()));
+                }
+            }
+        }
+    }
 }
 
 // ============================================================================
@@ -236,27 +826,34 @@ impl Player {
     /// # 说明
     ///
     /// 玩家始终显示在屏幕左侧 x=0 的位置，
-    /// 使用黄色 '@' 字符表示
-    fn render(&mut self, ctx: &mut BTerm) {
-        ctx.set(0, self.y, YELLOW, BLACK, to_cp437('@'));
+    /// 字符和颜色由当前外观 `skin` 决定（默认黄色 '@'）
+    fn render(&mut self, ctx: &mut BTerm, skin: &Skin) {
+        ctx.set(0, self.y, skin.color, BLACK, to_cp437(skin.glyph));
     }
 
     /// 应用重力并移动玩家
     ///
+    /// # 参数
+    ///
+    /// * `extra_force` - 额外作用力（正值向下），来自玩家所在的环境区域，不在区域内时为 0
+    ///
     /// # 物理计算原理
     ///
     /// 每次调用时执行以下操作：
-    /// 1. 增加向下的速度（重力加速度 0.2），最大速度限制为 2.0
-    /// 2. 将速度应用到 y 坐标（向下移动）
-    /// 3. x 坐标增加 1（自动前进）
-    /// 4. 如果 y < 0，将 y 设为 0（防止飞出屏幕顶部）
+    /// 1. 增加向下的速度（重力加速度 0.2 加上额外作用力），最大速度限制为 2.0
+    /// 2. 向上的速度不超过拍打的 2.0（上升气流不会把玩家无限加速）
+    /// 3. 将速度应用到 y 坐标（向下移动）
+    /// 4. x 坐标增加 1（自动前进）
+    /// 5. 如果 y < 0，将 y 设为 0（防止飞出屏幕顶部）
     ///
     /// 这实现了简单的抛物线运动效果
-    fn gravity_and_move(&mut self) {
+    fn gravity_and_move(&mut self, extra_force: f32) {
         // 应用重力加速度，但限制最大下落速度
         if self.velocity < 2.0 {
-            self.velocity += 0.2;
+            self.velocity += 0.2 + extra_force;
         }
+        // 没有额外作用力时速度本来就不会低于 -2.0，这里只限制上升气流
+        self.velocity = self.velocity.max(-2.0);
         // 将速度应用到位置
         self.y += self.velocity as i32;
 
@@ -294,13 +891,54 @@ impl State {
     /// - 第一个障碍物在屏幕右边缘
     /// - 游戏模式为菜单
     /// - 分数为 0
+    /// - 外观读取自存档（没有存档时使用默认外观）
+    /// - 拍打键读取自按键配置（没有配置时使用空格）
+    /// - 显示选项读取自选项文件（没有时使用默认选项）
+    /// - 技能评估读取自技能评估文件（没有时为中性的 0.0）
+    /// - 分数上报地址读取自 `SCORE_ENDPOINT` 环境变量（没有时不上报）
     fn new() -> Self {
+        let mut state = Self::with_rng(RandomNumberGenerator::new());
+        state.skin = load_skin();
+        state.flap_key = load_flap_key();
+        state.options = load_options();
+        state.skill_estimate = load_skill();
+        state.score_endpoint = parse_score_endpoint(std::env::var(SCORE_ENDPOINT_VAR).ok());
+        state
+    }
+
+    /// 使用指定的随机数生成器创建游戏状态
+    ///
+    /// 传入 `RandomNumberGenerator::seeded(..)` 可以让障碍物序列完全可复现
+    fn with_rng(mut rng: RandomNumberGenerator) -> Self {
         State {
             player: Player::new(5, 25),
             frame_time: 0.0,
             mode: GameMode::Menu,
-            obstacle: Obstacle::new(SCREEN_WIDTH, 0),
+            obstacle: Obstacle::new(SCREEN_WIDTH, 0, &mut rng),
+            zone: None,
             score: 0,
+            pipes_passed: 0,
+            death_cause: None,
+            hardcore: false,
+            practice: false,
+            tunnel: false,
+            tunnel_phase: 0.0,
+            combo: 0,
+            last_score_time_ms: 0.0,
+            bonus: 0,
+            nice_flash_ms: 0.0,
+            tick_count: 0,
+            recording: Recording::default(),
+            replay_cursor: 0,
+            parallax_offsets: [0.0; 2],
+            spacing: Spacing::Normal,
+            skin: 0,
+            flap_key: VirtualKeyCode::Space,
+            options: Options::default(),
+            skill_estimate: 0.0,
+            score_endpoint: None,
+            menu_time_ms: 0.0,
+            rng,
         }
     }
 
@@ -319,125 +957,836 @@ impl State {
     /// 5. **得分**: 玩家通过障碍物时加分
     /// 6. **碰撞检测**: 检测死亡条件
     fn play(&mut self, ctx: &mut BTerm) {
-        // 清屏并设置背景色为深蓝色
-        ctx.cls_bg(NAVY);
+        let difficulty = difficulty_for(self.score);
 
-        // 累积帧时间
-        self.frame_time += ctx.frame_time_ms;
+        // 清屏并设置背景色（随分数切换配色）
+        ctx.cls_bg(difficulty.background);
 
-        // 固定时间步长更新游戏逻辑
-        // 只有当累积时间超过 FRAME_DURATION 时才更新
-        if self.frame_time > FRAME_DURATION {
-            self.frame_time = 0.0;
-            self.player.gravity_and_move();
+        // 累积帧时间，够一个逻辑帧就推进
+        self.advance_time(ctx.frame_time_ms);
+
+        // 处理按键输入
+        // - 拍打键（默认空格，见 `KEYS_FILE`）：拍打翅膀（回放中忽略，拍打来自录像）
+        // - Esc：练习模式下返回主菜单（练习模式本身永远不会结束）
+        // - Esc：回放中直接快进到结尾
+        let replaying = matches!(self.mode, GameMode::Replay);
+        match ctx.key {
+            Some(key) if key == self.flap_key && !replaying => self.flap(),
+            Some(VirtualKeyCode::Escape) if self.practice => self.mode = GameMode::Menu,
+            Some(VirtualKeyCode::Escape) if replaying => self.skip_replay(),
+            _ => {}
         }
 
-        // 处理空格键输入 - 拍打翅膀
-        if let Some(VirtualKeyCode::Space) = ctx.key {
-            self.player.flap();
+        // 推进并渲染视差背景（必须在障碍物和玩家之前绘制，避免盖住它们）
+        if self.options.parallax {
+            self.advance_parallax(ctx.frame_time_ms, difficulty.frame_duration);
+            self.render_parallax(ctx, difficulty.background);
+        }
+
+        // 渲染环境区域（同样在玩家和障碍物之前，只染背景色）
+        if let Some(zone) = &self.zone {
+            zone.render(ctx, self.player.x);
         }
 
         // 渲染玩家
-        self.player.render(ctx);
+        self.player.render(ctx, &SKINS[self.skin]);
 
         // 显示 UI 信息
-        ctx.print(0, 0, "Press space to flap");
+        ctx.print(0, 0, format!("Press {:?} to flap", self.flap_key));
         ctx.print(0, 1, &format!("Score {}", self.score));
+        if self.combo > 1 {
+            ctx.print(0, 2, format!("Combo x{}", self.combo));
+        }
+        if self.practice {
+            ctx.print(0, 2, "PRACTICE (Esc: menu)");
+        }
+        if replaying {
+            ctx.print(0, 0, "REPLAY (Esc: skip)  ");
+        }
+        if self.nice_flash_ms > 0.0 && self.options.screen_flash {
+            ctx.print_color(0, 3, YELLOW, difficulty.background, "NICE!");
+        }
+        if self.options.show_fps {
+            ctx.print(SCREEN_WIDTH - 8, 0, format!("FPS {:>3}", ctx.fps as i32));
+        }
 
-        // 渲染障碍物
-        self.obstacle.render(ctx, self.player.x);
+        // 渲染障碍物（隧道模式下渲染通道墙壁）
+        if self.tunnel {
+            self.render_tunnel(ctx, difficulty.background);
+        } else {
+            self.obstacle.render(ctx, self.player.x);
+        }
 
-        // 检测是否通过障碍物并计分
-        // 当玩家 x 坐标超过障碍物 x 坐标时，表示成功通过
-        if self.player.x > self.obstacle.x {
-            self.score += 1;
-            // 生成新障碍物，位置在当前位置 + 屏幕宽度处
-            self.obstacle = Obstacle::new(self.player.x + SCREEN_WIDTH, self.score);
+        let was_playing = matches!(self.mode, GameMode::Playing);
+        self.check_progress();
+        // 本局刚结束：技能评估已在 check_progress 中更新，写入文件供下次启动使用
+        if was_playing && matches!(self.mode, GameMode::End) && !self.tunnel {
+            save_skill(self.skill_estimate);
+            // 配置了上报地址就把分数发给 webservice（隧道模式按距离计分，不上排行榜）
+            if let Some(endpoint) = &self.score_endpoint {
+                submit_score(endpoint.clone(), player_name(), self.score);
+            }
         }
+    }
 
-        // 死亡检测：
-        // 1. 玩家掉出屏幕底部
-        // 2. 玩家撞到障碍物
-        if self.player.y > SCREEN_HEIGHT || self.obstacle.hit_obstacle(&self.player) {
-            self.mode = GameMode::End;
+    /// 累积真实经过的时间，达到当前难度的帧持续时间时推进一个逻辑帧
+    ///
+    /// 固定时间步长：渲染帧率再高，逻辑也只按 `frame_duration` 的节奏更新
+    fn advance_time(&mut self, dt_ms: f32) {
+        let frame_duration = difficulty_for(self.score).frame_duration;
+        self.frame_time += dt_ms;
+        self.nice_flash_ms = f32::max(0.0, self.nice_flash_ms - dt_ms);
+        if self.frame_time > frame_duration {
+            self.frame_time = 0.0;
+            self.tick();
         }
     }
 
-    /// 重新开始游戏
+    /// 无渲染地推进模拟（基准测试 / 批量模拟用）
+    ///
+    /// # 参数
+    ///
+    /// * `dt_ms` - 本步经过的时间（毫秒），相当于 `ctx.frame_time_ms`
+    /// * `flap` - 本步是否按下拍打键
+    ///
+    /// # 返回值
+    ///
+    /// 本步结束后玩家是否还活着
+    ///
+    /// 与 `play` 的顺序完全一致（推进时间 → 处理拍打 → 计分与死亡检测），只是不碰 `ctx`；
+    /// 同样的种子、同样的 `dt_ms` 和拍打序列总会得到同样的结果
+    fn step_headless(&mut self, dt_ms: f32, flap: bool) -> bool {
+        self.advance_time(dt_ms);
+        if flap && !matches!(self.mode, GameMode::Replay) {
+            self.flap();
+        }
+        self.check_progress();
+        !matches!(self.mode, GameMode::End)
+    }
+
+    /// 推进一个逻辑帧
     ///
     /// # 说明
     ///
-    /// 重置所有游戏状态到初始值：
-    /// - 切换到游戏模式
-    /// - 重置帧时间
-    /// - 重新创建玩家
-    /// - 重新创建障碍物
-    /// - 重置分数
-    fn restart(&mut self) {
-        self.mode = GameMode::Playing;
-        self.frame_time = 0.0;
-        self.player = Player::new(5, 25);
-        self.obstacle = Obstacle::new(SCREEN_WIDTH, 0);
-        self.score = 0;
+    /// 1. 回放模式下先应用录像中属于本帧的拍打
+    /// 2. 玩家受重力（以及所在区域的作用力）移动、障碍物漂移
+    /// 3. 逻辑帧数 +1，连击计时按当前难度的帧持续时间累加（与真实时间无关）
+    ///
+    /// 整个过程只依赖状态本身，同样的种子和拍打总能得到同样的结果
+    fn tick(&mut self) {
+        if matches!(self.mode, GameMode::Replay) {
+            self.apply_replay_flaps();
+        }
+        let frame_duration = difficulty_for(self.score).frame_duration;
+        self.player.gravity_and_move(self.zone_force());
+        self.obstacle.advance();
+        self.tick_count += 1;
+        self.last_score_time_ms += frame_duration;
     }
 
-    /// 显示主菜单
+    /// 玩家拍打翅膀，并记录到录像中
     ///
-    /// # 参数
+    /// 两个逻辑帧之间多次拍打效果相同（都只是把速度设为 -2.0），所以同一帧只记一次
+    fn flap(&mut self) {
+        self.player.flap();
+        if self.recording.flaps.last() != Some(&self.tick_count) {
+            self.recording.flaps.push(self.tick_count);
+        }
+    }
+
+    /// 回放：应用录像中所有属于当前逻辑帧的拍打
+    fn apply_replay_flaps(&mut self) {
+        while self.recording.flaps.get(self.replay_cursor) == Some(&self.tick_count) {
+            self.player.flap();
+            self.replay_cursor += 1;
+        }
+    }
+
+    /// 回放：不等待渲染，一直推进到本局结束
     ///
-    /// * `ctx` - BTerm 上下文
+    /// 录像里的拍打用完后玩家只会下落，所以循环一定会结束
+    fn skip_replay(&mut self) {
+        while matches!(self.mode, GameMode::Replay) {
+            self.tick();
+            self.check_progress();
+        }
+    }
+
+    /// 计分与死亡检测
     ///
     /// # 说明
     ///
-    /// 显示欢迎信息和操作提示：
-    /// - P 键开始游戏
-    /// - Q 键退出
-    fn main_menu(&mut self, ctx: &mut BTerm) {
-        ctx.cls();
-        ctx.print_centered(5, "welcome here");
-        ctx.print_centered(8, "(P) Play");
-        ctx.print_centered(9, "(Q) Quit");
+    /// 0. 隧道模式下没有障碍物，按飞过的距离计分
+    /// 1. 玩家完全越过障碍物时表示成功通过，生成新障碍物
+    ///    （练习模式下不计分、不累计连击、没有擦边奖励）
+    ///    擦边通过（见 `Obstacle::is_near_miss`）额外 +1 分并显示 "NICE!"
+    /// 2. 普通模式下满足死亡条件则记下死亡原因并切换到结束界面；
+    ///    管道模式的正式一局（不是回放）结束时按本局得分更新技能评估
+    /// 3. 练习模式跳过死亡切换：掉出底部就回到屏幕中间，撞到管道直接穿过
+    fn check_progress(&mut self) {
+        if self.tunnel {
+            // 隧道模式：没有管道可穿过，分数就是飞过的距离（玩家每个逻辑帧前进 1 格）
+            self.score = self.tick_count as i32 / TUNNEL_SCORE_DISTANCE;
+        } else if self.obstacle.passed_by(&self.player) {
+            self.pipes_passed += 1;
+            if !self.practice {
+                self.combo = next_combo(self.combo, self.last_score_time_ms, self.player.y);
+                self.last_score_time_ms = 0.0;
+                self.score += self.combo;
 
-        // 处理菜单输入
-        if let Some(key) = ctx.key {
-            match key {
-                VirtualKeyCode::P => self.restart(),
-                VirtualKeyCode::Q => ctx.quitting = true,
-                _ => {}
+                // 必须在生成新障碍物之前判断：用的是刚穿过的这根管道的缺口
+                if self.obstacle.is_near_miss(self.player.y) {
+                    self.bonus += 1;
+                    self.score += 1;
+                    self.nice_flash_ms = NICE_FLASH_MS;
+                }
+            }
+            // 生成新障碍物，与玩家的距离由当前难度和间距档位决定
+            let spacing = self.obstacle_spacing();
+            self.spawn_obstacle(self.player.x + spacing);
+            // 分数够高时，两者之间可能出现一个风区 / 重力区
+            self.zone = Zone::spawn(self.player.x, spacing, self.score, &mut self.rng);
+        }
+
+        if self.practice {
+            if self.player.y > SCREEN_HEIGHT {
+                self.player.y = SCREEN_HEIGHT / 2;
+                self.player.velocity = 0.0;
             }
+        } else if let Some(cause) = self.death_cause() {
+            self.death_cause = Some(cause);
+            if matches!(self.mode, GameMode::Playing) && !self.tunnel {
+                self.skill_estimate = next_skill_estimate(self.skill_estimate, self.score);
+            }
+            self.mode = GameMode::End;
         }
     }
 
-    /// 显示死亡/游戏结束界面
+    /// 在世界坐标 `x` 处生成新障碍物，缺口按本局的技能评估微调
+    fn spawn_obstacle(&mut self, x: i32) {
+        self.obstacle = Obstacle::new(x, self.score, &mut self.rng);
+        self.obstacle.size = adapt_gap_size(self.obstacle.size, self.recording.skill);
+    }
+
+    /// 玩家当前所在区域施加的额外作用力，不在任何区域内时为 0
+    fn zone_force(&self) -> f32 {
+        self.zone
+            .filter(|zone| zone.contains(self.player.x))
+            .map_or(0.0, |zone| zone.effect.force())
+    }
+
+    /// 下一根管道与玩家之间的水平距离
+    ///
+    /// 难度曲线的基础间距先按本局的技能评估微调，再按 `spacing` 档位缩放，最小 `MIN_OBSTACLE_SPACING`
+    fn obstacle_spacing(&self) -> i32 {
+        let base = difficulty_for(self.score).obstacle_spacing;
+        self.spacing
+            .apply(adapt_obstacle_spacing(base, self.recording.skill))
+    }
+
+    /// 推进视差背景
     ///
     /// # 参数
     ///
-    /// * `ctx` - BTerm 上下文
+    /// * `frame_time_ms` - 本帧经过的时间（毫秒）
+    /// * `frame_duration` - 玩家前进 1 格所需的时间（毫秒）
     ///
-    /// # 说明
+    /// # 原理
     ///
-    /// 显示游戏结束信息、最终得分和操作提示
-    fn dead(&mut self, ctx: &mut BTerm) {
-        ctx.cls();
-        ctx.print_centered(5, "You are dead");
-        ctx.print_centered(6, &format!("you earned {} point", self.score));
-        ctx.print_centered(8, "(P) Play");
-        ctx.print_centered(9, "(Q) Quit");
+    /// 玩家每 `frame_duration` 毫秒前进 1 格，
+    /// 每层按各自比例换算成"格/毫秒"累加到偏移上，并对屏幕宽度取模实现循环
+    fn advance_parallax(&mut self, frame_time_ms: f32, frame_duration: f32) {
+        for (offset, speed) in self.parallax_offsets.iter_mut().zip(PARALLAX_SPEEDS) {
+            *offset = (*offset + speed * frame_time_ms / frame_duration) % SCREEN_WIDTH as f32;
+        }
+    }
 
-        // 处理结束界面输入
-        if let Some(key) = ctx.key {
-            match key {
-                VirtualKeyCode::P => self.restart(),
-                VirtualKeyCode::Q => ctx.quitting = true,
-                _ => {}
+    /// 渲染视差背景
+    ///
+    /// 纯装饰：只调用 `ctx.set`，不参与碰撞检测和计分
+    fn render_parallax(&self, ctx: &mut BTerm, background: (u8, u8, u8)) {
+        let layers = [
+            (&PARALLAX_STARS[..], '.', WHITE),
+            (&PARALLAX_CLOUDS[..], '~', LIGHT_BLUE),
+        ];
+        for ((positions, glyph, color), offset) in layers.into_iter().zip(self.parallax_offsets) {
+            for &(x, y) in positions {
+                let screen_x = (x - offset as i32).rem_euclid(SCREEN_WIDTH);
+                ctx.set(screen_x, y, color, background, to_cp437(glyph));
             }
         }
     }
-}
-
-// ============================================================================
-// GameState trait 实现
-// ============================================================================
+
+    /// 判断玩家是否死亡、因为什么死亡
+    ///
+    /// # 死亡条件
+    ///
+    /// 1. 玩家掉出屏幕底部
+    /// 2. 玩家撞到障碍物
+    /// 3. 硬核模式下玩家碰到屏幕顶部（y <= 0）
+    /// 4. 隧道模式下把第 2 条换成：玩家碰到通道上下墙壁
+    ///
+    /// 普通模式下 `gravity_and_move` 会把 y 限制在 0，顶部是安全的
+    ///
+    /// # 返回值
+    ///
+    /// 死亡原因，还活着时为 `None`；同时满足多个条件时按上面列出的顺序取第一个
+    fn death_cause(&self) -> Option<DeathCause> {
+        if self.player.y > SCREEN_HEIGHT {
+            return Some(DeathCause::FellOff);
+        }
+        if self.tunnel {
+            let (top, bottom) = tunnel_bounds(self.player.x, self.tunnel_phase);
+            if self.player.y <= top || self.player.y >= bottom {
+                return Some(DeathCause::HitTunnelWall);
+            }
+        } else if self.obstacle.hit_obstacle(&self.player) {
+            return Some(DeathCause::HitPipe);
+        }
+        if self.hardcore && self.player.y <= 0 {
+            return Some(DeathCause::HitCeiling);
+        }
+        None
+    }
+
+    /// 渲染隧道墙壁
+    ///
+    /// 屏幕上每一列对应世界坐标 `player.x + 列号`（玩家固定在第 0 列），
+    /// 逐列计算通道上下边界，边界之外用 '#' 填满
+    fn render_tunnel(&self, ctx: &mut BTerm, background: (u8, u8, u8)) {
+        for screen_x in 0..SCREEN_WIDTH {
+            let (top, bottom) = tunnel_bounds(self.player.x + screen_x, self.tunnel_phase);
+            for y in (0..=top).chain(bottom..SCREEN_HEIGHT) {
+                ctx.set(screen_x, y, GREEN, background, to_cp437('#'));
+            }
+        }
+    }
+
+    /// 重新开始游戏
+    ///
+    /// # 说明
+    ///
+    /// 重置所有游戏状态到初始值：
+    /// - 切换到游戏模式
+    /// - 重置帧时间
+    /// - 重新创建玩家
+    /// - 重新创建障碍物
+    /// - 重置分数
+    /// - 退出练习模式
+    /// - 开始新的录像：从当前随机数生成器取一个种子，本局改用它重新播种，
+    ///   并记下当前的技能评估（本局的缺口和间距都按它微调）
+    fn restart(&mut self) {
+        let seed = self.rng.next_u64();
+        self.recording = Recording {
+            seed,
+            flaps: Vec::new(),
+            skill: self.skill_estimate,
+        };
+        self.reset_run(seed);
+        self.mode = GameMode::Playing;
+    }
+
+    /// 回放上一局
+    ///
+    /// 用录像的种子重置本局，回放模式下 `tick` 会按录像拍打
+    fn start_replay(&mut self) {
+        self.reset_run(self.recording.seed);
+        self.mode = GameMode::Replay;
+    }
+
+    /// 用指定种子重置一局的全部状态（不改变模式和录像）
+    fn reset_run(&mut self, seed: u64) {
+        self.rng = RandomNumberGenerator::seeded(seed);
+        self.practice = false;
+        // 相位只由种子决定，不消耗随机数，管道模式的障碍物序列不受影响
+        self.tunnel_phase = (seed % 628) as f32 / 100.0;
+        self.frame_time = 0.0;
+        self.player = Player::new(5, 25);
+        // 先清零分数：第一根管道按 0 分生成
+        self.score = 0;
+        self.pipes_passed = 0;
+        self.death_cause = None;
+        self.spawn_obstacle(SCREEN_WIDTH);
+        self.zone = None;
+        self.combo = 0;
+        self.last_score_time_ms = 0.0;
+        self.bonus = 0;
+        self.nice_flash_ms = 0.0;
+        self.tick_count = 0;
+        self.replay_cursor = 0;
+    }
+
+    /// 以练习模式开始游戏
+    ///
+    /// 先按普通流程重置状态，再打开练习模式开关
+    fn start_practice(&mut self) {
+        self.tunnel = false;
+        self.restart();
+        self.practice = true;
+    }
+
+    /// 从主菜单开始一局
+    ///
+    /// `tunnel` 选择管道模式还是隧道模式；结束界面按 P 重开和按 W 回放都沿用本局的模式
+    fn start_game(&mut self, tunnel: bool) {
+        self.tunnel = tunnel;
+        self.restart();
+    }
+
+    /// 显示主菜单
+    ///
+    /// # 参数
+    ///
+    /// * `ctx` - BTerm 上下文
+    ///
+    /// # 说明
+    ///
+    /// 显示欢迎信息和操作提示：
+    /// - P 键开始游戏
+    /// - H 键切换硬核模式
+    /// - T 键进入练习模式
+    /// - E 键进入隧道模式
+    /// - D 键切换障碍物间距档位
+    /// - S 键切换玩家外观（立即写入存档）
+    /// - O 键进入选项界面
+    /// - Q 键退出
+    ///
+    /// 外观字符按 `title_bob_offset` 上下浮动，"(P) Play" 按 `title_prompt_visible` 闪烁；
+    /// 两者都由累积的 `ctx.frame_time_ms` 驱动，和帧率无关
+    fn main_menu(&mut self, ctx: &mut BTerm) {
+        self.menu_time_ms = (self.menu_time_ms + ctx.frame_time_ms) % TITLE_ANIMATION_PERIOD_MS;
+
+        ctx.cls();
+        ctx.print_centered(5, "welcome here");
+        if title_prompt_visible(self.menu_time_ms) {
+            ctx.print_centered(8, "(P) Play");
+        }
+        ctx.print_centered(9, "(Q) Quit");
+        let hardcore = if self.hardcore { "ON" } else { "OFF" };
+        ctx.print_centered(11, format!("(H) Hardcore: {}", hardcore));
+        ctx.print_centered(12, "(T) Practice");
+        ctx.print_centered(15, "(E) Endless tunnel");
+        let skin = &SKINS[self.skin];
+        ctx.print_centered(13, format!("(S) Skin: {} {}", skin.glyph, skin.name));
+        ctx.print_centered(14, format!("(D) Spacing: {}", self.spacing.name()));
+        ctx.print_centered(16, "(O) Options");
+        ctx.set(
+            SCREEN_WIDTH / 2,
+            19 + title_bob_offset(self.menu_time_ms),
+            skin.color,
+            BLACK,
+            to_cp437(skin.glyph),
+        );
+
+        // 处理菜单输入
+        if let Some(key) = ctx.key {
+            match key {
+                VirtualKeyCode::P => self.start_game(false),
+                VirtualKeyCode::E => self.start_game(true),
+                VirtualKeyCode::H => self.hardcore = !self.hardcore,
+                VirtualKeyCode::T => self.start_practice(),
+                VirtualKeyCode::D => self.spacing = self.spacing.next(),
+                VirtualKeyCode::O => self.mode = GameMode::Options,
+                VirtualKeyCode::S => {
+                    self.skin = (self.skin + 1) % SKINS.len();
+                    save_skin(self.skin);
+                }
+                VirtualKeyCode::Q => ctx.quitting = true,
+                _ => {}
+            }
+        }
+    }
+
+    /// 显示选项界面
+    ///
+    /// 1 / 2 / 3 键切换对应选项（立即写入选项文件），Esc 返回主菜单
+    fn options_menu(&mut self, ctx: &mut BTerm) {
+        ctx.cls();
+        ctx.print_centered(5, "Options");
+        for (row, (name, enabled)) in self.options.entries().into_iter().enumerate() {
+            let value = if *enabled { "ON" } else { "OFF" };
+            ctx.print_centered(8 + row as i32, format!("({}) {}: {}", row + 1, name, value));
+        }
+        ctx.print_centered(12, "(Esc) Back");
+
+        let toggled = match ctx.key {
+            Some(VirtualKeyCode::Key1) => Some(0),
+            Some(VirtualKeyCode::Key2) => Some(1),
+            Some(VirtualKeyCode::Key3) => Some(2),
+            Some(VirtualKeyCode::Escape) => {
+                self.mode = GameMode::Menu;
+                None
+            }
+            _ => None,
+        };
+        if let Some(index) = toggled {
+            let (_, enabled) = &mut self.options.entries()[index];
+            **enabled = !**enabled;
+            save_options(&self.options);
+        }
+    }
+
+    /// 显示死亡/游戏结束界面
+    ///
+    /// # 参数
+    ///
+    /// * `ctx` - BTerm 上下文
+    ///
+    /// # 说明
+    ///
+    /// 显示游戏结束信息、死亡原因（以及出事的是第几根管道）、最终得分和操作提示
+    fn dead(&mut self, ctx: &mut BTerm) {
+        ctx.cls();
+        ctx.print_centered(5, "You are dead");
+        if let Some(cause) = self.death_cause {
+            ctx.print_centered(6, cause.describe(self.pipes_passed + 1));
+        }
+        ctx.print_centered(7, &format!("you earned {} point", self.score));
+        if self.bonus > 0 {
+            ctx.print_centered(8, format!("including {} near-miss bonus", self.bonus));
+        }
+        ctx.print_centered(9, "(P) Play");
+        ctx.print_centered(10, "(Q) Quit");
+        ctx.print_centered(11, "(W) Watch replay");
+
+        // 处理结束界面输入
+        if let Some(key) = ctx.key {
+            match key {
+                VirtualKeyCode::P => self.restart(),
+                VirtualKeyCode::W => self.start_replay(),
+                VirtualKeyCode::Q => ctx.quitting = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 存档读写
+// ============================================================================
+
+/// 从存档内容中解析外观下标
+///
+/// 找不到 `skin=` 行、值不是数字或超出 `SKINS` 范围时都回落到默认外观 0
+fn parse_skin(contents: &str) -> usize {
+    contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("skin="))
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&skin| skin < SKINS.len())
+        .unwrap_or(0)
+}
+
+/// 读取存档中的外观；文件不存在或读取失败时使用默认外观
+fn load_skin() -> usize {
+    fs::read_to_string(SAVE_FILE)
+        .map(|contents| parse_skin(&contents))
+        .unwrap_or(0)
+}
+
+/// 从按键配置内容中解析拍打键
+///
+/// 没有 `flap=` 行时返回空格；键名不在 `KEY_NAMES` 中时返回错误信息
+fn parse_flap_key(contents: &str) -> Result<VirtualKeyCode, String> {
+    let Some(name) = contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("flap="))
+        .map(str::trim)
+    else {
+        return Ok(VirtualKeyCode::Space);
+    };
+    KEY_NAMES
+        .iter()
+        .find(|(key_name, _)| key_name.eq_ignore_ascii_case(name))
+        .map(|&(_, key)| key)
+        .ok_or_else(|| format!("unknown flap key {:?}", name))
+}
+
+/// 读取按键配置中的拍打键
+///
+/// 文件不存在时静默使用空格；键名写错时打印提示并使用空格，不影响游戏启动
+fn load_flap_key() -> VirtualKeyCode {
+    let Ok(contents) = fs::read_to_string(KEYS_FILE) else {
+        return VirtualKeyCode::Space;
+    };
+    parse_flap_key(&contents).unwrap_or_else(|err| {
+        eprintln!("{} in {}, using Space", err, KEYS_FILE);
+        VirtualKeyCode::Space
+    })
+}
+
+/// 从选项文件内容中解析显示选项
+///
+/// 每个选项独立解析：缺失或值不是 `on` / `off` 的选项保持默认值
+fn parse_options(contents: &str) -> Options {
+    let mut options = Options::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+        let value = match value.trim() {
+            "on" => true,
+            "off" => false,
+            _ => continue,
+        };
+        if let Some((_, enabled)) = options
+            .entries()
+            .into_iter()
+            .find(|(name, _)| *name == key.trim())
+        {
+            *enabled = value;
+        }
+    }
+    options
+}
+
+/// 把显示选项格式化成选项文件内容（每个选项一行）
+fn format_options(options: &Options) -> String {
+    let mut options = *options;
+    options
+        .entries()
+        .into_iter()
+        .map(|(name, enabled)| format!("{}={}\n", name, if *enabled { "on" } else { "off" }))
+        .collect()
+}
+
+/// 读取选项文件；文件不存在或读取失败时使用默认选项
+fn load_options() -> Options {
+    fs::read_to_string(OPTIONS_FILE)
+        .map(|contents| parse_options(&contents))
+        .unwrap_or_default()
+}
+
+/// 把显示选项写入选项文件；写入失败只打印提示，不影响游戏
+fn save_options(options: &Options) {
+    if let Err(err) = fs::write(OPTIONS_FILE, format_options(options)) {
+        eprintln!("failed to save {}: {}", OPTIONS_FILE, err);
+    }
+}
+
+/// 从技能评估文件内容中解析技能评估
+///
+/// 找不到 `skill=` 行或值不是有限的数字时回落到中性的 0.0，超出范围的值截断到上下限
+fn parse_skill(contents: &str) -> f32 {
+    contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("skill="))
+        .and_then(|value| value.trim().parse::<f32>().ok())
+        .filter(|skill| skill.is_finite())
+        .map_or(0.0, |skill| skill.clamp(-SKILL_LIMIT, SKILL_LIMIT))
+}
+
+/// 读取技能评估文件；文件不存在或读取失败时为中性的 0.0
+fn load_skill() -> f32 {
+    fs::read_to_string(SKILL_FILE)
+        .map(|contents| parse_skill(&contents))
+        .unwrap_or(0.0)
+}
+
+/// 把技能评估写入文件；写入失败只打印提示，不影响游戏
+fn save_skill(skill: f32) {
+    if let Err(err) = fs::write(SKILL_FILE, format!("skill={:.3}\n", skill)) {
+        eprintln!("failed to save {}: {}", SKILL_FILE, err);
+    }
+}
+
+// ============================================================================
+// 分数上报
+// ============================================================================
+
+/// 解析分数上报地址：没有设置或只有空白时返回 None（不上报）
+fn parse_score_endpoint(value: Option<String>) -> Option<String> {
+    value
+        .map(|endpoint| endpoint.trim().to_string())
+        .filter(|endpoint| !endpoint.is_empty())
+}
+
+/// 上报用的玩家名称：读取自 `PLAYER_NAME`，没有设置或只有空白时为 `DEFAULT_PLAYER_NAME`
+fn player_name() -> String {
+    std::env::var(PLAYER_NAME_VAR)
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_PLAYER_NAME.to_string())
+}
+
+/// 在后台线程里把 `{name, score}` POST 到 `endpoint`
+///
+/// 阻塞式 HTTP 客户端放在单独的线程里，不会卡住游戏循环；
+/// 服务没启动、超时或返回错误都只打印提示，不影响游戏
+fn submit_score(endpoint: String, name: String, score: i32) {
+    thread::spawn(move || {
+        let result = ureq::post(&endpoint)
+            .timeout(Duration::from_secs(SCORE_SUBMIT_TIMEOUT_SECS))
+            .send_json(ureq::json!({ "name": name, "score": score }));
+        if let Err(err) = result {
+            eprintln!("failed to submit score to {}: {}", endpoint, err);
+        }
+    });
+}
+
+/// 把外观写入存档；写入失败只打印提示，不影响游戏
+fn save_skin(skin: usize) {
+    if let Err(err) = fs::write(SAVE_FILE, format!("skin={}\n", skin)) {
+        eprintln!("failed to save {}: {}", SAVE_FILE, err);
+    }
+}
+
+// ============================================================================
+// 连击计算
+// ============================================================================
+
+/// 计算穿过管道后的新连击倍数
+///
+/// # 参数
+///
+/// * `combo` - 当前连击倍数
+/// * `since_last_score_ms` - 距离上次得分经过的时间（毫秒）
+/// * `player_y` - 穿过管道时玩家的 y 坐标
+///
+/// # 规则
+///
+/// - 在时间窗口内、且飞行高度高于阈值：倍数 +1，最多 `MAX_COMBO`
+/// - 否则连击中断，从 x1 重新开始
+fn next_combo(combo: i32, since_last_score_ms: f32, player_y: i32) -> i32 {
+    if since_last_score_ms <= COMBO_WINDOW_MS && player_y < COMBO_FLOOR_Y {
+        i32::min(combo + 1, MAX_COMBO)
+    } else {
+        1
+    }
+}
+
+// ============================================================================
+// 主菜单动画
+// ============================================================================
+
+/// 主菜单外观字符在相位 `time_ms` 时的纵向偏移
+///
+/// 一个 `TITLE_ANIMATION_PERIOD_MS` 周期内做一次完整的正弦摆动，
+/// 取整后落在 `[-TITLE_BOB_AMPLITUDE, TITLE_BOB_AMPLITUDE]` 内
+fn title_bob_offset(time_ms: f32) -> i32 {
+    let angle = time_ms / TITLE_ANIMATION_PERIOD_MS * std::f32::consts::TAU;
+    (angle.sin() * TITLE_BOB_AMPLITUDE).round() as i32
+}
+
+/// "(P) Play" 在相位 `time_ms` 时是否显示：每个周期前半段显示、后半段隐藏
+fn title_prompt_visible(time_ms: f32) -> bool {
+    time_ms.rem_euclid(TITLE_ANIMATION_PERIOD_MS) < TITLE_ANIMATION_PERIOD_MS / 2.0
+}
+
+// ============================================================================
+// 隧道通道
+// ============================================================================
+
+/// 隧道模式下世界坐标 `world_x` 处通道的上下边界 `(top, bottom)`
+///
+/// # 参数
+///
+/// * `world_x` - 世界 x 坐标
+/// * `phase` - 波形相位（每局不同，见 `State::reset_run`）
+///
+/// # 规则
+///
+/// - 通道高度：从 `TUNNEL_START_GAP` 开始，每 `TUNNEL_SHRINK_DISTANCE` 格收窄 1 格，最小 `TUNNEL_MIN_GAP`
+/// - 通道中线：两个不同频率的正弦叠加，摆动幅度从 0 逐渐增大到 `TUNNEL_MAX_AMPLITUDE`，
+///   所以开局时通道就在屏幕正中，玩家不会一出生就撞墙
+/// - 中线会被限制在屏幕内，保证 `0 <= top` 且 `bottom <= SCREEN_HEIGHT - 1`
+///
+/// 玩家的 y 必须满足 `top < y < bottom` 才算在通道内
+fn tunnel_bounds(world_x: i32, phase: f32) -> (i32, i32) {
+    let distance = world_x.max(0);
+    let gap = i32::max(
+        TUNNEL_MIN_GAP,
+        TUNNEL_START_GAP - distance / TUNNEL_SHRINK_DISTANCE,
+    );
+
+    let x = distance as f32;
+    let amplitude = f32::min(TUNNEL_MAX_AMPLITUDE, x / 20.0);
+    let wave = (x * 0.05 + phase).sin() * 0.7 + (x * 0.13 + phase * 2.0).sin() * 0.3;
+    let center = (SCREEN_HEIGHT / 2) as f32 + amplitude * wave;
+
+    let top = (center as i32 - gap / 2).clamp(0, SCREEN_HEIGHT - 1 - gap);
+    (top, top + gap)
+}
+
+// ============================================================================
+// 难度曲线
+// ============================================================================
+
+/// 某个分数下的难度参数
+///
+/// 集中管理所有随分数变化的数值，`play` 和 `Obstacle::new` 都从这里读取
+struct Difficulty {
+    /// 障碍物缺口大小，最小为 2
+    gap_size: i32,
+    /// 新障碍物与玩家之间的水平距离
+    obstacle_spacing: i32,
+    /// 滚动速度：玩家前进 1 格所需的毫秒数，越小越快
+    frame_duration: f32,
+    /// 背景颜色
+    background: (u8, u8, u8),
+}
+
+/// 根据分数计算难度参数
+///
+/// # 规则
+///
+/// - 缺口大小：max(2, 20 - score)
+/// - 障碍物间距：从一个屏幕宽度开始，每得 1 分缩短 1 格，最短半个屏幕宽度
+///   （这是 `Spacing::Normal` 下的值，实际生成时再按档位缩放，见 `State::obstacle_spacing`）
+/// - 滚动速度：从 `FRAME_DURATION` 开始，每得 1 分快 1 毫秒，最快 `MIN_FRAME_DURATION`
+/// - 背景颜色：每 `PALETTE_STEP` 分在 `PALETTE` 中循环切换
+fn difficulty_for(score: i32) -> Difficulty {
+    let score = score.max(0);
+    Difficulty {
+        gap_size: i32::max(2, 20 - score),
+        obstacle_spacing: i32::max(SCREEN_WIDTH / 2, SCREEN_WIDTH - score),
+        frame_duration: f32::max(MIN_FRAME_DURATION, FRAME_DURATION - score as f32),
+        background: PALETTE[(score / PALETTE_STEP) as usize % PALETTE.len()],
+    }
+}
+
+// ============================================================================
+// 自适应难度
+// ============================================================================
+
+/// 根据刚结束的一局更新技能评估
+///
+/// # 规则
+///
+/// - 本局表现：0 分记为 -1.0，`SKILL_SMOOTH_SCORE` 分及以上记为 1.0，中间线性插值
+/// - 新评估 = 旧评估向本局表现靠拢 `SKILL_LEARNING_RATE`（指数滑动平均，最近几局影响最大）
+/// - 结果始终在 [-SKILL_LIMIT, SKILL_LIMIT] 内
+///
+/// 连续早早撞死会让评估逐渐变负（更简单），连续顺畅的局会让它逐渐变正（更难），
+/// 单独一局的起伏只会带来很小的变化
+fn next_skill_estimate(skill: f32, score: i32) -> f32 {
+    let performance =
+        (2.0 * score.max(0) as f32 / SKILL_SMOOTH_SCORE as f32 - 1.0).min(SKILL_LIMIT);
+    (skill + (performance - skill) * SKILL_LEARNING_RATE).clamp(-SKILL_LIMIT, SKILL_LIMIT)
+}
+
+/// 按技能评估微调缺口大小
+///
+/// 评估为负时放宽、为正时收窄，最多 `SKILL_GAP_ADJUST` 格，且不小于难度曲线的下限 2
+fn adapt_gap_size(gap_size: i32, skill: f32) -> i32 {
+    let adjust = (skill.clamp(-SKILL_LIMIT, SKILL_LIMIT) * SKILL_GAP_ADJUST as f32).round() as i32;
+    i32::max(2, gap_size - adjust)
+}
+
+/// 按技能评估微调管道间距
+///
+/// 评估为负时拉长（留更多反应时间）、为正时缩短，最多 `SKILL_SPACING_ADJUST` 格；
+/// 之后还会按间距档位缩放并受 `MIN_OBSTACLE_SPACING` 限制（见 `Spacing::apply`）
+fn adapt_obstacle_spacing(spacing: i32, skill: f32) -> i32 {
+    let adjust =
+        (skill.clamp(-SKILL_LIMIT, SKILL_LIMIT) * SKILL_SPACING_ADJUST as f32).round() as i32;
+    spacing - adjust
+}
+
+// ============================================================================
+// GameState trait 实现
+// ============================================================================
 
 /// 为 State 实现 bracket-lib 的 GameState trait
 ///
@@ -457,13 +1806,16 @@ impl GameState for State {
     ///
     /// 使用 match 表达式根据当前游戏模式分发到对应处理函数：
     /// - Menu -> main_menu(): 显示主菜单
+    /// - Options -> options_menu(): 显示选项界面
     /// - Playing -> play(): 执行游戏逻辑
     /// - End -> dead(): 显示结束界面
+    /// - Replay -> play(): 与游戏共用逻辑和渲染，拍打来自录像
     fn tick(&mut self, ctx: &mut BTerm) {
         match self.mode {
             GameMode::Menu => self.main_menu(ctx),
+            GameMode::Options => self.options_menu(ctx),
             GameMode::End => self.dead(ctx),
-            GameMode::Playing => self.play(ctx),
+            GameMode::Playing | GameMode::Replay => self.play(ctx),
         }
     }
 }
@@ -472,8 +1824,36 @@ impl GameState for State {
 // 程序入口
 // ============================================================================
 
+/// 无窗口基准测试：`cargo run --release -- --bench [步数]`
+///
+/// 用固定种子反复开局，按"低于缺口就拍打"的简单策略调用 `State::step_headless`，
+/// 死亡后立即重开，最后打印总步数、局数和每秒步数
+fn run_benchmark(steps: u64) {
+    let mut state = State::with_rng(RandomNumberGenerator::seeded(0));
+    state.restart();
+    let mut runs = 1;
+    let start = Instant::now();
+    for _ in 0..steps {
+        let flap = state.player.y > state.obstacle.gap_y;
+        if !state.step_headless(16.0, flap) {
+            state.restart();
+            runs += 1;
+        }
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{} steps, {} runs in {:?} ({:.0} steps/s)",
+        steps,
+        runs,
+        elapsed,
+        steps as f64 / elapsed.as_secs_f64()
+    );
+}
+
 /// 程序主入口
 ///
+/// 带 `--bench` 参数时只跑无窗口基准测试（见 `run_benchmark`），不创建窗口
+///
 /// # 返回值
 ///
 /// 返回 BError，bracket-lib 的错误类型
@@ -491,6 +1871,16 @@ impl GameState for State {
 /// 2. 处理窗口事件（关闭、调整大小等）
 /// 3. 管理渲染和输入
 fn main() -> BError {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("--bench") {
+        let steps = args
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(1_000_000);
+        run_benchmark(steps);
+        return Ok(());
+    }
+
     println!("Hello, world!");
 
     // 创建游戏窗口
@@ -501,3 +1891,703 @@ fn main() -> BError {
     // 启动游戏主循环
     main_loop(context, State::new())
 }
+
+// ============================================================================
+// 单元测试
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试统一使用的固定随机种子
+    const SEED: u64 = 42;
+
+    /// 构造一个障碍物远在右侧、玩家位于指定高度的游戏状态
+    fn state_with_player_at(y: i32, hardcore: bool) -> State {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        state.player = Player::new(5, y);
+        state.obstacle.x = SCREEN_WIDTH;
+        state.hardcore = hardcore;
+        state
+    }
+
+    #[test]
+    fn ceiling_is_safe_in_normal_mode() {
+        assert!(state_with_player_at(0, false).death_cause().is_none());
+    }
+
+    #[test]
+    fn ceiling_kills_in_hardcore_mode() {
+        assert!(state_with_player_at(0, true).death_cause().is_some());
+        assert!(state_with_player_at(1, true).death_cause().is_none());
+    }
+
+    #[test]
+    fn falling_off_bottom_kills_in_both_modes() {
+        assert!(state_with_player_at(SCREEN_HEIGHT + 1, false)
+            .death_cause()
+            .is_some());
+        assert!(state_with_player_at(SCREEN_HEIGHT + 1, true)
+            .death_cause()
+            .is_some());
+    }
+
+    #[test]
+    fn death_cause_distinguishes_falling_from_hitting_a_pipe() {
+        let mut state = state_with_player_at(SCREEN_HEIGHT + 1, false);
+        state.mode = GameMode::Playing;
+        state.check_progress();
+        assert!(matches!(state.mode, GameMode::End));
+        assert_eq!(state.death_cause, Some(DeathCause::FellOff));
+
+        // 玩家和管道在同一列、位于缺口之外
+        let mut state = state_with_player_at(5, false);
+        state.mode = GameMode::Playing;
+        state.obstacle = obstacle_at(state.player.x, 30, 4);
+        assert!(state.obstacle.hit_obstacle(&state.player));
+        state.pipes_passed = 2;
+        state.check_progress();
+        assert!(matches!(state.mode, GameMode::End));
+        assert_eq!(state.death_cause, Some(DeathCause::HitPipe));
+        assert_eq!(
+            state.death_cause.unwrap().describe(state.pipes_passed + 1),
+            "You hit pipe #3"
+        );
+
+        assert_eq!(
+            state_with_player_at(0, true).death_cause(),
+            Some(DeathCause::HitCeiling)
+        );
+        assert_eq!(state_with_player_at(25, false).death_cause(), None);
+
+        // 重开之后清空
+        state.restart();
+        assert_eq!(state.death_cause, None);
+        assert_eq!(state.pipes_passed, 0);
+    }
+
+    #[test]
+    fn skin_parses_from_save_file() {
+        assert_eq!(parse_skin("skin=2\n"), 2);
+        assert_eq!(parse_skin("other=1\n  skin= 1 \n"), 1);
+        // 缺失、不是数字、超出范围 → 默认外观
+        assert_eq!(parse_skin(""), 0);
+        assert_eq!(parse_skin("skin=abc"), 0);
+        assert_eq!(parse_skin(&format!("skin={}", SKINS.len())), 0);
+    }
+
+    #[test]
+    fn flap_key_parses_from_keys_file() {
+        assert_eq!(parse_flap_key(""), Ok(VirtualKeyCode::Space));
+        assert_eq!(parse_flap_key("flap=Up\n"), Ok(VirtualKeyCode::Up));
+        assert_eq!(
+            parse_flap_key("other=1\n  flap= w \n"),
+            Ok(VirtualKeyCode::W)
+        );
+        // 保留键、不认识的键名 → 报错（load_flap_key 会回落到空格）
+        assert!(parse_flap_key("flap=Escape").is_err());
+        assert_eq!(
+            parse_flap_key("flap=Banana"),
+            Err("unknown flap key \"Banana\"".to_string())
+        );
+    }
+
+    #[test]
+    fn options_round_trip_through_file_format() {
+        // 8 种组合都能原样读回
+        for bits in 0..8 {
+            let options = Options {
+                screen_flash: bits & 1 != 0,
+                show_fps: bits & 2 != 0,
+                parallax: bits & 4 != 0,
+            };
+            assert_eq!(parse_options(&format_options(&options)), options);
+        }
+        // 空文件、无法识别的行和值 → 对应选项保持默认
+        assert_eq!(parse_options(""), Options::default());
+        assert_eq!(
+            parse_options("junk\nshow_fps=maybe\n parallax = off \n"),
+            Options {
+                parallax: false,
+                ..Options::default()
+            }
+        );
+    }
+
+    #[test]
+    fn title_animation_is_bounded_and_periodic() {
+        let amplitude = TITLE_BOB_AMPLITUDE.round() as i32;
+        let mut seen = std::collections::BTreeSet::new();
+        for ms in (0..TITLE_ANIMATION_PERIOD_MS as i32).step_by(10) {
+            let offset = title_bob_offset(ms as f32);
+            assert!((-amplitude..=amplitude).contains(&offset));
+            seen.insert(offset);
+        }
+        // 一个周期内确实上下都动过
+        assert_eq!(seen.first(), Some(&-amplitude));
+        assert_eq!(seen.last(), Some(&amplitude));
+        assert_eq!(title_bob_offset(0.0), 0);
+
+        // 闪烁：前半周期显示、后半周期隐藏，下一个周期重复
+        assert!(title_prompt_visible(0.0));
+        assert!(!title_prompt_visible(TITLE_ANIMATION_PERIOD_MS * 0.75));
+        assert!(title_prompt_visible(TITLE_ANIMATION_PERIOD_MS * 1.25));
+    }
+
+    #[test]
+    fn combo_progresses_and_resets() {
+        // (距上次得分的时间, 玩家高度) → 期望倍数
+        let events = [
+            (3000.0, 25, 1),
+            (6000.0, 25, 2),
+            (6000.0, 25, 3),
+            (6000.0, 25, 3),                // 封顶 x3
+            (COMBO_WINDOW_MS + 1.0, 25, 1), // 太慢，中断
+            (6000.0, 25, 2),
+            (6000.0, COMBO_FLOOR_Y, 1), // 飞得太低，中断
+        ];
+
+        let mut combo = 0;
+        for (elapsed, y, expected) in events {
+            combo = next_combo(combo, elapsed, y);
+            assert_eq!(combo, expected);
+        }
+    }
+
+    #[test]
+    fn practice_mode_never_ends_and_never_scores() {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        state.start_practice();
+
+        // 撞进管道：直接穿过
+        state.player = Player::new(SCREEN_WIDTH, 0);
+        state.obstacle = Obstacle::new(SCREEN_WIDTH, 0, &mut state.rng);
+        state.obstacle.gap_y = SCREEN_HEIGHT;
+        assert!(state.obstacle.hit_obstacle(&state.player));
+        state.check_progress();
+        assert!(matches!(state.mode, GameMode::Playing));
+
+        // 穿过管道：不计分
+        state.player.x += 1;
+        state.check_progress();
+        assert_eq!(state.score, 0);
+
+        // 掉出底部：回到屏幕中间
+        state.player.y = SCREEN_HEIGHT + 1;
+        state.check_progress();
+        assert!(matches!(state.mode, GameMode::Playing));
+        assert_eq!(state.player.y, SCREEN_HEIGHT / 2);
+
+        // 普通重新开始会清除练习模式
+        state.restart();
+        assert!(!state.practice);
+    }
+
+    /// 模拟 `play` 中的一次逻辑更新（不含渲染和输入）
+    fn step(state: &mut State) {
+        state.player.gravity_and_move(state.zone_force());
+        state.obstacle.advance();
+        state.check_progress();
+    }
+
+    /// 构造一个缺口固定的障碍物
+    fn obstacle_at(x: i32, gap_y: i32, size: i32) -> Obstacle {
+        Obstacle {
+            x,
+            gap_y,
+            size,
+            speed: 0,
+        }
+    }
+
+    #[test]
+    fn obstacle_size_shrinks_but_is_clamped_to_two() {
+        let mut rng = RandomNumberGenerator::seeded(SEED);
+        assert_eq!(Obstacle::new(0, 0, &mut rng).size, 20);
+        assert_eq!(Obstacle::new(0, 10, &mut rng).size, 10);
+        assert_eq!(Obstacle::new(0, 18, &mut rng).size, 2);
+        assert_eq!(Obstacle::new(0, 100, &mut rng).size, 2);
+        assert_eq!(Obstacle::new(0, i32::MAX / 2, &mut rng).size, 2);
+    }
+
+    #[test]
+    fn obstacle_gap_stays_in_range() {
+        let mut rng = RandomNumberGenerator::seeded(SEED);
+        for score in 0..1000 {
+            let gap_y = Obstacle::new(0, score, &mut rng).gap_y;
+            assert!((10..50).contains(&gap_y), "gap_y = {}", gap_y);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_obstacles() {
+        let mut a = RandomNumberGenerator::seeded(SEED);
+        let mut b = RandomNumberGenerator::seeded(SEED);
+        for score in 0..20 {
+            assert_eq!(
+                Obstacle::new(0, score, &mut a).gap_y,
+                Obstacle::new(0, score, &mut b).gap_y
+            );
+        }
+    }
+
+    #[test]
+    fn hit_obstacle_only_outside_gap() {
+        // 缺口中心 25，大小 10 → 安全区间 [20, 30]
+        let obstacle = obstacle_at(10, 25, 10);
+
+        for y in 20..=30 {
+            assert!(!obstacle.hit_obstacle(&Player::new(10, y)), "y = {}", y);
+        }
+        assert!(obstacle.hit_obstacle(&Player::new(10, 19)));
+        assert!(obstacle.hit_obstacle(&Player::new(10, 0)));
+        assert!(obstacle.hit_obstacle(&Player::new(10, 31)));
+        assert!(obstacle.hit_obstacle(&Player::new(10, SCREEN_HEIGHT)));
+
+        // x 不重叠时永远不会撞到
+        assert!(!obstacle.hit_obstacle(&Player::new(9, 0)));
+        assert!(!obstacle.hit_obstacle(&Player::new(11, 0)));
+    }
+
+    #[test]
+    fn passing_obstacle_increments_score() {
+        let mut state = state_with_player_at(25, false);
+        state.mode = GameMode::Playing;
+        state.obstacle = obstacle_at(8, 25, 20);
+
+        // 玩家从 x=5 出发，每步前进 1 格，第 4 步越过 x=8
+        for _ in 0..3 {
+            step(&mut state);
+            state.player.flap();
+            assert_eq!(state.score, 0);
+        }
+        step(&mut state);
+
+        assert!(matches!(state.mode, GameMode::Playing));
+        assert_eq!(state.score, 1);
+        assert_eq!(
+            state.obstacle.x,
+            state.player.x + difficulty_for(1).obstacle_spacing
+        );
+    }
+
+    #[test]
+    fn gap_edge_distance_at_edges_and_outside() {
+        // 缺口中心 25，大小 10 → 安全区间 [20, 30]
+        let obstacle = obstacle_at(10, 25, 10);
+
+        assert_eq!(obstacle.gap_edge_distance(20), 0);
+        assert_eq!(obstacle.gap_edge_distance(30), 0);
+        assert_eq!(obstacle.gap_edge_distance(21), 1);
+        assert_eq!(obstacle.gap_edge_distance(29), 1);
+        assert_eq!(obstacle.gap_edge_distance(25), 5);
+        assert_eq!(obstacle.gap_edge_distance(19), -1);
+        assert_eq!(obstacle.gap_edge_distance(31), -1);
+
+        assert!(obstacle.is_near_miss(20));
+        assert!(obstacle.is_near_miss(29));
+        assert!(!obstacle.is_near_miss(22));
+        assert!(!obstacle.is_near_miss(19));
+
+        // 最小缺口（大小 2）：安全区间 [24, 26]，每一格都算擦边
+        let narrow = obstacle_at(10, 25, 2);
+        assert!((24..=26).all(|y| narrow.is_near_miss(y)));
+        assert!(!narrow.is_near_miss(23));
+    }
+
+    #[test]
+    fn near_miss_awards_bonus_point() {
+        let mut state = state_with_player_at(20, false);
+        state.mode = GameMode::Playing;
+        // 玩家贴着缺口上边缘（y=20）越过管道
+        state.obstacle = obstacle_at(4, 25, 10);
+        state.check_progress();
+
+        assert_eq!(state.score, 2);
+        assert_eq!(state.bonus, 1);
+        assert_eq!(state.nice_flash_ms, NICE_FLASH_MS);
+
+        state.restart();
+        assert_eq!(state.bonus, 0);
+    }
+
+    /// 不经过渲染，一直推进到本局结束（或达到帧数上限）
+    ///
+    /// `should_flap` 决定每个逻辑帧之前是否拍打，只在正式游戏中调用
+    fn run_to_end(state: &mut State, should_flap: impl Fn(&State) -> bool) {
+        while !matches!(state.mode, GameMode::End) && state.tick_count < 10_000 {
+            if matches!(state.mode, GameMode::Playing) && should_flap(state) {
+                state.flap();
+            }
+            state.tick();
+            state.check_progress();
+        }
+    }
+
+    /// 用固定的拍打策略无渲染地跑一局，返回每一步之后的 (得分, 玩家 x, 玩家 y, 障碍物 x, 缺口 y)
+    fn headless_trace(seed: u64, steps: usize) -> Vec<(i32, i32, i32, i32, i32)> {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(seed));
+        state.restart();
+        let mut trace = Vec::with_capacity(steps);
+        for step in 0..steps {
+            // 60 FPS 左右但带一点抖动的帧时间，验证累积器也是确定的
+            let dt_ms = 16.0 + (step % 3) as f32;
+            let flap = state.player.y > state.obstacle.gap_y && state.player.velocity >= 0.0;
+            let alive = state.step_headless(dt_ms, flap);
+            trace.push((
+                state.score,
+                state.player.x,
+                state.player.y,
+                state.obstacle.x,
+                state.obstacle.gap_y,
+            ));
+            if !alive {
+                break;
+            }
+        }
+        trace
+    }
+
+    #[test]
+    fn headless_simulation_is_reproducible() {
+        let first = headless_trace(SEED, 20_000);
+        let second = headless_trace(SEED, 20_000);
+        assert_eq!(first, second);
+        // 跟着缺口拍打能穿过管道，这一局确实跑出了分数
+        assert!(first.last().unwrap().0 > 0);
+
+        // 换一个种子障碍物就不同
+        assert_ne!(first, headless_trace(SEED + 1, 20_000));
+    }
+
+    #[test]
+    fn replay_reproduces_recorded_run() {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        state.restart();
+
+        // 简单的自动驾驶：低于缺口中心就拍打
+        run_to_end(&mut state, |s| s.player.y > s.obstacle.gap_y);
+        assert!(matches!(state.mode, GameMode::End));
+        assert!(state.score > 0, "score = {}", state.score);
+        let recorded = (
+            state.score,
+            state.bonus,
+            state.tick_count,
+            state.player.x,
+            state.player.y,
+        );
+
+        state.start_replay();
+        assert_eq!(state.score, 0);
+        run_to_end(&mut state, |_| false);
+        assert_eq!(
+            (
+                state.score,
+                state.bonus,
+                state.tick_count,
+                state.player.x,
+                state.player.y
+            ),
+            recorded
+        );
+
+        // Esc 快进得到同样的结果
+        state.start_replay();
+        state.skip_replay();
+        assert_eq!(state.score, recorded.0);
+        assert_eq!(state.tick_count, recorded.2);
+    }
+
+    #[test]
+    fn flaps_are_recorded_once_per_tick() {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        state.restart();
+        state.flap();
+        state.flap();
+        state.tick();
+        state.flap();
+        assert_eq!(state.recording.flaps, vec![0, 1]);
+    }
+
+    #[test]
+    fn difficulty_gap_never_below_two_and_spacing_monotonic() {
+        let mut previous = difficulty_for(0);
+        assert_eq!(previous.gap_size, 20);
+        assert_eq!(previous.obstacle_spacing, SCREEN_WIDTH);
+        assert_eq!(previous.frame_duration, FRAME_DURATION);
+
+        for score in 1..500 {
+            let current = difficulty_for(score);
+            assert!(current.gap_size >= 2);
+            assert!(current.gap_size <= previous.gap_size);
+            assert!(current.obstacle_spacing <= previous.obstacle_spacing);
+            assert!(current.obstacle_spacing >= SCREEN_WIDTH / 2);
+            assert!(current.frame_duration <= previous.frame_duration);
+            assert!(current.frame_duration >= MIN_FRAME_DURATION);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn successive_spawns_respect_spacing_preset() {
+        for spacing in [Spacing::Close, Spacing::Normal, Spacing::Far] {
+            let mut state = state_with_player_at(25, false);
+            state.mode = GameMode::Playing;
+            state.spacing = spacing;
+
+            // 每次把玩家放到刚越过当前管道的位置、停在缺口中，触发生成下一根
+            for _ in 0..100 {
+                let previous_x = state.obstacle.x;
+                state.player.x = previous_x + state.obstacle.speed + 1;
+                state.player.y = state.obstacle.gap_y;
+                state.check_progress();
+
+                assert!(matches!(state.mode, GameMode::Playing));
+                let gap = state.obstacle.x - previous_x;
+                assert!(gap > MIN_OBSTACLE_SPACING, "{:?}: gap {}", spacing, gap);
+                assert_eq!(state.obstacle.x - state.player.x, state.obstacle_spacing());
+            }
+        }
+
+        let base = difficulty_for(0).obstacle_spacing;
+        assert!(Spacing::Close.apply(base) < Spacing::Normal.apply(base));
+        assert!(Spacing::Normal.apply(base) < Spacing::Far.apply(base));
+        assert_eq!(Spacing::Normal.apply(base), base);
+        assert_eq!(Spacing::Close.apply(0), MIN_OBSTACLE_SPACING);
+    }
+
+    #[test]
+    fn early_deaths_make_the_game_easier() {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        assert_eq!(state.skill_estimate, 0.0);
+
+        // 连续几局一下都不拍，很快掉出底部
+        let mut previous = state.skill_estimate;
+        for _ in 0..4 {
+            state.restart();
+            run_to_end(&mut state, |_| false);
+            assert_eq!(state.score, 0);
+            assert!(state.skill_estimate < previous, "{}", state.skill_estimate);
+            previous = state.skill_estimate;
+        }
+
+        // 下一局的缺口更宽、间距更长
+        state.restart();
+        assert!(state.obstacle.size > difficulty_for(0).gap_size);
+        assert!(state.obstacle_spacing() > difficulty_for(0).obstacle_spacing);
+
+        // 回放沿用开局时的评估，即使评估在局后又变了
+        let size = state.obstacle.size;
+        run_to_end(&mut state, |_| false);
+        state.start_replay();
+        assert_eq!(state.obstacle.size, size);
+    }
+
+    #[test]
+    fn skill_estimate_stays_bounded() {
+        let mut skill = 0.0;
+        for _ in 0..1000 {
+            skill = next_skill_estimate(skill, 0);
+        }
+        assert!(skill >= -SKILL_LIMIT);
+        assert!(adapt_gap_size(20, skill) <= 20 + SKILL_GAP_ADJUST);
+
+        for _ in 0..1000 {
+            skill = next_skill_estimate(skill, i32::MAX);
+        }
+        assert!(skill <= SKILL_LIMIT);
+        assert!(skill > 0.9);
+        assert_eq!(adapt_gap_size(2, skill), 2);
+        assert_eq!(adapt_gap_size(20, skill), 20 - SKILL_GAP_ADJUST);
+
+        // 中性评估不改变难度曲线；一半的顺畅分数是中性表现
+        assert_eq!(adapt_gap_size(20, 0.0), 20);
+        assert_eq!(adapt_obstacle_spacing(SCREEN_WIDTH, 0.0), SCREEN_WIDTH);
+        assert_eq!(next_skill_estimate(0.0, SKILL_SMOOTH_SCORE / 2), 0.0);
+
+        // 文件内容坏掉时回落到中性，超出范围时截断
+        assert_eq!(parse_skill("skill=-0.375\n"), -0.375);
+        assert_eq!(parse_skill("skill=7"), SKILL_LIMIT);
+        assert_eq!(parse_skill("skill=NaN"), 0.0);
+        assert_eq!(parse_skill(""), 0.0);
+    }
+
+    #[test]
+    fn score_endpoint_is_optional() {
+        assert_eq!(parse_score_endpoint(None), None);
+        assert_eq!(parse_score_endpoint(Some("   ".into())), None);
+        assert_eq!(
+            parse_score_endpoint(Some(" http://127.0.0.1:3339/scores\n".into())),
+            Some("http://127.0.0.1:3339/scores".to_string())
+        );
+
+        // 测试用的状态从不上报
+        assert_eq!(
+            State::with_rng(RandomNumberGenerator::seeded(1)).score_endpoint,
+            None
+        );
+    }
+
+    #[test]
+    fn tunnel_always_leaves_minimum_gap() {
+        for phase in [0.0, 1.5, 3.0, 6.27] {
+            for world_x in -10..20_000 {
+                let (top, bottom) = tunnel_bounds(world_x, phase);
+                assert!(
+                    top >= 0 && bottom < SCREEN_HEIGHT,
+                    "x={}: {:?}",
+                    world_x,
+                    (top, bottom)
+                );
+                assert!(
+                    bottom - top >= TUNNEL_MIN_GAP,
+                    "x={}: {:?}",
+                    world_x,
+                    (top, bottom)
+                );
+            }
+        }
+        // 最终收窄到最小高度
+        let (top, bottom) = tunnel_bounds(1_000_000, 0.0);
+        assert_eq!(bottom - top, TUNNEL_MIN_GAP);
+    }
+
+    #[test]
+    fn tunnel_run_scores_by_distance() {
+        let mut state = State::with_rng(RandomNumberGenerator::seeded(SEED));
+        state.start_game(true);
+
+        // 出生点一定在通道内
+        assert!(state.death_cause().is_none());
+
+        // 每帧把玩家放回通道中间，只验证计分
+        for _ in 0..TUNNEL_SCORE_DISTANCE * 3 {
+            state.tick();
+            let (top, bottom) = tunnel_bounds(state.player.x, state.tunnel_phase);
+            state.player.y = (top + bottom) / 2;
+            state.check_progress();
+            assert!(matches!(state.mode, GameMode::Playing));
+        }
+        assert_eq!(state.score, 3);
+
+        // 贴到上墙 → 死亡
+        state.player.y = tunnel_bounds(state.player.x, state.tunnel_phase).0;
+        state.check_progress();
+        assert!(matches!(state.mode, GameMode::End));
+    }
+
+    #[test]
+    fn palette_shifts_every_step() {
+        assert_eq!(difficulty_for(0).background, NAVY);
+        assert_eq!(difficulty_for(PALETTE_STEP - 1).background, NAVY);
+        assert_eq!(difficulty_for(PALETTE_STEP).background, PURPLE);
+        assert_eq!(difficulty_for(PALETTE_STEP * 2).background, MAROON);
+        assert_eq!(difficulty_for(PALETTE_STEP * 3).background, NAVY);
+    }
+
+    #[test]
+    fn moving_obstacles_only_above_threshold() {
+        let mut rng = RandomNumberGenerator::seeded(SEED);
+        for _ in 0..100 {
+            assert_eq!(
+                Obstacle::new(0, MOVING_OBSTACLE_SCORE - 1, &mut rng).speed,
+                0
+            );
+        }
+        let speeds: Vec<i32> = (0..100)
+            .map(|_| Obstacle::new(0, MOVING_OBSTACLE_SCORE, &mut rng).speed)
+            .collect();
+        assert!(speeds.contains(&0));
+        assert!(speeds.contains(&MOVING_OBSTACLE_SPEED));
+    }
+
+    #[test]
+    fn moving_obstacle_screen_position_updates_each_tick() {
+        let mut state = state_with_player_at(25, false);
+        state.mode = GameMode::Playing;
+        state.obstacle = Obstacle {
+            speed: 1,
+            ..obstacle_at(40, 25, 20)
+        };
+
+        // 玩家从 x=5 出发：每个逻辑帧玩家前进 1 格、管道后退 1 格，屏幕距离缩短 2 格
+        assert_eq!(state.obstacle.screen_x(state.player.x), 35);
+        for tick in 1..=5 {
+            step(&mut state);
+            state.player.flap();
+            assert_eq!(state.obstacle.x, 40 - tick);
+            assert_eq!(state.obstacle.screen_x(state.player.x), 35 - 2 * tick);
+        }
+    }
+
+    #[test]
+    fn moving_obstacle_cannot_be_tunnelled_through() {
+        // 管道在 x=10，本帧会扫过 [10, 11]；玩家在 x=11 处于缺口外时也要判定为碰撞
+        let obstacle = Obstacle {
+            speed: 1,
+            ..obstacle_at(10, 25, 10)
+        };
+        assert!(obstacle.hit_obstacle(&Player::new(10, 0)));
+        assert!(obstacle.hit_obstacle(&Player::new(11, 0)));
+        assert!(!obstacle.hit_obstacle(&Player::new(11, 25)));
+        assert!(!obstacle.passed_by(&Player::new(11, 25)));
+        assert!(obstacle.passed_by(&Player::new(12, 25)));
+    }
+
+    #[test]
+    fn zone_force_changes_velocity_only_inside_zone() {
+        // 玩家在 x=5，区域覆盖 [6, 9)：第一帧在区域外，第二帧进入区域
+        let zone_at = |effect| Zone {
+            x: 6,
+            width: 3,
+            effect,
+        };
+
+        let mut plain = state_with_player_at(25, false);
+        let mut heavy = state_with_player_at(25, false);
+        heavy.zone = Some(zone_at(ZoneEffect::HeavyGravity));
+        let mut updraft = state_with_player_at(25, false);
+        updraft.zone = Some(zone_at(ZoneEffect::Updraft));
+
+        // 区域外：和没有区域时完全一样
+        for state in [&mut plain, &mut heavy, &mut updraft] {
+            assert_eq!(state.zone_force(), 0.0);
+            state.tick();
+            assert!((state.player.velocity - 0.2).abs() < 1e-6);
+        }
+
+        // 区域内：重力区加速下落，上升气流让速度反向
+        assert_eq!(heavy.zone_force(), ZoneEffect::HeavyGravity.force());
+        plain.tick();
+        heavy.tick();
+        updraft.tick();
+        assert!((plain.player.velocity - 0.4).abs() < 1e-6);
+        assert!((heavy.player.velocity - 0.6).abs() < 1e-6);
+        assert!((updraft.player.velocity - 0.1).abs() < 1e-6);
+
+        // 上升气流持续作用：速度一直变小，但不超过拍打的 -2.0
+        for _ in 0..40 {
+            updraft.player.gravity_and_move(ZoneEffect::Updraft.force());
+        }
+        assert_eq!(updraft.player.velocity, -2.0);
+    }
+
+    #[test]
+    fn zones_only_spawn_above_threshold_between_player_and_pipe() {
+        let mut rng = RandomNumberGenerator::seeded(SEED);
+        for _ in 0..100 {
+            assert_eq!(Zone::spawn(0, 60, ZONE_SCORE - 1, &mut rng), None);
+        }
+        let zones: Vec<Zone> = (0..100)
+            .filter_map(|_| Zone::spawn(100, 60, ZONE_SCORE, &mut rng))
+            .collect();
+        assert!(!zones.is_empty() && zones.len() < 100);
+        for zone in &zones {
+            assert!(zone.x > 100 && zone.x + zone.width < 160);
+        }
+        for effect in ZoneEffect::ALL {
+            assert!(zones.iter().any(|zone| zone.effect == effect));
+        }
+    }
+}
@@ -1,5 +1,7 @@
 // 02_data_types.rs - Rust数据类型详解
 
+use std::num::TryFromIntError;
+
 pub fn run_example() {
     println!("=== Rust学习示例 ===\n");
     // 1. 标量类型（Scalar Types）
@@ -29,7 +31,11 @@ pub fn run_example() {
     
     println!("整数类型示例：");
     println!("i8: {}, u8: {}", i8_value, u8_value);
+    println!("i16: {}, u16: {}", i16_value, u16_value);
     println!("i32(默认): {}, u32: {}", i32_value, u32_value);
+    println!("i64: {}, u64: {}", i64_value, u64_value);
+    println!("i128: {}, u128: {}", i128_value, u128_value);
+    println!("isize: {}, usize: {}", isize_value, usize_value);
     
     // 整数字面量
     let decimal = 98_222;  // 十进制
@@ -45,7 +51,7 @@ pub fn run_example() {
     // 1.2 浮点数类型
     // 浮点数是带有小数部分的数字
     let f32_value: f32 = 2.0;     // 32位浮点数（单精度）
-    let f64_value: f64 = 3.14159;  // 64位浮点数（双精度，默认浮点类型）
+    let f64_value: f64 = std::f64::consts::PI;  // 64位浮点数（双精度，默认浮点类型）
     
     println!("\n浮点数类型示例：");
     println!("f32: {}, f64(默认): {}", f32_value, f64_value);
@@ -126,6 +132,11 @@ pub fn run_example() {
     println!("inferred_float: {} (类型: f64)", inferred_float);
     println!("inferred_bool: {} (类型: bool)", inferred_bool);
     println!("inferred_char: {} (类型: char)", inferred_char);
+
+    slice_example();
+    overflow_example();
+    type_size_example();
+    conversion_example();
 }
 
 // 4. 切片类型（Slice Types）
@@ -160,7 +171,165 @@ fn slice_example() {
     println!("world切片: {}", world);
     println!("整个字符串切片: {}", whole);
 }
+// 5. 整数溢出（Integer Overflow）
+// debug 模式下整数溢出会 panic，release 模式下会静默回绕
+// 为了行为明确，标准库为每种整数类型提供了四组显式处理溢出的方法
+fn overflow_example() {
+    let max: u8 = u8::MAX;  // 255
+
+    // checked_*：溢出时返回 None，未溢出返回 Some(结果)
+    let checked = max.checked_add(1);
+    // wrapping_*：按位宽取模回绕，255 + 1 = 0
+    let wrapping = max.wrapping_add(1);
+    // saturating_*：溢出时停在边界值，255 + 1 = 255
+    let saturating = max.saturating_add(1);
+    // overflowing_*：返回 (回绕后的结果, 是否发生溢出)
+    let (overflowing, overflowed) = max.overflowing_add(1);
+
+    println!("\n整数溢出示例（u8::MAX + 1）：");
+    println!("checked_add: {:?}", checked);
+    println!("wrapping_add: {}", wrapping);
+    println!("saturating_add: {}", saturating);
+    println!("overflowing_add: ({}, {})", overflowing, overflowed);
+
+    // 整数类型的边界常量
+    println!("\n整数边界常量：");
+    println!("i32::MIN = {}, i32::MAX = {}", i32::MIN, i32::MAX);
+    println!("u8::MIN = {}, u8::MAX = {}", u8::MIN, u8::MAX);
+
+    // 6. 浮点数特殊值
+    // 浮点数遵循 IEEE 754 标准，除以 0 不会 panic，而是得到无穷大或 NaN
+    let nan = f64::NAN;
+    let inf = f64::INFINITY;
+    let neg_inf = f64::NEG_INFINITY;
+
+    println!("\n浮点数特殊值：");
+    println!("NAN: {}, INFINITY: {}, NEG_INFINITY: {}", nan, inf, neg_inf);
+    println!("1.0 / 0.0 = {}", 1.0_f64 / 0.0);
+    println!("负数开平方是 NaN: {}", (-1.0_f64).sqrt().is_nan());
+    // NaN 不等于任何值，包括它自己，所以判断 NaN 必须用 is_nan()
+    println!("NAN 与自身比较: {:?}", nan.partial_cmp(&nan));
+}
+
+// 7. 类型大小
+// std::mem::size_of::<T>() 返回类型 T 在内存中占用的字节数
+fn type_size_example() {
+    use std::mem::size_of;
+
+    println!("\n类型大小（字节）：");
+    println!("{:<8} {:>4}", "类型", "大小");
+    println!("{:<8} {:>4}", "i8", size_of::<i8>());
+    println!("{:<8} {:>4}", "i16", size_of::<i16>());
+    println!("{:<8} {:>4}", "i32", size_of::<i32>());
+    println!("{:<8} {:>4}", "i64", size_of::<i64>());
+    println!("{:<8} {:>4}", "i128", size_of::<i128>());
+    println!("{:<8} {:>4}", "isize", size_of::<isize>());  // 与平台指针大小一致
+    println!("{:<8} {:>4}", "f32", size_of::<f32>());
+    println!("{:<8} {:>4}", "f64", size_of::<f64>());
+    println!("{:<8} {:>4}", "bool", size_of::<bool>());
+    println!("{:<8} {:>4}", "char", size_of::<char>());  // char 固定 4 字节
+    println!("{:<8} {:>4}", "()", size_of::<()>());      // 单元类型不占空间
+}
+
+// 8. 类型转换（Casting & Conversion）
+// `as` 永远不会失败，但可能悄悄丢数据：整数截断、浮点数饱和
+// TryFrom / TryInto 在放不下时返回 Err，让调用方决定怎么处理
+fn conversion_example() {
+    println!("\n类型转换示例：");
+
+    // 8.1 整数 as 窄化：只保留低位，300 = 0x12C → 0x2C = 44
+    let big: i32 = 300;
+    println!("300i32 as u8 = {}", big as u8);
+    println!("-1i32 as u8 = {}", -1i32 as u8); // 补码 0xFF → 255
+
+    // 8.2 TryFrom：放不下就是 Err，不会得到一个"看起来正常"的错误值
+    println!("u8::try_from(300) = {:?}", u8::try_from(big));
+    println!("u8::try_from(200) = {:?}", u8::try_from(200i32));
+    let narrowed: Result<u8, _> = 42i64.try_into(); // TryInto 是同一件事的另一种写法
+    println!("42i64.try_into::<u8>() = {:?}", narrowed);
+
+    // 8.3 浮点数 as 整数：向零截断，超出范围饱和到边界，NaN 变成 0（不会 panic）
+    println!("3.99f64 as i32 = {}", 3.99_f64 as i32);
+    println!("-3.99f64 as i32 = {}", -3.99_f64 as i32);
+    println!("1e10f64 as i32 = {}", 1e10_f64 as i32);
+    println!("-1e10f64 as u8 = {}", -1e10_f64 as u8);
+    let nan = (-1.0_f64).sqrt(); // 运行时得到的 NaN
+    println!("NaN as i32 = {}", nan as i32);
+
+    // 8.4 char 与 u32：char 一定是合法的 Unicode 标量值
+    // char → u32 永远成功；u32 → char 可能失败（代理区、超出 0x10FFFF）
+    println!("'中' as u32 = {:#X}", '中' as u32);
+    println!("char::from_u32(0x4E2D) = {:?}", char::from_u32(0x4E2D));
+    println!("char::from_u32(0xD800) = {:?}", char::from_u32(0xD800));
+    println!("char::from_digit(7, 10) = {:?}", char::from_digit(7, 10));
+    // 只有 u8 → char 可以直接 as（0..=255 都是合法字符）
+    println!("b'A' as char = {}", b'A' as char);
+
+    // 8.5 推荐写法
+    println!("safe_narrow(200) = {:?}", safe_narrow(200));
+    println!("safe_narrow(-1) = {:?}", safe_narrow(-1));
+}
+
+// 把 i64 安全地收窄为 u8：超出 0..=255 返回 Err，而不是像 `as` 那样截断
+// 调用方用 ? 传播，或 match 后返回友好的错误信息，而不是 unwrap() 然后 panic
+pub fn safe_narrow(i: i64) -> Result<u8, TryFromIntError> {
+    u8::try_from(i)
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn u8_overflow_methods() {
+        assert_eq!(255u8.wrapping_add(1), 0);
+        assert_eq!(255u8.checked_add(1), None);
+        assert_eq!(254u8.checked_add(1), Some(255));
+        assert_eq!(255u8.saturating_add(1), 255);
+        assert_eq!(255u8.overflowing_add(1), (0, true));
+    }
+
+    #[test]
+    fn float_special_values() {
+        let nan = f64::NAN;
+        assert!(nan.is_nan());
+        assert_eq!(nan.partial_cmp(&nan), None);
+        assert_eq!(1.0_f64 / 0.0, f64::INFINITY);
+    }
+
+    #[test]
+    fn safe_narrow_ok_and_error() {
+        use super::safe_narrow;
+        assert_eq!(safe_narrow(0), Ok(0));
+        assert_eq!(safe_narrow(255), Ok(255));
+        assert!(safe_narrow(256).is_err());
+        assert!(safe_narrow(-1).is_err());
+        // 对比：as 不报错，而是截断
+        assert_eq!(300i64 as u8, 44);
+    }
+
+    #[test]
+    fn float_and_char_casts() {
+        assert_eq!(3.99_f64 as i32, 3);
+        assert_eq!(1e10_f64 as i32, i32::MAX);
+        assert_eq!(-1e10_f64 as u8, 0);
+        assert_eq!((-1.0_f64).sqrt() as i32, 0);
+        assert_eq!('中' as u32, 0x4E2D);
+        assert_eq!(char::from_u32(0x4E2D), Some('中'));
+        assert_eq!(char::from_u32(0xD800), None);
+    }
+
+    #[test]
+    fn type_sizes() {
+        use std::mem::size_of;
+        assert_eq!(size_of::<u8>(), 1);
+        assert_eq!(size_of::<i32>(), 4);
+        assert_eq!(size_of::<char>(), 4);
+        assert_eq!(size_of::<()>(), 0);
+    }
+}
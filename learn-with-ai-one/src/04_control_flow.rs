@@ -34,7 +34,8 @@ pub fn run_example() {
     // if let用于简化模式匹配，处理只关心一种情况的场景
     let optional_number: Option<i32> = Some(7);
     
-    // 使用match
+    // 使用match（故意写成单分支 match，和下面的 if let 对比）
+    #[allow(clippy::single_match)]
     match optional_number {
         Some(n) => println!("使用match获取到数字: {}", n),
         _ => ()  // 忽略其他情况
@@ -70,7 +71,8 @@ pub fn run_example() {
     
     println!("{}号是{}", day, day_name);
     
-    // match用于枚举
+    // match用于枚举（只构造了 Right，其它变体用来演示穷尽匹配）
+    #[allow(dead_code)]
     enum Direction {
         Up,
         Down,
@@ -220,18 +222,29 @@ pub fn run_example() {
     println!("\n流程控制组合使用示例:");
     
     let numbers = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    let (even_sum, odd_sum) = loop_even_odd_sums(&numbers);
+    
+    println!("偶数和: {}, 奇数和: {}", even_sum, odd_sum);
+    
+    fizzbuzz();
+    iterator_example();
+    labeled_break_example();
+}
+
+// 10. 用循环分别累加偶数和奇数
+fn loop_even_odd_sums(numbers: &[i32]) -> (i32, i32) {
     let mut even_sum = 0;
     let mut odd_sum = 0;
     
     for number in numbers.iter() {
         match number % 2 {
             0 => even_sum += number,
-            1 => odd_sum += number,
+            1 | -1 => odd_sum += number,  // 负奇数取余结果为-1
             _ => unreachable!()  // 不可能的情况，用于调试
         }
     }
     
-    println!("偶数和: {}, 奇数和: {}", even_sum, odd_sum);
+    (even_sum, odd_sum)
 }
 
 // 11. 示例：使用流程控制实现FizzBuzz游戏
@@ -247,7 +260,141 @@ fn fizzbuzz() {
         }
     }
 }
+// 12. 迭代器适配器：用链式调用代替显式循环
+// 迭代器是惰性的，filter/map等适配器只描述"怎么处理"，
+// 直到sum/collect等消费者方法调用时才真正执行
+fn iter_even_odd_sums(numbers: &[i32]) -> (i32, i32) {
+    let even_sum = numbers.iter().filter(|n| *n % 2 == 0).sum();
+    let odd_sum = numbers.iter().filter(|n| *n % 2 != 0).sum();
+    (even_sum, odd_sum)
+}
+
+// FizzBuzz的单个数字转换，便于在map中复用
+fn fizzbuzz_word(n: u32) -> String {
+    match (n.is_multiple_of(3), n.is_multiple_of(5)) {
+        (true, true) => "FizzBuzz".to_string(),
+        (true, false) => "Fizz".to_string(),
+        (false, true) => "Buzz".to_string(),
+        (false, false) => n.to_string(),
+    }
+}
+
+// 13. 自定义迭代器：斐波那契数列
+// 只需实现next方法，就能免费获得take、map、filter等所有适配器
+struct Fib {
+    curr: u64,
+    next: u64,
+}
+
+impl Fib {
+    fn new() -> Self {
+        Fib { curr: 0, next: 1 }
+    }
+}
+
+impl Iterator for Fib {
+    type Item = u64;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.curr;
+        self.curr = self.next;
+        self.next += value;
+        Some(value)  // 无限序列，永远返回Some，由调用方用take限制数量
+    }
+}
+
+fn iterator_example() {
+    println!("\n迭代器适配器示例:");
+    
+    let numbers = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    let (even_sum, odd_sum) = iter_even_odd_sums(&numbers);
+    println!("filter + sum -> 偶数和: {}, 奇数和: {}", even_sum, odd_sum);
+    
+    let words: Vec<String> = (1..=15).map(fizzbuzz_word).collect();
+    println!("map + collect 实现FizzBuzz: {:?}", words);
+    
+    let fib: Vec<u64> = Fib::new().take(10).collect();
+    println!("斐波那契数列前10项: {:?}", fib);
+}
+
+// 14. 带标签的break返回值：在二维网格中查找第一个满足条件的坐标
+// break 'outer value 可以从内层循环直接跳出外层loop，并把value作为整个loop表达式的值
+// 注意：带值的break只能用于loop（for/while没有"返回值"的概念）
+fn find_first<F: Fn(i32) -> bool>(grid: &[Vec<i32>], predicate: F) -> Option<(usize, usize)> {
+    let mut row = 0;
+    'outer: loop {
+        if row >= grid.len() {
+            break 'outer None;  // 所有行都找完了，没有找到
+        }
+        for (col, &value) in grid[row].iter().enumerate() {
+            if predicate(value) {
+                break 'outer Some((row, col));  // 从内层for直接带值跳出外层loop
+            }
+        }
+        row += 1;
+    }
+}
+
+fn labeled_break_example() {
+    println!("\n带标签的break返回值示例:");
+    
+    let grid = vec![
+        vec![1, 3, 5],
+        vec![7, 8, 9],
+        vec![10, 12, 14],
+    ];
+    
+    // 第一个偶数
+    match find_first(&grid, |v| v % 2 == 0) {
+        Some((row, col)) => println!("第一个偶数位于 ({}, {})，值为 {}", row, col, grid[row][col]),
+        None => println!("没有找到偶数"),
+    }
+    
+    // 没有大于100的数
+    println!("大于100的数: {:?}", find_first(&grid, |v| v > 100));
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_loop_and_iterator_sums_match() {
+        let numbers = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        assert_eq!(loop_even_odd_sums(&numbers), (30, 25));
+        assert_eq!(loop_even_odd_sums(&numbers), iter_even_odd_sums(&numbers));
+        
+        let mixed = [-3, -2, 0, 7, 11, 14];
+        assert_eq!(loop_even_odd_sums(&mixed), iter_even_odd_sums(&mixed));
+        assert_eq!(loop_even_odd_sums(&[]), iter_even_odd_sums(&[]));
+    }
+    
+    #[test]
+    fn test_fizzbuzz_word() {
+        let words: Vec<String> = (1..=5).map(fizzbuzz_word).collect();
+        assert_eq!(words, ["1", "2", "Fizz", "4", "Buzz"]);
+        assert_eq!(fizzbuzz_word(15), "FizzBuzz");
+    }
+    
+    #[test]
+    fn test_find_first_with_labeled_break() {
+        let grid = vec![vec![1, 3, 5], vec![7, 8, 9], vec![10, 12, 14]];
+        assert_eq!(find_first(&grid, |v| v % 2 == 0), Some((1, 1)));
+        assert_eq!(find_first(&grid, |v| v >= 10), Some((2, 0)));
+        assert_eq!(find_first(&grid, |v| v > 100), None);
+        assert_eq!(find_first(&[], |_| true), None);
+    }
+    
+    #[test]
+    fn test_fib_first_ten() {
+        let fib: Vec<u64> = Fib::new().take(10).collect();
+        assert_eq!(fib, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    }
+}
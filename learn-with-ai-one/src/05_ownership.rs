@@ -65,6 +65,12 @@ pub fn run_example() {
     // let r1 = &s;
     // let r2 = &mut s; // 这会报错
     
+    // 引用的作用域到最后一次使用为止：r1 用完之后再借用就没问题
+    let r1 = &mut s;
+    r1.push_str(", borrow");
+    let r2 = &s;
+    println!("借用规则示例: {}", r2);
+    
     // 8. 切片（Slices）类型
     // 切片是对集合中一段连续元素的引用，没有所有权
     
@@ -90,6 +96,7 @@ pub fn run_example() {
     
     // 10. 字符串字面量是切片
     let s = "Hello, world!"; // s的类型是 &str，它是一个指向二进制程序特定位置的切片
+    println!("字符串字面量: {}", s);
     
     // 11. 悬垂引用（Dangling References）
     // Rust编译器会防止悬垂引用的产生
@@ -121,9 +128,19 @@ pub fn run_example() {
     let s3 = take_and_give_back(s2); // s2的所有权转移到函数，然后返回给s3
     println!("s3的值: {}", s3);
     // println!("s2的值: {}", s2); // 这会报错，因为s2的所有权已被转移
+    
+    slice_example();
+    
+    // 15. Box与递归类型
+    box_list_example();
+    
+    // 16. Rc与RefCell：共享所有权与内部可变性
+    rc_refcell_example();
 }
 
 // 计算字符串长度（不可变借用）
+// 故意写成 &String，和第 14 节接受 &str 的 first_word 对比
+#[allow(clippy::ptr_arg)]
 fn calculate_length(s: &String) -> usize {
     s.len() // 返回字符串长度，不获取所有权
 }
@@ -152,6 +169,8 @@ fn borrow_mutable(s: &mut String) {
 }
 
 // 返回一个String，调用者获得所有权
+// 先绑定到 s 再返回，是为了说明 s 的所有权被转移出去
+#[allow(clippy::let_and_return)]
 fn give_ownership() -> String {
     let s = String::from("hello from give_ownership");
     s // 返回s，所有权转移给调用者
@@ -180,7 +199,7 @@ fn first_word(s: &str) -> &str {
         }
     }
     
-    &s[..] // 返回整个字符串切片
+    s // 没有空格：s 本身就是整个字符串切片（等同于 &s[..]）
 }
 
 // 切片函数示例
@@ -193,7 +212,103 @@ fn slice_example() {
     
     println!("第一个单词: '{}' 和 '{}'", word, word2);
 }
+// 15. 使用Box实现递归类型
+// 编译器必须在编译期知道每个类型的大小。
+// 如果写成 Cons(i32, List)，List里包含List，大小会无限递归，无法计算。
+// Box<List>是一个指向堆内存的指针，大小固定（一个usize），
+// 这样List的大小 = i32 + 指针 + 枚举标记，是确定的。
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+impl List {
+    // 递归地累加链表中所有元素
+    fn sum(&self) -> i32 {
+        match self {
+            List::Cons(value, rest) => value + rest.sum(),
+            List::Nil => 0,
+        }
+    }
+}
+
+fn box_list_example() {
+    use List::{Cons, Nil};
+    
+    // 构建链表：1 -> 2 -> 3 -> Nil
+    // 每个Box拥有下一个节点，离开作用域时整条链表会被依次释放
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    
+    println!("\n链表 1 -> 2 -> 3 -> Nil 的和: {}", list.sum());
+    println!("List占用的栈空间: {} 字节（与链表长度无关）", std::mem::size_of::<List>());
+}
+
+// 16. Rc<RefCell<T>>：共享所有权 + 内部可变性
+// 所有权规则要求一个值只有一个所有者，但图、树、观察者等结构需要多个地方共享同一份数据。
+// - Rc<T>（引用计数）：允许多个所有者，最后一个所有者离开作用域时才释放数据（仅限单线程）
+// - RefCell<T>：把借用检查从编译期推迟到运行期，可以通过不可变引用修改内部数据
+// 两者组合后，多个句柄可以共享并修改同一份数据。
+// 注意：RefCell在运行时检查借用规则，如果已经有borrow()未释放时再调用borrow_mut()，
+// 程序会直接panic（BorrowMutError），编译器无法帮你发现这类错误。
+fn rc_refcell_example() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    
+    println!("\nRc与RefCell示例:");
+    
+    let shared = Rc::new(RefCell::new(vec![1, 2, 3]));
+    // Rc::clone只增加引用计数，不会深拷贝数据
+    let observer = Rc::clone(&shared);
+    println!("strong_count: {}", Rc::strong_count(&shared));
+    
+    // 通过一个句柄修改，borrow_mut()返回的RefMut在语句结束时释放
+    shared.borrow_mut().push(4);
+    
+    // 通过另一个句柄观察到修改
+    println!("通过另一个句柄看到的数据: {:?}", observer.borrow());
+    
+    // 同时持有不可变借用和可变借用会在运行时panic：
+    // let r = shared.borrow();
+    // shared.borrow_mut().push(5); // panic: already borrowed
+    // 可以用try_borrow_mut()避免panic
+    let reader = shared.borrow();
+    println!("持有borrow()时try_borrow_mut()是否成功: {}", observer.try_borrow_mut().is_ok());
+    drop(reader);
+    
+    drop(observer);
+    println!("释放一个句柄后 strong_count: {}", Rc::strong_count(&shared));
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_box_list_sum() {
+        use List::{Cons, Nil};
+        let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+        assert_eq!(list.sum(), 6);
+        assert_eq!(Nil.sum(), 0);
+    }
+    
+    #[test]
+    fn test_rc_refcell_shared_mutation() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        
+        let shared = Rc::new(RefCell::new(vec![1, 2, 3]));
+        let observer = Rc::clone(&shared);
+        
+        shared.borrow_mut().push(4);
+        
+        assert_eq!(*observer.borrow(), vec![1, 2, 3, 4]);
+        assert_eq!(Rc::strong_count(&shared), 2);
+    }
+}
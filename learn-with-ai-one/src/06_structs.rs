@@ -1,7 +1,10 @@
 // 06_structs.rs - Rust结构体详解
 
+use std::ops::Deref;
+
 // 1. 结构体的定义
 // 使用struct关键字定义结构体
+#[derive(Debug, PartialEq)]
 struct User {
     active: bool,
     username: String,
@@ -16,6 +19,7 @@ struct Point(i32, i32, i32);
 
 // 3. 单元结构体（Unit Structs）
 // 没有任何字段的结构体，类似于单元类型()
+#[derive(Debug)]
 struct Unit;
 
 // 4. 结构体的方法定义（使用impl块）
@@ -170,6 +174,7 @@ pub fn run_example() {
     };
     
     println!("文章标题: {}", article.get_title());
+    println!("作者: {}，内容: {}", article.author, article.content);
     
     let title = article.take_title(); // 获取标题的所有权
     println!("获取到的标题: {}", title);
@@ -186,6 +191,10 @@ pub fn run_example() {
     println!("修改后 - 公开字段: {}, 私有字段: {}", 
              public_struct.public_field, 
              public_struct.get_private());
+    
+    rectangle_example();
+    builder_example();
+    newtype_example();
 }
 
 // 16. 结构体的示例应用：矩形
@@ -238,7 +247,166 @@ fn rectangle_example() {
     println!("rect1能否容纳rect3: {}", rect1.can_hold(&rect3));
     println!("正方形的面积: {}", square.area());
 }
+// 17. 构建者模式（Builder Pattern）
+// 字段较多或有可选字段时，用构建者按需设置字段，最后统一校验并生成结构体
+// 每个设置方法都获取self的所有权并返回Self，因此可以链式调用
+#[derive(Default)]
+struct UserBuilder {
+    username: Option<String>,
+    email: Option<String>,
+    active: bool,
+}
+
+impl UserBuilder {
+    fn new() -> Self {
+        // 默认用户为活跃状态
+        UserBuilder { active: true, ..Default::default() }
+    }
+    
+    fn username(mut self, username: &str) -> Self {
+        self.username = Some(username.to_string());
+        self
+    }
+    
+    fn email(mut self, email: &str) -> Self {
+        self.email = Some(email.to_string());
+        self
+    }
+    
+    fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+    
+    // 校验必填字段：缺少username或email时返回Err
+    fn build(self) -> Result<User, String> {
+        let username = self.username.ok_or("缺少必填字段: username")?;
+        let email = self.email.ok_or("缺少必填字段: email")?;
+        Ok(User {
+            active: self.active,
+            username,
+            email,
+            sign_in_count: 0,
+        })
+    }
+}
+
+fn builder_example() {
+    println!("\n构建者模式示例:");
+    
+    let user = UserBuilder::new()
+        .username("carol")
+        .email("carol@example.com")
+        .active(false)
+        .build();
+    println!("完整构建: {:?}", user);
+    
+    let missing = UserBuilder::new().email("nobody@example.com").build();
+    println!("缺少用户名: {:?}", missing);
+}
+
+// 18. 新类型模式（Newtype Pattern）
+// 用只有一个字段的元组结构体包一层基础类型：运行时零开销，编译期却是不同的类型
+// Meters 和 Feet 都是 f64，但不能互相混用，换算必须显式写出来
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Feet(f64);
+
+// 1 英尺 = 0.3048 米（国际英尺的定义值）
+const METERS_PER_FOOT: f64 = 0.3048;
+
+// From：英尺 → 米；实现 From 后自动获得 Into，可以写 feet.into()
+impl From<Feet> for Meters {
+    fn from(feet: Feet) -> Self {
+        Meters(feet.0 * METERS_PER_FOOT)
+    }
+}
+
+impl From<Meters> for Feet {
+    fn from(meters: Meters) -> Self {
+        Feet(meters.0 / METERS_PER_FOOT)
+    }
+}
+
+// Deref：只读地暴露内部的 f64，*meters 得到 f64，也可以直接调用 f64 的方法（如 meters.round()）
+// 只实现 Deref 不实现 DerefMut → 外部不能改写内部的值
+impl Deref for Meters {
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+fn newtype_example() {
+    println!("\n新类型模式示例:");
+
+    let run = Meters(1500.0);
+    let climb = Feet(1000.0);
+
+    // 不同单位不能直接相加：run.0 + climb.0 虽然能编译，但结果毫无意义
+    // 先把 Feet 换算成 Meters，再通过 Deref 取出 f64 求和
+    let climb_m: Meters = climb.into();
+    let legs = [run, climb_m, Meters(42.195)];
+    let total = Meters(legs.iter().map(|leg| **leg).sum());
+
+    println!("{:?} = {:?}", climb, climb_m);
+    println!("总距离: {:.3} 米 = {:.1} 英尺", *total, Feet::from(total).0);
+    println!("Deref 调用 f64 的方法: round() = {}", total.round());
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_builder_requires_username() {
+        let result = UserBuilder::new().email("a@example.com").build();
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_feet_meters_conversion() {
+        assert_eq!(Meters::from(Feet(1.0)), Meters(0.3048));
+        assert_eq!(Meters::from(Feet(0.0)), Meters(0.0));
+
+        // 来回换算一次，误差在浮点精度以内
+        let back = Feet::from(Meters::from(Feet(1000.0)));
+        assert!((back.0 - 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_meters_deref_sum() {
+        let legs = [Meters(1.5), Feet(10.0).into(), Meters(0.5)];
+        let total: f64 = legs.iter().map(|leg| **leg).sum();
+        assert!((total - 5.048).abs() < 1e-9);
+        assert_eq!(Meters(2.4).round(), 2.0);
+    }
+
+    #[test]
+    fn test_builder_full_build() {
+        let user = UserBuilder::new()
+            .username("alice")
+            .email("alice@example.com")
+            .active(false)
+            .build();
+        assert_eq!(
+            user,
+            Ok(User {
+                active: false,
+                username: String::from("alice"),
+                email: String::from("alice@example.com"),
+                sign_in_count: 0,
+            })
+        );
+    }
+}
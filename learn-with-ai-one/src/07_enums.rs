@@ -2,6 +2,9 @@
 
 // 1. 枚举的基本定义
 // 使用enum关键字定义枚举
+// run_example 只构造了 Up，其余变体只演示定义
+#[derive(Debug)]
+#[allow(dead_code)]
 enum Direction {
     Up,
     Down,
@@ -41,18 +44,22 @@ impl Message {
 // 6. 自定义Result类型
 type MyResult<T> = Result<T, MyError>;
 
+// 示例只会返回 InvalidInput，其余变体用来演示 match 要覆盖所有情况
+#[allow(dead_code)]
 enum MyError {
     NotFound,
     PermissionDenied,
     InvalidInput(String),
 }
 
-// 7. 嵌套枚举
+// 7. 嵌套枚举（只演示定义，没有构造）
+#[allow(dead_code)]
 enum OuterEnum {
     Variant1,
     Variant2(InnerEnum),
 }
 
+#[allow(dead_code)]
 enum InnerEnum {
     Inner1,
     Inner2(i32),
@@ -80,10 +87,10 @@ pub fn run_example() {
     let number = Some(7);
     
     let result = match number {
-        Some(5) => "五",
-        Some(n) if n % 2 == 0 => "偶数",
+        Some(5) => "五".to_string(),
+        Some(n) if n % 2 == 0 => "偶数".to_string(),
         Some(n) => format!("奇数: {}", n),
-        None => "没有数字",
+        None => "没有数字".to_string(),
     };
     
     println!("模式匹配结果: {}", result);
@@ -100,6 +107,10 @@ pub fn run_example() {
         Some(n) => println!("some_number的值是: {}", n),
         None => println!("some_number没有值"),
     }
+    match absent_number {
+        Some(n) => println!("absent_number的值是: {}", n),
+        None => println!("absent_number没有值"),
+    }
     
     // 12. Result枚举的使用示例
     let success_result: Result<i32, &str> = Ok(10);
@@ -118,7 +129,8 @@ pub fn run_example() {
     // 13. if let表达式（简化模式匹配）
     let some_u8_value = Some(0u8);
     
-    // 使用match
+    // 使用match（故意写成单分支 match，和下面的 if let 对比）
+    #[allow(clippy::single_match)]
     match some_u8_value {
         Some(3) => println!("数字3"),
         _ => (),
@@ -173,27 +185,7 @@ pub fn run_example() {
         _ => println!("其他情况"),
     }
     
-    // 16. 枚举的实际应用示例：计算表达式
-enum Expr {
-    Literal(i32),
-    Add(Box<Expr>, Box<Expr>),
-    Subtract(Box<Expr>, Box<Expr>),
-    Multiply(Box<Expr>, Box<Expr>),
-    Divide(Box<Expr>, Box<Expr>),
-}
-
-impl Expr {
-    fn evaluate(&self) -> i32 {
-        match self {
-            Expr::Literal(n) => *n,
-            Expr::Add(left, right) => left.evaluate() + right.evaluate(),
-            Expr::Subtract(left, right) => left.evaluate() - right.evaluate(),
-            Expr::Multiply(left, right) => left.evaluate() * right.evaluate(),
-            Expr::Divide(left, right) => left.evaluate() / right.evaluate(),
-        }
-    }
-}
-    
+    // 16. 枚举的实际应用示例：计算表达式（Expr定义见下方）
     // 创建表达式：1 + 2 * 3
     let expr = Expr::Add(
         Box::new(Expr::Literal(1)),
@@ -203,7 +195,23 @@ impl Expr {
         )),
     );
     
-    println!("\n表达式计算结果: {}", expr.evaluate());
+    println!("\n表达式计算结果: {:?}", expr.evaluate());
+    
+    // 除以0不会panic，而是返回Err(EvalError::DivideByZero)
+    // From<i32>让我们可以用 10.into() 代替 Expr::Literal(10)
+    let bad_expr = Expr::Divide(Box::new(10.into()), Box::new(0.into()));
+    match bad_expr.evaluate() {
+        Ok(n) => println!("10 / 0 = {}", n),
+        Err(e) => println!("10 / 0 计算失败: {:?}", e),
+    }
+    
+    // TryFrom<&str>：把字符串解析为运算符，失败时返回Err
+    for symbol in ["*", "%"] {
+        match Op::try_from(symbol) {
+            Ok(op) => println!("'{}' 解析为 {:?}，6 {} 3 = {:?}", symbol, op, symbol, op.apply(6.into(), 3.into()).evaluate()),
+            Err(e) => println!("'{}' 解析失败: {}", symbol, e),
+        }
+    }
     
     // 17. 自定义Result类型的使用
     fn divide(a: i32, b: i32) -> MyResult<i32> {
@@ -235,20 +243,117 @@ impl Expr {
     // 18. 使用_通配符和..省略符
     let colors = (255, 0, 130);
     
+    // 这里的 match 只有一个分支，目的是演示 _ 和 .. 模式
+    #[allow(clippy::match_single_binding)]
     match colors {
         (r, _, b) => println!("红色: {}, 蓝色: {}", r, b), // 忽略绿色
     }
     
+    #[allow(dead_code)] // age 和 city 被 .. 忽略
     struct Person { name: String, age: u32, city: String }
     let person = Person { name: String::from("Alice"), age: 30, city: String::from("Beijing") };
     
+    #[allow(clippy::match_single_binding)]
     match person {
         Person { name, .. } => println!("姓名: {}", name), // 只关心name字段
     }
+
+    debug_example();
+    visitor_example();
+}
+
+// 16. 枚举的实际应用示例：计算表达式
+// 表达式求值可能失败（例如除以0），因此evaluate返回Result而不是直接panic
+#[derive(Debug, PartialEq)]
+enum EvalError {
+    DivideByZero,
+}
+
+enum Expr {
+    Literal(i32),
+    Add(Box<Expr>, Box<Expr>),
+    Subtract(Box<Expr>, Box<Expr>),
+    Multiply(Box<Expr>, Box<Expr>),
+    Divide(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    // 子表达式的错误通过 ? 运算符向上传播
+    fn evaluate(&self) -> Result<i32, EvalError> {
+        match self {
+            Expr::Literal(n) => Ok(*n),
+            Expr::Add(left, right) => Ok(left.evaluate()? + right.evaluate()?),
+            Expr::Subtract(left, right) => Ok(left.evaluate()? - right.evaluate()?),
+            Expr::Multiply(left, right) => Ok(left.evaluate()? * right.evaluate()?),
+            Expr::Divide(left, right) => {
+                let divisor = right.evaluate()?;
+                if divisor == 0 {
+                    return Err(EvalError::DivideByZero);
+                }
+                Ok(left.evaluate()? / divisor)
+            }
+        }
+    }
+}
+
+// From：不会失败的转换，实现后自动获得 into()
+impl From<i32> for Expr {
+    fn from(n: i32) -> Self {
+        Expr::Literal(n)
+    }
+}
+
+// 四则运算符
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+// TryFrom：可能失败的转换，失败时返回关联类型Error
+impl TryFrom<&str> for Op {
+    type Error = String;
+    
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "+" => Ok(Op::Add),
+            "-" => Ok(Op::Subtract),
+            "*" => Ok(Op::Multiply),
+            "/" => Ok(Op::Divide),
+            other => Err(format!("未知运算符: {}", other)),
+        }
+    }
+}
+
+impl Op {
+    // 运算符的书写符号（TryFrom<&str> 的反方向）
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Subtract => "-",
+            Op::Multiply => "*",
+            Op::Divide => "/",
+        }
+    }
+
+    // 用运算符把两个子表达式组合成新表达式
+    fn apply(self, left: Expr, right: Expr) -> Expr {
+        let (left, right) = (Box::new(left), Box::new(right));
+        match self {
+            Op::Add => Expr::Add(left, right),
+            Op::Subtract => Expr::Subtract(left, right),
+            Op::Multiply => Expr::Multiply(left, right),
+            Op::Divide => Expr::Divide(left, right),
+        }
+    }
 }
 
 // 19. 为枚举实现Debug trait以便打印
+// 字段只通过 {:?} 打印，编译器不把 derive(Debug) 算作读取 → 需要 allow(dead_code)
 #[derive(Debug)]
+#[allow(dead_code)]
 enum DebugEnum {
     Variant1,
     Variant2(i32, String),
@@ -258,14 +363,162 @@ enum DebugEnum {
 fn debug_example() {
     let enum1 = DebugEnum::Variant1;
     let enum2 = DebugEnum::Variant2(42, String::from("hello"));
-    let enum3 = DebugEnum::Variant3 { x: 3.14, y: 2.71 };
+    let enum3 = DebugEnum::Variant3 { x: 1.5, y: 2.5 };
     
     println!("\n调试示例:");
     println!("enum1: {:?}", enum1);
     println!("enum2: {:?}", enum2);
     println!("enum3: {:?}", enum3);
 }
+// 20. 访问者模式（Visitor Pattern）
+// evaluate 把"遍历树"和"对节点做什么"写在了一起，每加一种操作就要再写一遍 match
+// 访问者模式把两者拆开：accept 只负责遍历，做什么交给实现了 ExprVisitor 的类型
+trait ExprVisitor {
+    fn visit_literal(&mut self, n: i32);
+    fn visit_binop(&mut self, op: Op);
+}
+
+impl Expr {
+    // 后序遍历：先访问左右子树，再访问当前运算符（与逆波兰表达式的顺序一致）
+    fn accept(&self, v: &mut impl ExprVisitor) {
+        let (op, left, right) = match self {
+            Expr::Literal(n) => return v.visit_literal(*n),
+            Expr::Add(l, r) => (Op::Add, l, r),
+            Expr::Subtract(l, r) => (Op::Subtract, l, r),
+            Expr::Multiply(l, r) => (Op::Multiply, l, r),
+            Expr::Divide(l, r) => (Op::Divide, l, r),
+        };
+        left.accept(v);
+        right.accept(v);
+        v.visit_binop(op);
+    }
+}
+
+// 访问者一：统计节点数
+#[derive(Default)]
+struct NodeCounter {
+    literals: usize,
+    binops: usize,
+}
+
+impl NodeCounter {
+    fn total(&self) -> usize {
+        self.literals + self.binops
+    }
+}
+
+impl ExprVisitor for NodeCounter {
+    fn visit_literal(&mut self, _n: i32) {
+        self.literals += 1;
+    }
+
+    fn visit_binop(&mut self, _op: Op) {
+        self.binops += 1;
+    }
+}
+
+// 访问者二：打印成带括号的中缀表达式
+// 后序遍历下，访问到运算符时它的两个操作数一定已经在栈顶：弹出右、左，拼好再压回去
+#[derive(Default)]
+struct PrettyPrinter {
+    stack: Vec<String>,
+}
+
+impl PrettyPrinter {
+    fn print(expr: &Expr) -> String {
+        let mut printer = PrettyPrinter::default();
+        expr.accept(&mut printer);
+        printer.stack.pop().unwrap_or_default()
+    }
+}
+
+impl ExprVisitor for PrettyPrinter {
+    fn visit_literal(&mut self, n: i32) {
+        self.stack.push(n.to_string());
+    }
+
+    fn visit_binop(&mut self, op: Op) {
+        let right = self.stack.pop().unwrap_or_default();
+        let left = self.stack.pop().unwrap_or_default();
+        self.stack.push(format!("({} {} {})", left, op.symbol(), right));
+    }
+}
+
+fn visitor_example() {
+    // (1 + 2) * (10 / (4 - 2))
+    let expr = Op::Multiply.apply(
+        Op::Add.apply(1.into(), 2.into()),
+        Op::Divide.apply(10.into(), Op::Subtract.apply(4.into(), 2.into())),
+    );
+
+    let mut counter = NodeCounter::default();
+    expr.accept(&mut counter);
+
+    println!("\n访问者模式示例:");
+    println!("表达式: {}", PrettyPrinter::print(&expr));
+    println!("节点数: {}（字面量 {}，运算符 {}）", counter.total(), counter.literals, counter.binops);
+    println!("求值: {:?}", expr.evaluate());
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_divide_by_zero_is_error() {
+        let expr = Expr::Divide(Box::new(10.into()), Box::new(0.into()));
+        assert_eq!(expr.evaluate(), Err(EvalError::DivideByZero));
+        
+        // 嵌套在内层的错误也会被传播出来
+        let nested = Expr::Add(Box::new(1.into()), Box::new(expr));
+        assert_eq!(nested.evaluate(), Err(EvalError::DivideByZero));
+    }
+    
+    #[test]
+    fn test_nested_expression() {
+        // (8 - 2) * (9 / 3) = 18
+        let expr = Op::Multiply.apply(
+            Op::Subtract.apply(8.into(), 2.into()),
+            Op::Divide.apply(9.into(), 3.into()),
+        );
+        assert_eq!(expr.evaluate(), Ok(18));
+    }
+    
+    #[test]
+    fn test_visitor_counts_nodes() {
+        let mut counter = NodeCounter::default();
+        Expr::Literal(7).accept(&mut counter);
+        assert_eq!((counter.literals, counter.binops), (1, 0));
+
+        // (1 + 2) * (10 / (4 - 2))：5 个字面量 + 4 个运算符
+        let expr = Op::Multiply.apply(
+            Op::Add.apply(1.into(), 2.into()),
+            Op::Divide.apply(10.into(), Op::Subtract.apply(4.into(), 2.into())),
+        );
+        let mut counter = NodeCounter::default();
+        expr.accept(&mut counter);
+        assert_eq!((counter.literals, counter.binops), (5, 4));
+        assert_eq!(counter.total(), 9);
+    }
+
+    #[test]
+    fn test_visitor_pretty_prints() {
+        let expr = Op::Subtract.apply(Op::Multiply.apply(2.into(), 3.into()), 4.into());
+        assert_eq!(PrettyPrinter::print(&expr), "((2 * 3) - 4)");
+        assert_eq!(PrettyPrinter::print(&Expr::Literal(-1)), "-1");
+    }
+
+    #[test]
+    fn test_op_try_from() {
+        assert_eq!(Op::try_from("+"), Ok(Op::Add));
+        assert_eq!(Op::try_from("/"), Ok(Op::Divide));
+        assert!(Op::try_from("%").is_err());
+    }
+}
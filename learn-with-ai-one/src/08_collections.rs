@@ -30,7 +30,7 @@ pub fn run_example() {
     
     // 访问元素
     println!("v2的第一个元素: {}", v2[0]); // 索引访问（越界会panic）
-    println!("v2的第一个元素: {:?}", v2.get(0)); // get方法（返回Option<T>）
+    println!("v2的第一个元素: {:?}", v2.first()); // first方法（返回Option<T>，等同于 get(0)）
     println!("v2的第10个元素: {:?}", v2.get(10)); // 安全访问不存在的元素
     
     // 修改元素
@@ -68,6 +68,7 @@ pub fn run_example() {
     
     // 创建String
     let s1 = String::new();
+    println!("s1（空字符串）: {:?}", s1);
     let s2 = String::from("Hello");
     let s3 = " world".to_string();
     
@@ -86,7 +87,7 @@ pub fn run_example() {
     
     // 访问字符串
     println!("s4的长度: {}", s4.len());
-    println!("s4的字节数: {}", s4.as_bytes().len());
+    println!("s4的字符数: {}", s4.chars().count()); // len() 是字节数，中文等字符会占多个字节
     
     // 注意：Rust的String不支持索引访问，因为UTF-8字符可能占用多个字节
     // println!("s4[0]: {}", s4[0]); // 这会报错
@@ -122,7 +123,7 @@ pub fn run_example() {
     // 从元组向量创建HashMap
     let teams = vec![String::from("Blue"), String::from("Yellow")];
     let initial_scores = vec![10, 50];
-    let mut team_scores: HashMap<_, _> = teams.into_iter().zip(initial_scores.into_iter()).collect();
+    let team_scores: HashMap<_, _> = teams.into_iter().zip(initial_scores).collect();
     
     println!("scores: {:?}", scores);
     println!("team_scores: {:?}", team_scores);
@@ -158,6 +159,9 @@ pub fn run_example() {
     
     println!("单词计数: {:?}", word_count);
     
+    // 把上面的entry用法封装成可复用的函数，见第13节
+    word_frequency_example();
+    
     // 4. HashSet<T> - 哈希集合
     println!("\n=== HashSet<T> - 哈希集合 ===");
     
@@ -284,6 +288,19 @@ pub fn run_example() {
         println!("{}: {}", key, value);
     }
     
+    // 范围查询：BTreeMap相对HashMap的最大优势
+    // 键是有序存储的，range只需O(log n)定位起点，再顺序往后走
+    // HashMap做同样的事只能遍历全部元素再过滤
+    let middle: Vec<_> = btree_map.range(2..=4).collect();
+    println!("键在2..=4之间: {:?}", middle);
+    let head: Vec<_> = btree_map.range(..3).collect();
+    println!("键小于3: {:?}", head);
+    
+    // 最小键和最大键，同样是O(log n)
+    println!("最小的键值对: {:?}", btree_map.first_key_value());
+    println!("最大的键值对: {:?}", btree_map.last_key_value());
+    println!("between(&btree_map, 2, 3): {:?}", between(&btree_map, 2, 3));
+    
     // 8. BTreeSet<T> - 基于B树的有序集合
     println!("\n=== BTreeSet<T> - 有序集合 ===");
     
@@ -316,6 +333,9 @@ pub fn run_example() {
     println!("- 有序映射: BTreeMap<K, V> - 用于需要按键排序的映射场景");
     println!("- 无序集合: HashSet<T> - 用于需要快速判断元素是否存在的场景");
     println!("- 有序集合: BTreeSet<T> - 用于需要按元素排序的集合场景");
+
+    custom_type_in_hashmap();
+    ownership_example();
 }
 
 // 10. 为自定义类型实现Hash和Eq trait以用于HashMap和HashSet
@@ -382,9 +402,8 @@ fn ownership_example() {
     let s1 = String::from("hello");
     let s2 = String::from("world");
     
-    let mut vec = Vec::new();
-    vec.push(s1); // s1的所有权被转移到vec
-    vec.push(s2); // s2的所有权被转移到vec
+    let vec = vec![s1, s2]; // s1、s2的所有权被转移到vec
+    println!("vec: {:?}", vec);
     
     // println!("s1: {}", s1); // 这会报错，因为s1的所有权已被转移
     
@@ -392,14 +411,101 @@ fn ownership_example() {
     let s3 = String::from("rust");
     let s4 = String::from("programming");
     
-    let mut vec_ref = Vec::new();
-    vec_ref.push(&s3);
-    vec_ref.push(&s4);
+    let vec_ref = vec![&s3, &s4]; // 只存引用，s3、s4的所有权不变
     
     println!("vec_ref: {:?}", vec_ref);
     println!("s3: {}", s3); // 可以正常访问，因为只是借用
 }
+
+// 13. 用entry API统计词频，并取出现次数最多的n个词
+// 统计时统一转成小写、去掉首尾标点，"Rust," 和 "rust" 算同一个词
+fn word_frequencies(text: &str) -> HashMap<String, usize> {
+    let mut freq = HashMap::new();
+    for word in text.split_whitespace() {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        // entry返回键对应的"槽位"：不存在就插入0，再返回可变引用
+        *freq.entry(word).or_insert(0) += 1;
+    }
+    freq
+}
+
+// HashMap的遍历顺序是不确定的，直接取前n个每次运行结果可能不同
+// 所以先按次数降序排序，次数相同再按单词升序，保证结果确定
+fn most_common(freq: &HashMap<String, usize>, n: usize) -> Vec<(String, usize)> {
+    let mut pairs: Vec<(String, usize)> = freq
+        .iter()
+        .map(|(word, &count)| (word.clone(), count))
+        .collect();
+    pairs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    pairs.truncate(n);
+    pairs
+}
+
+// 14. 取出键在[lo, hi]闭区间内的所有值（按键的顺序）
+// lo > hi 时返回空列表（range遇到起点大于终点会panic，所以先判断）
+fn between<K: Ord, V>(map: &BTreeMap<K, V>, lo: K, hi: K) -> Vec<&V> {
+    if lo > hi {
+        return Vec::new();
+    }
+    map.range(lo..=hi).map(|(_, v)| v).collect()
+}
+
+fn word_frequency_example() {
+    println!("\n=== 词频统计（entry API） ===");
+    
+    let paragraph = "Rust is fast. Rust is safe, and Rust is fun! \
+                     Safe code is fast code.";
+    let freq = word_frequencies(paragraph);
+    println!("不同的单词数: {}", freq.len());
+    println!("出现最多的3个词: {:?}", most_common(&freq, 3));
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_word_frequencies_normalizes_words() {
+        let freq = word_frequencies("Rust, rust! RUST is fun.");
+        assert_eq!(freq.get("rust"), Some(&3));
+        assert_eq!(freq.get("fun"), Some(&1));
+        assert_eq!(freq.len(), 3);
+        assert!(word_frequencies("  ... ").is_empty());
+    }
+    
+    #[test]
+    fn test_between_is_inclusive_and_ordered() {
+        let map: BTreeMap<i32, &str> = [(5, "five"), (1, "one"), (3, "three"), (4, "four")]
+            .into_iter()
+            .collect();
+        assert_eq!(between(&map, 2, 4), [&"three", &"four"]);
+        assert_eq!(between(&map, 1, 5).len(), 4);
+        assert!(between(&map, 6, 9).is_empty());
+        assert!(between(&map, 4, 2).is_empty());
+    }
+    
+    #[test]
+    fn test_most_common_breaks_ties_by_word() {
+        // b、c、a 都出现2次，d出现3次
+        let freq = word_frequencies("b c a d b c a d d");
+        assert_eq!(
+            most_common(&freq, 3),
+            [("d".to_string(), 3), ("a".to_string(), 2), ("b".to_string(), 2)]
+        );
+        // n大于单词数时返回全部
+        assert_eq!(most_common(&freq, 10).len(), 4);
+        assert!(most_common(&freq, 0).is_empty());
+    }
+}
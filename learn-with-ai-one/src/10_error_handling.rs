@@ -4,6 +4,9 @@
 // 1. 不可恢复错误（Unrecoverable Errors）：使用panic!宏
 // 2. 可恢复错误（Recoverable Errors）：使用Result<T, E>枚举
 
+// 本章有不少函数和变量只用来对比不同写法（如read_username_simple），并不会在run_example中调用
+#![allow(dead_code, unused_variables)]
+
 use std::error::Error;
 use std::fmt;
 use std::fs::File;
@@ -19,7 +22,7 @@ fn panic_example() {
     // panic!("这是一个不可恢复的错误！");
     
     // 使用panic!的调试信息
-    let v = vec![1, 2, 3];
+    let v = [1, 2, 3];
     // v[100]; // 索引越界，会自动panic
     
     println!("panic!演示完成");
@@ -103,7 +106,7 @@ impl fmt::Display for MyError {
 // 为MyError实现Error trait
 impl Error for MyError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.source.as_ref().map(|e| e.as_ref())
+        self.source.as_ref().map(|e| e.as_ref() as &(dyn Error + 'static))
     }
 }
 
@@ -133,35 +136,120 @@ fn process_data() -> Result<(), MyError> {
     Ok(())
 }
 
-// 6. 使用thiserror crate简化自定义错误（示例，实际需要添加依赖）
+// 6. 使用thiserror crate简化自定义错误
 // 这是一个非常流行的错误处理库，简化了自定义错误的定义
-/*
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-enum ThisErrorExample {
+// 和第5节的MyError对比：Display、Error、From都由derive生成，不用再手写
+// - #[error("...")]：生成Display，{0}是元组变体的第一个字段
+// - #[from]：生成From<ParseIntError> for AppError，?运算符会自动转换
+//   （同时把它作为source()返回，错误链不丢）
+// 这里写成thiserror::Error，避免和上面导入的std::error::Error重名
+
+#[derive(thiserror::Error, Debug)]
+pub enum AppError {
     #[error("解析错误: {0}")]
-    ParseError(#[from] ParseIntError),
-    
+    Parse(#[from] ParseIntError),
+
     #[error("IO错误: {0}")]
-    IoError(#[from] std::io::Error),
-    
-    #[error("自定义错误: {0}")]
-    CustomError(String),
+    Io(#[from] io::Error),
+
+    #[error("数值超出范围: {0}（允许 0~100）")]
+    OutOfRange(i32),
+}
+
+// 读取配置文件里的一个百分比数值并校验
+// 两个?分别把io::Error和ParseIntError转换成AppError，范围不对时手动返回OutOfRange
+fn check_percent_file(path: &std::path::Path) -> Result<(), AppError> {
+    let content = std::fs::read_to_string(path)?; // io::Error → AppError::Io
+    let value: i32 = content.trim().parse()?; // ParseIntError → AppError::Parse
+    if !(0..=100).contains(&value) {
+        return Err(AppError::OutOfRange(value));
+    }
+    println!("{} 中的数值 {} 有效", path.display(), value);
+    Ok(())
 }
-*/
 
-// 7. 使用anyhow crate进行错误处理（示例，实际需要添加依赖）
+fn thiserror_example() {
+    println!("\n=== thiserror自定义错误 ===");
+    let dir = std::env::temp_dir();
+    let cases = [("ok", Some("42")), ("bad", Some("abc")), ("big", Some("250")), ("missing", None)];
+    for (name, content) in cases {
+        let path = dir.join(format!("thiserror_example_{}.txt", name));
+        match content {
+            Some(text) => {
+                if let Err(e) = std::fs::write(&path, text) {
+                    println!("写入临时文件失败: {}", e);
+                    continue;
+                }
+            }
+            None => {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        if let Err(e) = check_percent_file(&path) {
+            println!("{}: {}", name, e);
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+// 7. 使用anyhow crate进行错误处理
 // anyhow提供了一种更灵活的错误处理方式，适合应用程序
-/*
-use anyhow::Result;
+// - anyhow::Result<T> = Result<T, anyhow::Error>，任何实现了Error的类型都能用?转换进来
+// - .context("...") / .with_context(|| ...)：在原始错误外面再包一层说明，形成错误链
+// - {} 只打印最外层说明；{:#} 把整条链用 ": " 连起来打印
+// 分工：库用thiserror定义具体的错误类型（调用方能match），应用用anyhow汇总并补充上下文
+use anyhow::Context;
+
+// 读文件 → 逐行解析整数 → 求平均值，每一步失败都带上"在做什么"的说明
+fn run_pipeline(path: &std::path::Path) -> anyhow::Result<i32> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("读取输入文件 {} 失败", path.display()))?;
+
+    let numbers = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.trim()
+                .parse::<i32>()
+                .with_context(|| format!("第 {} 行不是整数: {:?}", i + 1, line))
+        })
+        .collect::<anyhow::Result<Vec<i32>>>()?;
 
-fn anyhow_example() -> Result<()> {
-    let _number = parse_number("not_a_number")?;
-    let _content = std::fs::read_to_string("nonexistent.txt")?;
-    Ok(())
+    // ensure!：条件不满足时直接返回一个带消息的anyhow::Error
+    anyhow::ensure!(!numbers.is_empty(), "输入文件 {} 中没有数字", path.display());
+
+    let sum: i64 = numbers.iter().map(|&n| n as i64).sum();
+    Ok((sum / numbers.len() as i64) as i32)
+}
+
+fn anyhow_example() {
+    println!("\n=== anyhow应用层错误处理 ===");
+    let dir = std::env::temp_dir();
+    let cases = [("good", Some("10\n20\n30\n")), ("bad", Some("10\nabc\n")), ("missing", None)];
+    for (name, content) in cases {
+        let path = dir.join(format!("anyhow_example_{}.txt", name));
+        match content {
+            Some(text) => {
+                if let Err(e) = std::fs::write(&path, text) {
+                    println!("写入临时文件失败: {}", e);
+                    continue;
+                }
+            }
+            None => {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+        match run_pipeline(&path) {
+            Ok(average) => println!("{}: 平均值 {}", name, average),
+            Err(e) => {
+                println!("{}: {}", name, e); // 只有最外层说明
+                println!("{}: {:#}", name, e); // 完整错误链
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    }
 }
-*/
 
 // 8. 错误处理的组合
 
@@ -209,7 +297,7 @@ fn multi_error_operation() -> Result<(), Box<dyn Error>> {
 
 // 尽早返回错误
 fn best_practice_early_return() -> Result<(), Box<dyn Error>> {
-    let file = match File::open("data.txt") {
+    let mut file = match File::open("data.txt") {
         Ok(f) => f,
         Err(e) => return Err(Box::new(e)),
     };
@@ -244,7 +332,7 @@ fn error_chain_example() -> Result<(), MyError> {
 // 12. 处理Option<T>和Result<T, E>的组合
 
 fn option_result_combination() -> Result<Option<i32>, ParseIntError> {
-    let numbers = vec!["1", "2", "three", "4"];
+    let numbers = ["1", "2", "three", "4"];
     
     for num_str in numbers {
         match num_str.parse::<i32>() {
@@ -304,25 +392,8 @@ fn custom_result_type_example() -> MyResult<()> {
     Ok(())
 }
 
-// 15. 错误处理与测试
+// 15. 错误处理与测试（见文件末尾的tests模块）
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
-    #[test]
-    fn test_parse_number() {
-        assert_eq!(parse_number("42"), Ok(42));
-        assert!(parse_number("not_a_number").is_err());
-    }
-    
-    #[test]
-    #[should_panic(expected = "索引越界")]
-    fn test_panic() {
-        let v = vec![1, 2, 3];
-        v[100];
-    }
-}
 
 pub fn run_example() {
     println!("=== Rust学习示例 ===\n");
@@ -364,7 +435,7 @@ pub fn run_example() {
         Err(e) => {
             println!("\nerror_chain_example失败: {}", e);
             // 遍历错误链
-            let mut current = Some(e.as_ref());
+            let mut current: Option<&dyn Error> = Some(&e);
             let mut index = 0;
             while let Some(err) = current {
                 println!("  错误{}: {}", index, err);
@@ -387,6 +458,12 @@ pub fn run_example() {
         Err(e) => println!("\ncustom_result_type_example失败: {}", e),
     }
     
+    thiserror_example();
+
+    anyhow_example();
+
+    aggregate_errors_example();
+
     // 16. 错误处理总结
     println!("\n=== 错误处理总结 ===");
     println!("1. 不可恢复错误：使用panic!宏，导致程序崩溃");
@@ -441,7 +518,129 @@ fn error_recovery() -> Result<i32, Box<dyn Error>> {
     let number: i32 = content.trim().parse()?;
     Ok(number)
 }
+
+// 18. 批量处理：把多个Result汇总
+// Result实现了FromIterator：迭代器里是Result<T, E>，可以直接collect成Result<Vec<T>, E>
+// - 全部Ok → Ok(Vec)
+// - 遇到第一个Err就停止迭代并返回它（短路，后面的元素不再处理）
+
+fn parse_all(strs: &[&str]) -> Result<Vec<i32>, ParseIntError> {
+    strs.iter().map(|s| s.parse::<i32>()).collect::<Result<Vec<_>, _>>()
+}
+
+// 不想短路、要"能解析的都解析，错误也全部收集"时，用partition分成两堆
+fn parse_some(strs: &[&str]) -> (Vec<i32>, Vec<ParseIntError>) {
+    let (oks, errs): (Vec<_>, Vec<_>) = strs
+        .iter()
+        .map(|s| s.parse::<i32>())
+        .partition(|result| result.is_ok());
+    // partition后两堆仍是Result，再各自拆开（这里的unwrap不会失败）
+    let numbers = oks.into_iter().map(Result::unwrap).collect();
+    let errors = errs.into_iter().map(Result::unwrap_err).collect();
+    (numbers, errors)
+}
+
+fn aggregate_errors_example() {
+    let inputs = ["10", "abc", "20", "", "30"];
+    println!("\n批量解析: {:?}", inputs);
+
+    match parse_all(&inputs) {
+        Ok(numbers) => println!("parse_all全部成功: {:?}", numbers),
+        Err(e) => println!("parse_all在第一个错误处停止: {}", e),
+    }
+
+    let (numbers, errors) = parse_some(&inputs);
+    println!("parse_some成功 {} 个: {:?}", numbers.len(), numbers);
+    for e in &errors {
+        println!("parse_some错误: {}", e);
+    }
+}
+
 // 用于单独运行本文件的main函数
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("42"), Ok(42));
+        assert!(parse_number("not_a_number").is_err());
+    }
+    
+    #[test]
+    fn test_parse_all_short_circuits() {
+        assert_eq!(parse_all(&["1", "2", "3"]), Ok(vec![1, 2, 3]));
+        // 遇到第一个错误就停：返回的是 "x" 的错误，后面的 "y" 根本不会被解析
+        let err = parse_all(&["1", "x", "y"]).unwrap_err();
+        assert_eq!(err, "x".parse::<i32>().unwrap_err());
+    }
+
+    #[test]
+    fn test_parse_some_collects_everything() {
+        let (numbers, errors) = parse_some(&["1", "x", "2", "y", "3"]);
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(errors.len(), 2);
+
+        let (numbers, errors) = parse_some(&[]);
+        assert!(numbers.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    fn test_app_error_display_matches_attributes() {
+        let parse = AppError::from("x".parse::<i32>().unwrap_err());
+        assert_eq!(parse.to_string(), "解析错误: invalid digit found in string");
+
+        let io = AppError::from(io::Error::new(io::ErrorKind::NotFound, "找不到文件"));
+        assert_eq!(io.to_string(), "IO错误: 找不到文件");
+
+        assert_eq!(AppError::OutOfRange(250).to_string(), "数值超出范围: 250（允许 0~100）");
+    }
+
+    #[test]
+    fn test_question_mark_converts_into_app_error() {
+        let path = std::env::temp_dir().join(format!("thiserror_test_{}.txt", std::process::id()));
+
+        std::fs::write(&path, "abc").unwrap();
+        assert!(matches!(check_percent_file(&path), Err(AppError::Parse(_))));
+
+        std::fs::write(&path, "42").unwrap();
+        assert!(check_percent_file(&path).is_ok());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(check_percent_file(&path), Err(AppError::Io(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_panic() {
+        // 必须用Vec：对数组用常量下标越界，编译器会直接报错而不是运行时panic
+        #[allow(clippy::useless_vec)]
+        let v = vec![1, 2, 3];
+        let _ = v[100];
+    }
+
+    #[test]
+    fn test_run_pipeline_errors_carry_context() {
+        let path = std::env::temp_dir().join(format!("anyhow_test_{}.txt", std::process::id()));
+
+        std::fs::write(&path, "1\n2\n6\n").unwrap();
+        assert_eq!(run_pipeline(&path).unwrap(), 3);
+
+        // 解析失败：最外层是我们加的说明，{:#}里还能看到原始的ParseIntError
+        std::fs::write(&path, "1\nabc\n").unwrap();
+        let err = run_pipeline(&path).unwrap_err();
+        assert_eq!(err.to_string(), "第 2 行不是整数: \"abc\"");
+        assert!(format!("{:#}", err).ends_with("invalid digit found in string"));
+        assert!(err.downcast_ref::<ParseIntError>().is_some());
+
+        // 读取失败：说明里带文件路径
+        std::fs::remove_file(&path).unwrap();
+        let err = run_pipeline(&path).unwrap_err();
+        assert_eq!(err.to_string(), format!("读取输入文件 {} 失败", path.display()));
+        assert!(err.downcast_ref::<io::Error>().is_some());
+    }
+}
@@ -9,6 +9,7 @@
 // 1. 泛型函数
 
 // 定义一个泛型函数，用于比较两个值是否相等
+
 fn is_equal<T: PartialEq>(a: T, b: T) -> bool {
     a == b
 }
@@ -26,7 +27,7 @@ fn generic_function_example() {
     println!("\"rust\" == \"rust\": {}", is_equal("rust", "rust"));
     
     // 比较浮点数
-    println!("3.14 == 2.71: {}", is_equal(3.14, 2.71));
+    println!("1.5 == 2.5: {}", is_equal(1.5, 2.5));
     
     // 比较自定义类型（需要实现PartialEq trait）
     #[derive(PartialEq)]
@@ -36,8 +37,9 @@ fn generic_function_example() {
     let p2 = Point { x: 1, y: 2 };
     let p3 = Point { x: 3, y: 4 };
     
-    println!("p1 == p2: {}", is_equal(p1, p2));
-    println!("p1 == p3: {}", is_equal(p2, p3));
+    // 传引用：&Point同样实现了PartialEq，比较后p1、p2、p3仍可继续使用
+    println!("p1 == p2: {}", is_equal(&p1, &p2));
+    println!("p1 == p3: {}", is_equal(&p1, &p3));
 }
 
 // 2. 泛型结构体
@@ -85,6 +87,7 @@ fn generic_struct_example() {
     
     println!("pair1: ({}, {})", pair1.first(), pair1.second());
     println!("pair2: ({}, {})", pair2.first(), pair2.second());
+    println!("pair3: (({}, {}), ...)", pair3.first().first(), pair3.first().second());
     
     // 使用特定类型的方法
     println!("pair1的和: {}", pair1.sum());
@@ -124,11 +127,12 @@ fn generic_enum_example() {
     let none_value: MyOption<i32> = MyOption::None;
     
     println!("some_int是Some吗: {}", some_int.is_some());
+    println!("some_string是Some吗: {}", some_string.is_some());
     println!("none_value是Some吗: {}", none_value.is_some());
     println!("some_int的值: {}", some_int.unwrap());
     
     // 使用标准库的Option<T>
-    let std_some = Some(3.14);
+    let std_some = Some(2.5);
     let std_none: Option<String> = None;
     
     println!("std_some的值: {:?}", std_some);
@@ -178,8 +182,9 @@ fn generic_constraints_example() {
     let sum = complex_generic(10, 20);
     println!("complex_generic(10, 20) = {}", sum);
     
-    let sum = complex_generic(5, 3.5);
-    println!("complex_generic(5, 3.5) = {}", sum);
+    // 注意：f64没有实现Into<i32>（会丢失精度），所以complex_generic(5, 3.5)无法编译
+    let sum = complex_generic(5, 3_i16);
+    println!("complex_generic(5, 3_i16) = {}", sum);
 }
 
 // 5. 泛型与所有权
@@ -229,11 +234,8 @@ fn generics_and_ownership() {
 fn std_lib_generics_example() {
     println!("\n=== 标准库中的泛型 ===");
     
-    // Vec<T>
-    let mut vec = Vec::new();
-    vec.push(1);
-    vec.push(2);
-    vec.push(3);
+    // Vec<T>：vec! 宏创建，T 推断为 i32
+    let vec = vec![1, 2, 3];
     println!("Vec<T>: {:?}", vec);
     
     // HashMap<K, V>
@@ -461,8 +463,9 @@ fn advanced_constraints_example() {
     
     // 使用Into trait
     println!("sum(1, 2) = {}", sum(1, 2));
-    println!("sum(1.5, 2.5) = {}", sum(1.5, 2.5));
-    println!("sum('a' as u8, 'b' as u8) = {}", sum('a' as u8, 'b' as u8));
+    // sum(1.5, 2.5)无法编译：浮点数没有实现Into<i32>
+    println!("sum(1_i16, 2_i16) = {}", sum(1_i16, 2_i16));
+    println!("sum(b'a', b'b') = {}", sum(b'a', b'b'));
 }
 
 pub fn run_example() {
@@ -479,6 +482,10 @@ pub fn run_example() {
     generics_vs_trait_objects();
     generic_stack_example();
     advanced_constraints_example();
+    memoize_example();
+    type_state_example();
+    ring_buffer_example();
+    blanket_impl_example();
     
     // 12. 泛型总结
     println!("\n=== 泛型总结 ===");
@@ -539,7 +546,334 @@ macro_rules! print_generic {
 // 泛型与宏的比较：
 // - 泛型：类型安全，编译时检查，性能好
 // - 宏：更灵活，可以处理任意语法，但可能导致复杂的错误信息
+
+// 16. 泛型记忆化（Memoize）
+// 把"函数 + 结果缓存"打包成一个泛型结构体：同一个参数只计算一次，之后直接返回缓存
+// - A: 参数类型，要作为HashMap的键，所以需要Eq + Hash；存入缓存时要保留一份，所以需要Clone
+// - B: 结果类型，每次返回的是缓存里的副本，所以需要Clone
+// - F: 被缓存的函数/闭包，每个闭包都有自己独一无二的类型，只能用泛型参数表示
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Memoize<A, B, F>
+where
+    A: Eq + Hash + Clone,
+    B: Clone,
+    F: Fn(A) -> B,
+{
+    func: F,
+    cache: HashMap<A, B>,
+}
+
+impl<A, B, F> Memoize<A, B, F>
+where
+    A: Eq + Hash + Clone,
+    B: Clone,
+    F: Fn(A) -> B,
+{
+    fn new(func: F) -> Self {
+        Memoize {
+            func,
+            cache: HashMap::new(),
+        }
+    }
+
+    // &mut self：命中缓存时只读，未命中时要写入缓存
+    fn call(&mut self, arg: A) -> B {
+        if let Some(result) = self.cache.get(&arg) {
+            return result.clone();
+        }
+        let result = (self.func)(arg.clone());
+        self.cache.insert(arg, result.clone());
+        result
+    }
+}
+
+// 朴素递归的斐波那契，指数级复杂度
+fn naive_fib(n: u64) -> u64 {
+    if n < 2 {
+        n
+    } else {
+        naive_fib(n - 1) + naive_fib(n - 2)
+    }
+}
+
+fn memoize_example() {
+    use std::thread;
+    use std::time::{Duration, Instant};
+    
+    println!("\n=== 泛型记忆化（Memoize） ===");
+    
+    // 用sleep模拟一次耗时的计算（比如网络请求或复杂运算）
+    let mut fib = Memoize::new(|n: u64| {
+        thread::sleep(Duration::from_millis(200));
+        naive_fib(n)
+    });
+    
+    for round in 1..=2 {
+        let start = Instant::now();
+        let result = fib.call(25);
+        println!("第{}次 fib(25) = {}，耗时 {:?}", round, result, start.elapsed());
+    }
+    println!("缓存中的条目数: {}", fib.cache.len());
+}
+
+// 17. PhantomData与类型状态（Type-State）模式
+// 把"连接是否打开"编码进类型参数里，让非法的状态转换在编译期就报错
+// - Open / Closed 是没有任何字段的标记类型，只用来区分状态
+// - PhantomData<State> 告诉编译器"这个结构体和State有关"，但不占用任何内存
+//   （不写它的话，未使用的类型参数State会导致编译错误）
+use std::marker::PhantomData;
+
+struct Open;
+struct Closed;
+
+struct Connection<State> {
+    _marker: PhantomData<State>,
+}
+
+// 只有Connection<Open>才有send和close方法
+impl Connection<Open> {
+    fn connect() -> Connection<Open> {
+        println!("连接已打开");
+        Connection { _marker: PhantomData }
+    }
+
+    // 返回"发送"的字节数
+    fn send(&self, message: &str) -> usize {
+        println!("发送: {}", message);
+        message.len()
+    }
+
+    // close按值接收self：调用后旧的Connection<Open>被消耗，不能再send
+    fn close(self) -> Connection<Closed> {
+        println!("连接已关闭");
+        Connection { _marker: PhantomData }
+    }
+}
+
+fn type_state_example() {
+    println!("\n=== PhantomData与类型状态 ===");
+    
+    let conn = Connection::connect();
+    conn.send("hello");
+    let closed: Connection<Closed> = conn.close();
+    
+    // 下面两行都无法通过编译（compile_fail）：
+    // closed.send("again");
+    //   → error[E0599]: no method named `send` found for struct `Connection<Closed>`
+    // conn.send("again");
+    //   → error[E0382]: borrow of moved value: `conn`（close已经拿走了所有权）
+    let _ = closed;
+    
+    // 状态只存在于类型中，运行时大小为0
+    println!(
+        "Connection<Open>占用 {} 字节，Connection<Closed>占用 {} 字节",
+        std::mem::size_of::<Connection<Open>>(),
+        std::mem::size_of::<Connection<Closed>>()
+    );
+}
+
+// 18. 常量泛型：固定容量的环形缓冲区 RingBuffer<T, const N: usize>
+// 容量N是类型的一部分：RingBuffer<u32, 5>和RingBuffer<u32, 10>是两种不同的类型
+// - 数据直接放在数组[Option<T>; N]里，不需要堆分配
+// - 写满之后再push会覆盖最旧的元素 → 适合"只保留最近N条"的场景（最近分数、最近日志）
+struct RingBuffer<T, const N: usize> {
+    slots: [Option<T>; N],
+    next: usize, // 下一次写入的位置
+    len: usize,  // 当前元素个数，最多为N
+}
+
+impl<T, const N: usize> RingBuffer<T, N> {
+    fn new() -> Self {
+        // [None; N]要求T: Copy，用from_fn逐个构造就没有这个限制
+        RingBuffer {
+            slots: std::array::from_fn(|_| None),
+            next: 0,
+            len: 0,
+        }
+    }
+
+    // 写满时next正好指向最旧的元素，直接覆盖即可
+    fn push(&mut self, item: T) {
+        if N == 0 {
+            return;
+        }
+        self.slots[self.next] = Some(item);
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    // 从最旧到最新：最旧的元素在next往回数len个的位置
+    // 本文件第8节自定义了一个同名的Iterator trait，这里要写全路径
+    fn iter(&self) -> impl std::iter::Iterator<Item = &T> + '_ {
+        let start = (self.next + N - self.len) % N.max(1);
+        (0..self.len).filter_map(move |i| self.slots[(start + i) % N].as_ref())
+    }
+}
+
+fn ring_buffer_example() {
+    println!("\n=== 常量泛型：环形缓冲区 ===");
+    
+    // 只保留最近5局的分数
+    let mut recent_scores: RingBuffer<u32, 5> = RingBuffer::new();
+    for score in [3, 8, 12, 5, 9, 15, 7] {
+        recent_scores.push(score);
+        let scores: Vec<&u32> = recent_scores.iter().collect();
+        println!("得分 {:>2} → 最近{}局: {:?}", score, recent_scores.len(), scores);
+    }
+    
+    // 同一个结构体也能存字符串事件，容量换成3
+    let mut events: RingBuffer<String, 3> = RingBuffer::new();
+    for event in ["启动", "连接数据库", "收到请求", "返回响应"] {
+        events.push(event.to_string());
+    }
+    println!("最近3条事件: {:?}", events.iter().collect::<Vec<_>>());
+    println!(
+        "RingBuffer<u32, 5>占用 {} 字节（数据就在结构体里）",
+        std::mem::size_of::<RingBuffer<u32, 5>>()
+    );
+}
+
+// 19. 覆盖实现（blanket impl）与扩展trait
+// 普通impl是"给某一个类型实现trait"；覆盖实现是"给满足约束的所有类型实现trait"：
+//   impl<T: AsRef<[i32]> + ?Sized> Summable for T
+// Vec<i32>、[i32; N]、&[i32]、Box<[i32]>……只要能借出&[i32]，就自动拥有sum_all()
+// 标准库里到处是这种写法，例如 impl<T: Display + ?Sized> ToString for T
+//
+// 扩展trait：给别人的类型（这里是标准库的Vec、数组、切片）"加方法"
+// - 孤儿规则要求trait或类型至少有一个是本crate的 → trait是自己定义的，所以可以
+// - 要用这些方法，调用方必须把trait引入作用域（10_error_handling.rs的MyResultExt同理）
+trait Summable {
+    fn sum_all(&self) -> i64;
+}
+
+// ?Sized：让T也可以是[i32]这种动态大小类型，v[..].sum_all()才能直接调用
+// 累加用i64：很多个i32相加不会溢出
+impl<T: AsRef<[i32]> + ?Sized> Summable for T {
+    fn sum_all(&self) -> i64 {
+        self.as_ref().iter().map(|&n| i64::from(n)).sum()
+    }
+}
+
+// 覆盖实现之后，再写 impl Summable for Vec<i32> 会编译失败（E0119，实现冲突）：
+// Vec<i32>已经被上面的impl覆盖了。这也是覆盖实现的代价 —— 不能再为个别类型定制
+
+// 泛型函数只需要约束Summable，不关心具体是哪种容器
+fn report_total<S: Summable + ?Sized>(label: &str, values: &S) {
+    println!("{:<10} 合计 = {}", label, values.sum_all());
+}
+
+fn blanket_impl_example() {
+    println!("\n=== 覆盖实现与扩展trait ===");
+    
+    let scores = vec![90, 85, 77];
+    let array = [1, 2, 3, 4];
+    let slice: &[i32] = &scores[1..];
+    let boxed: Box<[i32]> = vec![-5, 10].into_boxed_slice();
+    
+    println!("Vec<i32>.sum_all() = {}", scores.sum_all());
+    println!("[i32; 4].sum_all() = {}", array.sum_all());
+    println!("&[i32].sum_all() = {}", slice.sum_all());
+    println!("Box<[i32]>.sum_all() = {}", boxed.sum_all());
+    println!("[i32]（v[..]）.sum_all() = {}", scores[..].sum_all());
+    
+    report_total("Vec", &scores);
+    report_total("切片", slice);
+    report_total("空数组", &[0_i32; 0]);
+    
+    // 两个i32::MAX相加会溢出i32，但sum_all用i64累加
+    println!("[i32::MAX; 2].sum_all() = {}", [i32::MAX; 2].sum_all());
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    
+    #[test]
+    fn test_memoize_calls_function_once_per_argument() {
+        let calls = Cell::new(0);
+        let mut double = Memoize::new(|n: u64| {
+            calls.set(calls.get() + 1);
+            n * 2
+        });
+        
+        assert_eq!(double.call(3), 6);
+        assert_eq!(double.call(3), 6);
+        assert_eq!(double.call(4), 8);
+        assert_eq!(double.call(3), 6);
+        assert_eq!(calls.get(), 2);
+    }
+    
+    #[test]
+    fn test_type_state_connection() {
+        let conn = Connection::connect();
+        assert_eq!(conn.send("ping"), 4);
+        let _closed: Connection<Closed> = conn.close();
+        assert_eq!(std::mem::size_of::<Connection<Open>>(), 0);
+    }
+    
+    #[test]
+    fn test_ring_buffer_fills_without_wrapping() {
+        let mut buffer: RingBuffer<i32, 4> = RingBuffer::new();
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.iter().count(), 0);
+        
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+    
+    #[test]
+    fn test_ring_buffer_overwrites_oldest_after_overflow() {
+        let mut buffer: RingBuffer<i32, 3> = RingBuffer::new();
+        for n in 1..=7 {
+            buffer.push(n);
+        }
+        // 只剩最近3个，且从旧到新排列
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+        
+        buffer.push(8);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![6, 7, 8]);
+    }
+    
+    #[test]
+    fn test_memoize_fib() {
+        let mut fib = Memoize::new(naive_fib);
+        assert_eq!(fib.call(10), 55);
+        assert_eq!(fib.call(20), 6765);
+    }
+    
+    #[test]
+    fn test_sum_all_works_for_every_container() {
+        let values = vec![1, 2, 3];
+        assert_eq!(values.sum_all(), 6);
+        assert_eq!(values[..].sum_all(), 6);
+        let tail: &[i32] = &values[1..];
+        assert_eq!(tail.sum_all(), 5);
+        assert_eq!([10, -4].sum_all(), 6);
+        assert_eq!(Box::<[i32]>::from(vec![7, 8]).sum_all(), 15);
+        assert_eq!(Vec::<i32>::new().sum_all(), 0);
+    }
+    
+    #[test]
+    fn test_sum_all_does_not_overflow_i32() {
+        assert_eq!([i32::MAX, i32::MAX].sum_all(), 2 * i32::MAX as i64);
+        assert_eq!(vec![i32::MIN; 3].sum_all(), 3 * i32::MIN as i64);
+    }
+}
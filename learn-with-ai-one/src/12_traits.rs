@@ -35,6 +35,21 @@ pub fn run_example() {
     // 标准库中的Trait
     std_trait_example();
     
+    // Trait继承
+    trait_inheritance_example();
+    
+    // 观察者模式（发布/订阅）
+    observer_example();
+    
+    // Reducer与事件溯源
+    reducer_example();
+    
+    // 基于Trait的表格输出
+    table_example();
+    
+    // 命令注册表（动态分发）
+    command_registry_example();
+    
     println!("\n=== Trait系统示例结束 ===");
 }
 
@@ -165,9 +180,10 @@ fn trait_as_param_return_example() {
     
     // Trait作为返回值（静态分发，只能返回一种类型）
     fn create_shape(shape_type: &str) -> impl Drawable {
+        // 所有分支必须返回同一种具体类型，下面这行会导致编译错误：
+        // "square" => Square { side: 3.0 },
         match shape_type {
             "circle" => Circle { radius: 2.0 },
-            "square" => Square { side: 3.0 },
             _ => Circle { radius: 1.0 },
         }
     }
@@ -357,12 +373,12 @@ fn associated_type_example() {
         fn next(&mut self) -> Option<Self::Item>;
         
         // 使用关联类型的默认方法
-        fn count(self) -> usize
+        fn count(mut self) -> usize
         where
             Self: Sized,
         {
             let mut count = 0;
-            while let Some(_) = self.next() {
+            while self.next().is_some() {
                 count += 1;
             }
             count
@@ -456,7 +472,7 @@ fn trait_object_example() {
 fn operator_overloading_example() {
     println!("9. 运算符重载:");
     
-    use std::ops::{Add, Sub, Mul, Div};
+    use std::ops::{Add, Sub, Mul};
     
     struct Vector2D {
         x: f64,
@@ -627,7 +643,422 @@ fn trait_inheritance_example() {
     person.give_birth();
     println!();
 }
+// 12. 观察者模式（发布/订阅）
+// 用Trait对象实现一个简单的事件总线：
+// Subject持有一组Box<dyn Observer>，事件发生时逐个通知
+
+// 事件：主题 + 消息
+#[derive(Debug, Clone, PartialEq)]
+struct Event {
+    topic: String,
+    message: String,
+}
+
+// 观察者Trait：只要实现on_event，就能订阅事件
+trait Observer {
+    fn on_event(&self, event: &Event);
+}
+
+// 主题（被观察者），保存所有订阅者
+struct Subject {
+    observers: Vec<Box<dyn Observer>>,
+}
+
+impl Subject {
+    fn new() -> Self {
+        Subject { observers: Vec::new() }
+    }
+    
+    // 订阅：把观察者放进列表
+    fn subscribe(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+    
+    // 广播：按订阅顺序通知所有观察者
+    fn notify_all(&self, event: &Event) {
+        for observer in &self.observers {
+            observer.on_event(event);
+        }
+    }
+}
+
+// 观察者1：输出一行简短日志
+struct ConsoleLogger;
+
+impl Observer for ConsoleLogger {
+    fn on_event(&self, event: &Event) {
+        println!("[日志] {}: {}", event.topic, event.message);
+    }
+}
+
+// 观察者2：模拟发送邮件通知，输出格式不同
+struct EmailNotifier {
+    to: String,
+}
+
+impl Observer for EmailNotifier {
+    fn on_event(&self, event: &Event) {
+        println!("发送邮件给 {} -> 主题《{}》，内容：{}", self.to, event.topic, event.message);
+    }
+}
+
+fn observer_example() {
+    println!("12. 观察者模式:");
+    
+    let mut subject = Subject::new();
+    subject.subscribe(Box::new(ConsoleLogger));
+    subject.subscribe(Box::new(EmailNotifier { to: "admin@example.com".to_string() }));
+    
+    let event = Event {
+        topic: "订单".to_string(),
+        message: "订单 #1001 已支付".to_string(),
+    };
+    // 一次广播，两个观察者以各自的方式处理
+    subject.notify_all(&event);
+    println!();
+}
+
+// 13. Reducer与事件溯源（关联类型驱动的状态机）
+// Reducer描述"状态 + 动作 -> 新状态"的纯函数，State和Action用关联类型表示：
+// 每个Reducer只对应一种状态和一种动作，不需要调用方再写泛型参数
+trait Reducer {
+    type State;
+    type Action;
+    
+    fn reduce(state: Self::State, action: Self::Action) -> Self::State;
+}
+
+// 计数器的动作
+#[derive(Debug, Clone, PartialEq)]
+enum CounterAction {
+    Increment,
+    Decrement,
+    Reset,
+}
+
+// 计数器Reducer：本身不存数据，只提供reduce逻辑
+struct CounterReducer;
+
+impl Reducer for CounterReducer {
+    type State = i32;
+    type Action = CounterAction;
+    
+    fn reduce(state: i32, action: CounterAction) -> i32 {
+        match action {
+            CounterAction::Increment => state + 1,
+            CounterAction::Decrement => state - 1,
+            CounterAction::Reset => 0,
+        }
+    }
+}
+
+// Store：保存当前状态和所有历史动作（事件溯源）
+// 通过R::State / R::Action引用关联类型，Store对任何Reducer都适用
+struct Store<R: Reducer> {
+    state: R::State,
+    history: Vec<R::Action>,
+}
+
+impl<R> Store<R>
+where
+    R: Reducer,
+    R::State: Clone,
+    R::Action: Clone,
+{
+    fn new(initial: R::State) -> Self {
+        Store { state: initial, history: Vec::new() }
+    }
+    
+    // 派发一个动作：记录到历史，并计算新状态
+    fn dispatch(&mut self, action: R::Action) {
+        self.history.push(action.clone());
+        self.state = R::reduce(self.state.clone(), action);
+    }
+    
+    // 依次派发一串动作
+    fn dispatch_all(&mut self, actions: impl IntoIterator<Item = R::Action>) {
+        for action in actions {
+            self.dispatch(action);
+        }
+    }
+    
+    // 从初始状态重放全部历史动作，得到的结果应与当前状态一致
+    fn replay(&self, initial: R::State) -> R::State {
+        self.history.iter().cloned().fold(initial, R::reduce)
+    }
+}
+
+fn reducer_example() {
+    println!("13. Reducer与事件溯源:");
+    
+    let mut store: Store<CounterReducer> = Store::new(0);
+    store.dispatch_all([
+        CounterAction::Increment,
+        CounterAction::Increment,
+        CounterAction::Decrement,
+        CounterAction::Reset,
+        CounterAction::Increment,
+        CounterAction::Increment,
+    ]);
+    
+    println!("动作历史: {:?}", store.history);
+    println!("当前状态: {}", store.state);
+    println!("从0重放历史得到: {}", store.replay(0));
+    println!("从10重放历史得到: {}", store.replay(10));
+    println!();
+}
+
+// 14. 基于Trait的表格格式化
+// 任何类型只要说明"表头是什么、每行有哪些单元格"，就能用同一个函数打印成对齐的表格
+// - headers是关联函数（没有self）：表头属于类型本身，而不是某个值
+// - cells是方法：每一行的数据来自具体的值
+trait TableRow {
+    fn cells(&self) -> Vec<String>;
+    fn headers() -> Vec<String>;
+}
+
+// 生成表格字符串：先算出每列最宽的内容，再按列宽补齐
+// 列宽按字符数计算，适合ASCII内容（中文在终端里通常占两格，会略微错位）
+fn format_table<T: TableRow>(rows: &[T]) -> String {
+    let headers = T::headers();
+    let body: Vec<Vec<String>> = rows.iter().map(|row| row.cells()).collect();
+    
+    // 每列宽度 = max(表头宽度, 该列所有单元格宽度)
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for cells in &body {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    
+    let separator = format!(
+        "+{}+",
+        widths.iter().map(|w| "-".repeat(w + 2)).collect::<Vec<_>>().join("+")
+    );
+    let render_row = |cells: &[String]| {
+        let padded: Vec<String> = widths
+            .iter()
+            .zip(cells)
+            .map(|(w, cell)| format!(" {:<width$} ", cell, width = w))
+            .collect();
+        format!("|{}|", padded.join("|"))
+    };
+    
+    let mut lines = vec![separator.clone(), render_row(&headers), separator.clone()];
+    lines.extend(body.iter().map(|cells| render_row(cells)));
+    lines.push(separator);
+    lines.join("\n")
+}
+
+fn print_table<T: TableRow>(rows: &[T]) {
+    println!("{}", format_table(rows));
+}
+
+struct Person {
+    name: String,
+    age: u32,
+    city: String,
+}
+
+impl TableRow for Person {
+    fn cells(&self) -> Vec<String> {
+        vec![self.name.clone(), self.age.to_string(), self.city.clone()]
+    }
+    
+    fn headers() -> Vec<String> {
+        vec!["Name".to_string(), "Age".to_string(), "City".to_string()]
+    }
+}
+
+fn table_example() {
+    println!("14. 基于Trait的表格输出:");
+    
+    let people = vec![
+        Person { name: "Alice".to_string(), age: 30, city: "Beijing".to_string() },
+        Person { name: "Bob".to_string(), age: 7, city: "Shanghai".to_string() },
+        Person { name: "Christopher".to_string(), age: 102, city: "Xi'an".to_string() },
+    ];
+    print_table(&people);
+    println!();
+}
+
+// 15. 命令注册表（Trait对象 + HashMap）
+// 插件式架构：注册表只认识Command这个Trait，具体有哪些命令运行时才注册进来
+// - 按名字查找 → HashMap<String, Box<dyn Command>>
+// - 调用run时走虚表（动态分发），新增命令不需要改Registry的代码
+trait Command {
+    fn name(&self) -> &str;
+    fn run(&self, args: &[&str]) -> String;
+}
+
+struct Registry {
+    commands: std::collections::HashMap<String, Box<dyn Command>>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Registry { commands: std::collections::HashMap::new() }
+    }
+    
+    // 以命令自己报告的名字为键；同名命令后注册的覆盖先注册的
+    fn register(&mut self, command: Box<dyn Command>) {
+        self.commands.insert(command.name().to_string(), command);
+    }
+    
+    // 按空白切分一行输入：第一个词是命令名，其余是参数
+    fn dispatch(&self, line: &str) -> Result<String, String> {
+        let mut words = line.split_whitespace();
+        let name = words.next().ok_or_else(|| "空命令".to_string())?;
+        let args: Vec<&str> = words.collect();
+        
+        match self.commands.get(name) {
+            Some(command) => Ok(command.run(&args)),
+            None => Err(format!("未知命令: {}", name)),
+        }
+    }
+}
+
+// 命令1：原样输出参数
+struct EchoCommand;
+
+impl Command for EchoCommand {
+    fn name(&self) -> &str {
+        "echo"
+    }
+    
+    fn run(&self, args: &[&str]) -> String {
+        args.join(" ")
+    }
+}
+
+// 命令2：把所有参数当整数相加；有解析失败的参数就报告出来
+struct AddCommand;
+
+impl Command for AddCommand {
+    fn name(&self) -> &str {
+        "add"
+    }
+    
+    fn run(&self, args: &[&str]) -> String {
+        let mut sum: i64 = 0;
+        for arg in args {
+            match arg.parse::<i64>() {
+                Ok(n) => sum += n,
+                Err(_) => return format!("add: 不是整数: {}", arg),
+            }
+        }
+        sum.to_string()
+    }
+}
+
+fn command_registry_example() {
+    println!("15. 命令注册表（动态分发）:");
+    
+    let mut registry = Registry::new();
+    registry.register(Box::new(EchoCommand));
+    registry.register(Box::new(AddCommand));
+    
+    for line in ["echo hello trait objects", "add 1 2 3 4", "add 1 two", "mul 2 3", "   "] {
+        match registry.dispatch(line) {
+            Ok(output) => println!("> {:<26} => {}", line, output),
+            Err(err) => println!("> {:<26} => 错误: {}", line, err),
+        }
+    }
+    println!();
+}
+
 // 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
 fn main() {
     run_example();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    
+    // 把收到的事件记录到共享列表中的观察者
+    struct RecordingObserver {
+        name: &'static str,
+        received: Rc<RefCell<Vec<(&'static str, Event)>>>,
+    }
+    
+    impl Observer for RecordingObserver {
+        fn on_event(&self, event: &Event) {
+            self.received.borrow_mut().push((self.name, event.clone()));
+        }
+    }
+    
+    #[test]
+    fn test_counter_store_reduces_and_replays() {
+        let mut store: Store<CounterReducer> = Store::new(0);
+        store.dispatch_all([
+            CounterAction::Increment,
+            CounterAction::Increment,
+            CounterAction::Increment,
+            CounterAction::Decrement,
+        ]);
+        assert_eq!(store.state, 2);
+        assert_eq!(store.history.len(), 4);
+        assert_eq!(store.replay(0), store.state);
+        
+        store.dispatch(CounterAction::Reset);
+        assert_eq!(store.state, 0);
+        // Reset之后的结果与初始状态无关
+        assert_eq!(store.replay(100), 0);
+    }
+    
+    #[test]
+    fn test_format_table_lines_have_equal_width() {
+        let people = vec![
+            Person { name: "Al".to_string(), age: 5, city: "X".to_string() },
+            Person { name: "Bartholomew".to_string(), age: 42, city: "Guangzhou".to_string() },
+        ];
+        let table = format_table(&people);
+        let lines: Vec<&str> = table.lines().collect();
+        
+        // 3条分隔线 + 表头 + 2行数据
+        assert_eq!(lines.len(), 6);
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+        // 最长的名字决定第一列宽度
+        assert_eq!(lines[4], "| Bartholomew | 42  | Guangzhou |");
+    }
+    
+    #[test]
+    fn test_registry_dispatches_to_matching_command() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(EchoCommand));
+        registry.register(Box::new(AddCommand));
+        
+        assert_eq!(registry.dispatch("echo a  b c"), Ok("a b c".to_string()));
+        assert_eq!(registry.dispatch("  add 10 -3 5 "), Ok("12".to_string()));
+        assert_eq!(registry.dispatch("add"), Ok("0".to_string()));
+        assert_eq!(registry.dispatch("add 1 x"), Ok("add: 不是整数: x".to_string()));
+    }
+    
+    #[test]
+    fn test_registry_reports_unknown_and_empty_commands() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(EchoCommand));
+        
+        assert_eq!(registry.dispatch("add 1 2"), Err("未知命令: add".to_string()));
+        assert_eq!(registry.dispatch(""), Err("空命令".to_string()));
+    }
+    
+    #[test]
+    fn test_notify_all_reaches_every_observer() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let mut subject = Subject::new();
+        subject.subscribe(Box::new(RecordingObserver { name: "a", received: Rc::clone(&received) }));
+        subject.subscribe(Box::new(RecordingObserver { name: "b", received: Rc::clone(&received) }));
+        
+        let event = Event { topic: "t".to_string(), message: "m".to_string() };
+        subject.notify_all(&event);
+        
+        assert_eq!(*received.borrow(), vec![("a", event.clone()), ("b", event)]);
+    }
+}
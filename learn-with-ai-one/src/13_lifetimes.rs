@@ -1,6 +1,9 @@
 // Rust生命周期详解
 // 生命周期是Rust中管理引用有效性的核心机制，用于防止悬垂引用和确保内存安全
 
+use std::cell::{Cell, RefCell};
+use std::time::Instant;
+
 pub fn run_example() {
     println!("=== Rust生命周期示例 ===\n");
 
@@ -31,6 +34,12 @@ pub fn run_example() {
     // 高级生命周期示例
     advanced_lifetime_example();
     
+    // 作用域结束与Drop（RAII）
+    drop_order_example();
+    
+    // 借用连接池的连接守卫（RAII + 生命周期）
+    db_guard_example();
+    
     println!("\n=== 生命周期示例结束 ===");
 }
 
@@ -429,4 +438,171 @@ fn practical_lifetime_example() {
     let extended_view = DocumentView::new(&doc, 15, 30);
     println!("扩展视图内容: {}", extended_view.get_content());
     println!();
-}
\ No newline at end of file
+}
+
+// 11. 作用域结束与Drop trait（RAII）
+// 值离开作用域时，Rust会自动调用它的Drop::drop方法，这就是RAII：
+// 资源的获取与对象的生命周期绑定，离开作用域即释放（文件关闭、锁释放、计时结束……）
+// 同一作用域内的变量按声明的相反顺序被drop：后创建的先销毁
+
+// 作用域计时器：创建时记录开始时间，被drop时打印耗时
+// label是借用的&str，生命周期'a保证计时器不会比它引用的标签活得更久
+struct ScopedTimer<'a> {
+    label: &'a str,
+    start: Instant,
+}
+
+impl<'a> ScopedTimer<'a> {
+    fn new(label: &'a str) -> Self {
+        println!("  开始: {}", label);
+        ScopedTimer { label, start: Instant::now() }
+    }
+}
+
+impl Drop for ScopedTimer<'_> {
+    fn drop(&mut self) {
+        println!("  结束: {}（耗时 {:?}）", self.label, self.start.elapsed());
+    }
+}
+
+fn drop_order_example() {
+    println!("11. 作用域结束与Drop:");
+    
+    let outer_label = String::from("外层作用域");
+    let _outer = ScopedTimer::new(&outer_label);
+    {
+        let _first = ScopedTimer::new("内层-第一个");
+        let _second = ScopedTimer::new("内层-第二个");
+        println!("  内层作用域即将结束");
+        // 离开这里时先drop _second，再drop _first
+    }
+    
+    // 注意：如果写成 let _ = ScopedTimer::new(...)，值不会绑定到变量，会立刻被drop
+    let _ = ScopedTimer::new("未绑定的计时器");
+    
+    println!("  外层作用域即将结束");
+    // 函数返回时drop _outer，之后才释放outer_label（声明顺序相反）
+}
+
+// 12. 连接守卫：生命周期 + RAII
+// 真实的连接池（sqlx的PgPool）里，acquire()返回的连接在drop时自动还给连接池，
+// 忘了"归还"这件事在Rust里根本不会发生。这里用一个玩具连接池模拟同样的模式：
+// - DbGuard<'a>借用了连接池（&'a ConnectionPool）和连接名（&'a str）
+//   → 编译器保证守卫不会比连接池活得更久，不可能"归还"到一个已经销毁的池
+// - 创建时记录acquired，Drop时记录released并把连接数减一
+// - 同一作用域里的两个守卫按相反顺序归还
+struct ConnectionPool {
+    active: Cell<usize>,        // 当前借出的连接数
+    events: RefCell<Vec<String>>, // 按时间顺序记录的借出/归还事件
+}
+
+impl ConnectionPool {
+    fn new() -> Self {
+        ConnectionPool { active: Cell::new(0), events: RefCell::new(Vec::new()) }
+    }
+    
+    // 返回的DbGuard借用了self：只要守卫还活着，连接池就不能被move或drop
+    fn acquire<'a>(&'a self, name: &'a str) -> DbGuard<'a> {
+        self.active.set(self.active.get() + 1);
+        self.record(format!("acquired {}", name));
+        DbGuard { name, pool: self }
+    }
+    
+    fn record(&self, event: String) {
+        println!("  {}（借出中: {}）", event, self.active.get());
+        self.events.borrow_mut().push(event);
+    }
+}
+
+struct DbGuard<'a> {
+    name: &'a str,
+    pool: &'a ConnectionPool,
+}
+
+impl DbGuard<'_> {
+    fn query(&self, sql: &str) {
+        println!("  [{}] 执行: {}", self.name, sql);
+    }
+}
+
+impl Drop for DbGuard<'_> {
+    fn drop(&mut self) {
+        self.pool.active.set(self.pool.active.get() - 1);
+        self.pool.record(format!("released {}", self.name));
+    }
+}
+
+fn db_guard_example() {
+    println!("12. 连接守卫（生命周期 + RAII）:");
+    
+    let pool = ConnectionPool::new();
+    {
+        let reader = pool.acquire("reader");
+        let writer = pool.acquire("writer");
+        reader.query("SELECT * FROM course");
+        writer.query("INSERT INTO course ...");
+        // 离开作用域：先归还writer，再归还reader
+    }
+    
+    // 提前归还：drop(guard)和离开作用域效果一样
+    let temp = pool.acquire("temp");
+    drop(temp);
+    
+    // 下面的代码无法编译：守卫借用了pool，pool不能先于守卫被销毁
+    // let guard;
+    // {
+    //     let short_lived = ConnectionPool::new();
+    //     guard = short_lived.acquire("leak");
+    // } // error[E0597]: `short_lived` does not live long enough
+    
+    println!("  全部归还后借出中: {}", pool.active.get());
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConnectionPool;
+    use std::cell::RefCell;
+    
+    // drop时把自己的标签记录到共享列表中
+    struct DropRecorder<'a> {
+        label: &'a str,
+        log: &'a RefCell<Vec<&'a str>>,
+    }
+    
+    impl Drop for DropRecorder<'_> {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.label);
+        }
+    }
+    
+    #[test]
+    fn test_drop_order_is_reverse_of_declaration() {
+        let log = RefCell::new(Vec::new());
+        {
+            let _a = DropRecorder { label: "a", log: &log };
+            {
+                let _b = DropRecorder { label: "b", log: &log };
+                let _c = DropRecorder { label: "c", log: &log };
+            }
+            // 内层作用域结束时c、b已按相反顺序drop
+            assert_eq!(*log.borrow(), vec!["c", "b"]);
+        }
+        assert_eq!(*log.borrow(), vec!["c", "b", "a"]);
+    }
+    
+    #[test]
+    fn test_db_guards_release_in_reverse_order() {
+        let pool = ConnectionPool::new();
+        {
+            let _first = pool.acquire("first");
+            let _second = pool.acquire("second");
+            assert_eq!(pool.active.get(), 2);
+        }
+        assert_eq!(pool.active.get(), 0);
+        assert_eq!(
+            *pool.events.borrow(),
+            vec!["acquired first", "acquired second", "released second", "released first"]
+        );
+    }
+}
@@ -1,11 +1,14 @@
 // Rust异步编程（async/await）详解
 // 本文件介绍Rust中的异步编程模型，包括async函数、await表达式、Future等概念
 
-use std::time::Duration;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 use std::thread;
+use tokio::sync::mpsc;
 
 // 注意：实际运行异步代码需要使用tokio或async-std等运行时
-// 这里我们使用模拟的方式展示异步编程的概念
+// 第 1~8 节使用模拟的方式展示异步编程的概念，第 9 节起使用 tokio 运行时真正执行
 
 pub fn run_example() {
     println!("=== Rust异步编程（async/await）===\n");
@@ -33,6 +36,12 @@ pub fn run_example() {
     
     // 实际应用示例
     async_practical_example();
+
+    // 有界通道与背压（tokio）
+    bounded_channel_example();
+
+    // select!：竞速与超时（tokio）
+    select_example();
     
     println!("\n=== 示例结束 ===");
 }
@@ -446,4 +455,161 @@ fn simulate_async_web_request() {
     simulate_web_client();
     
     println!("\n异步Web客户端模拟完成");
-}
\ No newline at end of file
+}
+
+// 9. 有界通道与背压（tokio::sync::mpsc）
+// 有界通道最多缓存 capacity 个元素：满了以后 send().await 会挂起，直到消费者取走一个
+// 这样快的生产者会被自动"拖慢"到消费者的速度（背压），内存不会无限增长
+
+// 单线程的 tokio 运行时：示例和测试都用它来执行 async 代码
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("failed to build tokio runtime")
+}
+
+fn bounded_channel_example() {
+    println!("9. 有界通道与背压:");
+
+    let n = 20;
+    let start = Instant::now();
+    let (received, producer_waited) =
+        runtime().block_on(produce_and_consume(n, 4, Duration::from_millis(5)));
+    let elapsed = start.elapsed();
+
+    println!("收到 {} 个元素: {:?}", received.len(), received);
+    println!("生产者因通道已满累计等待: {:?}", producer_waited);
+    println!(
+        "耗时 {:?}，吞吐量 {:.0} 个/秒",
+        elapsed,
+        n as f64 / elapsed.as_secs_f64()
+    );
+    println!();
+}
+
+// 一个生产者发送 0..n，一个消费者逐个接收（每个元素处理 consumer_delay）
+// 返回 (按接收顺序排列的元素, 生产者在 send 上累计等待的时间)
+async fn produce_and_consume(n: u32, capacity: usize, consumer_delay: Duration) -> (Vec<u32>, Duration) {
+    let (tx, mut rx) = mpsc::channel(capacity);
+
+    // 生产者：tokio::spawn 成独立任务；tx 被 move 进去，任务结束时 drop → 通道关闭
+    let producer = tokio::spawn(async move {
+        let mut waited = Duration::ZERO;
+        for i in 0..n {
+            let before = Instant::now();
+            tx.send(i).await.expect("consumer dropped the receiver");
+            waited += before.elapsed();
+        }
+        waited
+    });
+
+    // 消费者：recv() 在通道关闭且取空后返回 None，循环结束
+    let mut received = Vec::with_capacity(n as usize);
+    while let Some(item) = rx.recv().await {
+        tokio::time::sleep(consumer_delay).await; // 模拟处理得慢
+        received.push(item);
+    }
+
+    let waited = producer.await.expect("producer task panicked");
+    (received, waited)
+}
+
+// 10. tokio::select!：同时等待多个 future，谁先完成就走谁的分支
+// 其余没完成的 future 会被直接 drop → 这就是 Rust 异步里的"取消"
+// （future 是惰性的状态机，drop 之后不会再被 poll，里面的代码也就不会继续执行）
+
+#[derive(Debug, PartialEq)]
+enum RaceOutcome {
+    Finished(&'static str), // 某个任务先完成
+    TimedOut,               // 两个任务都没赶在超时前完成
+}
+
+// drop 时把自己的名字记进 log：用来观察哪个 future 被取消了
+struct DropNotice {
+    name: &'static str,
+    finished: bool,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Drop for DropNotice {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.log.borrow_mut().push(self.name);
+        }
+    }
+}
+
+// 一个"任务"：睡 ms 毫秒后返回名字；没睡完就被 drop → 记为已取消
+async fn named_task(name: &'static str, ms: u64, log: Rc<RefCell<Vec<&'static str>>>) -> &'static str {
+    let mut notice = DropNotice { name, finished: false, log };
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    notice.finished = true;
+    name
+}
+
+// 让 a、b 两个任务赛跑，再加一个超时分支
+// 返回 (结果, 被取消的任务名)
+async fn race(a_ms: u64, b_ms: u64, timeout_ms: u64) -> (RaceOutcome, Vec<&'static str>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    let outcome = tokio::select! {
+        name = named_task("A", a_ms, log.clone()) => RaceOutcome::Finished(name),
+        name = named_task("B", b_ms, log.clone()) => RaceOutcome::Finished(name),
+        _ = tokio::time::sleep(Duration::from_millis(timeout_ms)) => RaceOutcome::TimedOut,
+    };
+    // select! 返回时，没完成的分支已经被 drop 了
+
+    let cancelled = log.borrow().clone();
+    (outcome, cancelled)
+}
+
+fn select_example() {
+    println!("10. tokio::select! 竞速与超时:");
+    let rt = runtime();
+
+    let (outcome, cancelled) = rt.block_on(race(20, 200, 1000));
+    println!("A(20ms) vs B(200ms)，超时 1000ms → {:?}，被取消: {:?}", outcome, cancelled);
+
+    let (outcome, cancelled) = rt.block_on(race(300, 400, 50));
+    println!("A(300ms) vs B(400ms)，超时 50ms → {:?}，被取消: {:?}", outcome, cancelled);
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_channel_delivers_all_items_in_order() {
+        let (received, _) = runtime().block_on(produce_and_consume(100, 8, Duration::ZERO));
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn slow_consumer_applies_backpressure() {
+        // 容量 2、消费者每个元素 2ms → 生产者必然在 send 上等过
+        let (received, waited) = runtime().block_on(produce_and_consume(10, 2, Duration::from_millis(2)));
+        assert_eq!(received.len(), 10);
+        assert!(waited > Duration::ZERO);
+    }
+
+    #[test]
+    fn select_picks_faster_future_and_cancels_loser() {
+        let (outcome, cancelled) = runtime().block_on(race(10, 1000, 2000));
+        assert_eq!(outcome, RaceOutcome::Finished("A"));
+        assert_eq!(cancelled, vec!["B"]);
+
+        let (outcome, cancelled) = runtime().block_on(race(1000, 10, 2000));
+        assert_eq!(outcome, RaceOutcome::Finished("B"));
+        assert_eq!(cancelled, vec!["A"]);
+    }
+
+    #[test]
+    fn select_timeout_cancels_both() {
+        let (outcome, mut cancelled) = runtime().block_on(race(1000, 2000, 10));
+        assert_eq!(outcome, RaceOutcome::TimedOut);
+        cancelled.sort();
+        assert_eq!(cancelled, vec!["A", "B"]);
+    }
+}
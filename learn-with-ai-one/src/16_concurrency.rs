@@ -0,0 +1,129 @@
+// Rust并发编程详解：线程、通道与共享状态
+// 本文件介绍如何使用标准库的 std::thread、std::sync::mpsc 和 Arc<Mutex<T>> 编写多线程程序
+// 它连接了所有权章节（move语义）和异步章节（并发执行）
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub fn run_example() {
+    println!("=== Rust并发编程（线程与通道）===\n");
+
+    // 线程 + 通道：分片并行求和
+    channel_example();
+
+    // Arc<Mutex<T>>：多个线程共享并修改同一个计数器
+    shared_state_example();
+
+    println!("\n=== 示例结束 ===");
+}
+
+// 用于单独运行本文件的main函数
+// 作为模块被 main.rs 引用时不会用到
+#[allow(dead_code)]
+fn main() {
+    run_example();
+}
+
+// 1. 线程与通道（mpsc：multiple producer, single consumer）
+fn channel_example() {
+    println!("1. 线程与通道:");
+
+    let data: Vec<i64> = (1..=100).collect();
+    let total = parallel_sum(&data, 4);
+
+    println!("并行求和结果: {}", total);
+    println!("串行求和结果: {}", data.iter().sum::<i64>());
+    println!();
+}
+
+// 把数据切成若干片，每个工作线程计算一片的和，通过通道把结果发回主线程汇总
+fn parallel_sum(data: &[i64], workers: usize) -> i64 {
+    // 每片的长度向上取整，保证所有元素都被分到某个线程
+    let chunk_size = data.len().div_ceil(workers.max(1)).max(1);
+
+    // tx是发送端，rx是接收端
+    let (tx, rx) = mpsc::channel();
+
+    for (id, chunk) in data.chunks(chunk_size).enumerate() {
+        // 每个线程都需要自己的发送端，克隆tx即可（多个生产者）
+        let tx = tx.clone();
+        // thread::spawn要求闭包是'static的，不能借用data，因此复制一份数据并move进线程
+        let chunk = chunk.to_vec();
+        thread::spawn(move || {
+            let partial: i64 = chunk.iter().sum();
+            println!(
+                "  工作线程 {} 计算了 {} 个元素，部分和 = {}",
+                id,
+                chunk.len(),
+                partial
+            );
+            tx.send(partial).unwrap();
+        });
+    }
+
+    // 丢弃主线程持有的原始发送端，
+    // 否则通道永远不会关闭，下面的 rx.iter() 会一直阻塞
+    drop(tx);
+
+    // rx.iter() 会阻塞等待消息，直到所有发送端都被丢弃后结束
+    rx.iter().sum()
+}
+
+// 2. 共享状态：Arc<Mutex<T>>
+fn shared_state_example() {
+    println!("2. Arc<Mutex<T>>共享状态:");
+
+    let count = shared_counter(8, 1000);
+    println!("8个线程各自加1000次，计数器最终值: {}", count);
+    println!();
+}
+
+// 多个线程对同一个计数器加1
+// - Arc<T>：原子引用计数，可以在线程间共享所有权（Rc<T>不是线程安全的，不能跨线程）
+// - Mutex<T>：互斥锁，同一时刻只有一个线程能拿到内部数据的可变访问权
+fn shared_counter(threads: usize, increments: usize) -> i32 {
+    let counter = Arc::new(Mutex::new(0));
+    let mut handles = Vec::new();
+
+    for _ in 0..threads {
+        let counter = Arc::clone(&counter);
+        let handle = thread::spawn(move || {
+            for _ in 0..increments {
+                // lock()返回MutexGuard，离开作用域时自动解锁（RAII）
+                let mut num = counter.lock().unwrap();
+                *num += 1;
+            }
+        });
+        handles.push(handle);
+    }
+
+    // join等待所有线程结束，确保读取时所有加法都已完成
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // 所有线程都已结束，它们持有的Arc克隆也都被释放了，
+    // 此时只剩一个所有者，可以用try_unwrap取回Mutex，再用into_inner取出内部的值
+    Arc::try_unwrap(counter).unwrap().into_inner().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_sum_matches_serial() {
+        let data: Vec<i64> = (1..=1000).collect();
+        let serial: i64 = data.iter().sum();
+        for workers in [1, 3, 4, 7, 2000] {
+            assert_eq!(parallel_sum(&data, workers), serial);
+        }
+        assert_eq!(parallel_sum(&[], 4), 0);
+    }
+
+    #[test]
+    fn test_shared_counter() {
+        assert_eq!(shared_counter(8, 1000), 8000);
+    }
+}
@@ -4,34 +4,54 @@
 use std::process::Command;
 use std::path::Path;
 use std::io::{self, BufRead};
+use std::time::Instant;
 
-// 导入13-15号文件，这些文件已经有run_example函数
+// 导入已经改写为模块的文件，这些文件都有run_example函数
+#[path = "02_data_types.rs"] mod data_types;
+#[path = "04_control_flow.rs"] mod control_flow;
+#[path = "05_ownership.rs"] mod ownership;
+#[path = "06_structs.rs"] mod structs;
+#[path = "07_enums.rs"] mod enums;
+#[path = "08_collections.rs"] mod collections;
+#[path = "10_error_handling.rs"] mod error_handling;
+#[path = "11_generics.rs"] mod generics;
+#[path = "12_traits.rs"] mod traits;
 #[path = "13_lifetimes.rs"] mod lifetimes;
 #[path = "14_std_lib_macros.rs"] mod std_lib_macros;
 #[path = "15_async_await.rs"] mod async_await;
+#[path = "16_concurrency.rs"] mod concurrency;
+
+// 示例标题表：下标 + 1 就是菜单编号
+// 除了输入编号，也可以输入标题中的关键字（如 "trait"、"生命周期"）来选择示例
+const EXAMPLE_TITLES: [&str; 16] = [
+    "变量和可变性",
+    "数据类型",
+    "函数",
+    "控制流",
+    "所有权",
+    "结构体",
+    "枚举和模式匹配",
+    "集合",
+    "包、 crate 和模块",
+    "错误处理",
+    "泛型",
+    "Trait",
+    "生命周期",
+    "常用标准库函数与实用宏",
+    "异步编程（async/await）",
+    "并发编程（线程与通道）",
+];
 
 fn main() {
-    println!("=== Rust学习示例 ===\n");
+    println!("{}\n", colorize("=== Rust学习示例 ===", ANSI_BOLD_CYAN));
     println!("请选择要运行的示例：");
-    println!("1. 变量和可变性");
-    println!("2. 数据类型");
-    println!("3. 函数");
-    println!("4. 控制流");
-    println!("5. 所有权");
-    println!("6. 结构体");
-    println!("7. 枚举和模式匹配");
-    println!("8. 集合");
-    println!("9. 包、 crate 和模块");
-    println!("10. 错误处理");
-    println!("11. 泛型");
-    println!("12. Trait");
-    println!("13. 生命周期");
-    println!("14. 常用标准库函数与实用宏");
-    println!("15. 异步编程（async/await）");
+    for (i, title) in EXAMPLE_TITLES.iter().enumerate() {
+        println!("{}. {}", i + 1, title);
+    }
     println!("0. 退出");
     println!();
     
-    print!("请输入选择 (0-15): ");
+    print!("请输入编号或关键字 (0-16): ");
     // 手动刷新输出缓冲区，确保提示信息先显示
     std::io::Write::flush(&mut std::io::stdout()).unwrap();
     
@@ -42,50 +62,110 @@ fn main() {
     
     loop {
         input.clear();
-        if let Err(e) = stdin.lock().read_line(&mut input) {
-            println!("读取输入错误: {}", e);
-            continue;
+        match stdin.lock().read_line(&mut input) {
+            // 输入流已结束（例如管道输入读完），没有更多选择可读
+            Ok(0) => {
+                println!("\n输入结束，退出程序");
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                println!("读取输入错误: {}", e);
+                continue;
+            }
         }
         
         // 去除输入中的换行符和空格
         let trimmed = input.trim();
+        if trimmed.is_empty() {
+            println!("请输入编号或关键字");
+            continue;
+        }
         
         // 尝试解析为u8
         match trimmed.parse::<u8>() {
-            Ok(num) if num <= 15 => {
+            Ok(num) if num <= 16 => {
                 choice = num;
                 break;
             }
-            Ok(_) => println!("选择无效，请输入0-15之间的数字"),
-            Err(_) => println!("输入格式错误，请输入数字"),
+            Ok(_) => println!("选择无效，请输入0-16之间的数字"),
+            // 不是数字：按关键字匹配示例标题
+            Err(_) => {
+                let matches = find_examples(trimmed);
+                match matches.as_slice() {
+                    [num] => {
+                        choice = *num;
+                        break;
+                    }
+                    [] => println!("没有标题包含 \"{}\" 的示例，请重新输入", trimmed),
+                    candidates => {
+                        println!("找到多个匹配的示例，请输入编号：");
+                        for num in candidates {
+                            println!("  {}. {}", num, EXAMPLE_TITLES[*num as usize - 1]);
+                        }
+                    }
+                }
+            }
         }
     }
     
-    println!("\n您选择了: {}", choice);
+    println!("\n{}", colorize(&format!("您选择了: {}", choice), ANSI_GREEN));
     
+    // 记录示例运行耗时
+    let start = Instant::now();
     match choice {
         1 => run_example_file("01_variables"),
-        2 => run_example_file("02_data_types"),
+        2 => data_types::run_example(),
         3 => run_example_file("03_functions"),
-        4 => run_example_file("04_control_flow"),
-        5 => run_example_file("05_ownership"),
-        6 => run_example_file("06_structs"),
-        7 => run_example_file("07_enums"),
-        8 => run_example_file("08_collections"),
+        4 => control_flow::run_example(),
+        5 => ownership::run_example(),
+        6 => structs::run_example(),
+        7 => enums::run_example(),
+        8 => collections::run_example(),
         9 => run_example_file("09_packages_modules"),
-        10 => run_example_file("10_error_handling"),
-        11 => run_example_file("11_generics"),
-        12 => run_example_file("12_traits"),
+        10 => error_handling::run_example(),
+        11 => generics::run_example(),
+        12 => traits::run_example(),
         13 => lifetimes::run_example(),
         14 => std_lib_macros::run_example(),
         15 => async_await::run_example(),
+        16 => concurrency::run_example(),
         0 => println!("退出程序") ,
         _ => println!("无效选择") ,
     }
+    if choice != 0 {
+        println!("\n(completed in {} ms)", start.elapsed().as_millis());
+    }
     
     println!("\n=== 程序结束 ===");
 }
 
+// 按关键字查找示例，返回所有标题包含该关键字的菜单编号（不区分大小写）
+fn find_examples(keyword: &str) -> Vec<u8> {
+    let keyword = keyword.to_lowercase();
+    EXAMPLE_TITLES
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| title.to_lowercase().contains(&keyword))
+        .map(|(i, _)| i as u8 + 1)
+        .collect()
+}
+
+// ANSI 转义序列：设置前景色，输出后用 ANSI_RESET 恢复默认样式
+const ANSI_BOLD_CYAN: &str = "\x1b[1;36m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+// 给文本加上颜色
+// 遵循 https://no-color.org 约定：设置了 NO_COLOR 环境变量时输出纯文本
+fn colorize(text: &str, color: &str) -> String {
+    if std::env::var_os("NO_COLOR").is_some() {
+        text.to_string()
+    } else {
+        format!("{}{}{}", color, text, ANSI_RESET)
+    }
+}
+
 // 运行示例文件的函数
 fn run_example_file(filename: &str) {
     let file_path = Path::new("src").join(format!("{}.rs", filename));
@@ -133,3 +213,23 @@ fn run_example_file(filename: &str) {
         Err(e) => println!("编译失败: {}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_find_examples_unique_match() {
+        assert_eq!(find_examples("trait"), vec![12]);
+        assert_eq!(find_examples("TRAIT"), vec![12]);
+        assert_eq!(find_examples("生命周期"), vec![13]);
+        assert_eq!(find_examples("Async"), vec![15]);
+    }
+    
+    #[test]
+    fn test_find_examples_ambiguous_and_missing() {
+        // "编程" 同时出现在异步编程和并发编程的标题中
+        assert_eq!(find_examples("编程"), vec![15, 16]);
+        assert!(find_examples("不存在的主题").is_empty());
+    }
+}
@@ -15,15 +15,27 @@
 // - 属性变化自动触发依赖它的 UI 元素更新
 // - 条件表达式 (? :) 实现状态驱动的 UI 变化
 // - 这种模式称为"单向数据流"或"响应式状态"
+//
+// 【持久化】
+// 属性只活在内存里，关掉窗口就没了
+// Rust 端的 Settings 结构体负责和 settings.toml 互转：
+// 启动时 load → 写入属性；点击"保存"时从属性读出 → save
 // ============================================================================
 
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use slint::{Model, ModelRc, VecModel};
+use std::path::Path;
+use std::rc::Rc;
+
 slint::slint! {
     // 导入按钮组件
-    import { Button } from "std-widgets.slint";
+    import { Button, LineEdit } from "std-widgets.slint";
 
     export component StateManagement inherits Window {
         width: 400px;
-        height: 350px;
+        height: 680px;
         title: "状态管理示例";
 
         // ====================================================================
@@ -45,6 +57,22 @@ slint::slint! {
         // 计数器状态：一个简单的数值状态
         in-out property <int> counter: 0;
 
+        // 输入框里显示的文本，以及上一次输入是否合法
+        // 都由 Rust 端的 set-counter-text 回调写回
+        in-out property <string> counter-text: "0";
+        in-out property <bool> counter-text-invalid: false;
+
+        // 计数器历史：Rust 端的 VecModel，每次 counter 变化都追加一条
+        // history-max 是历史里绝对值最大的数，柱子高度按它归一化（由 Rust 计算）
+        in property <[int]> history;
+        in property <int> history-max: 1;
+
+        // counter 被按钮 / 重置 / 加载改动时，输入框文本跟着更新，并通知 Rust 记录历史
+        changed counter => {
+            counter-text = counter;
+            counter-changed(counter);
+        }
+
         Rectangle {
             width: 100%;
             height: 100%;
@@ -123,6 +151,48 @@ slint::slint! {
                     }
                 }
 
+                // ============================================================
+                // 通知开关 - 布尔状态示例
+                // ============================================================
+                HorizontalLayout {
+                    spacing: 10px;
+
+                    Text {
+                        text: "通知:";
+                        color: theme == "light" ? #333333 : #ffffff;
+                        vertical-alignment: center;
+                    }
+
+                    Button {
+                        // 按钮文字跟随状态变化
+                        text: notifications ? "关闭通知" : "开启通知";
+                        clicked => {
+                            // 取反布尔状态，再把新值通知给 Rust 端
+                            notifications = !notifications;
+                            notifications-changed(notifications);
+                        }
+                    }
+                }
+
+                // ============================================================
+                // 条件渲染 - 只有开启通知时才显示横幅
+                // ============================================================
+                // if 条件: 元素 → 条件为 false 时元素不会被创建
+                // 横幅颜色同样跟随主题
+                if notifications: Rectangle {
+                    height: 36px;
+                    border-radius: 6px;
+                    background: theme == "light" ? #fff4d6 : #5a4a1a;
+
+                    Text {
+                        text: "🔔 通知已开启";
+                        font-size: 14px;
+                        color: theme == "light" ? #8a6d00 : #ffe08a;
+                        horizontal-alignment: center;
+                        vertical-alignment: center;
+                    }
+                }
+
                 // ============================================================
                 // 计数器 - 数值状态示例
                 // ============================================================
@@ -152,6 +222,71 @@ slint::slint! {
                     }
                 }
 
+                // ============================================================
+                // 数字输入框 - 带校验的双向绑定
+                // ============================================================
+                // text <=> counter-text：输入框和属性互相同步
+                // 每次编辑都交给 Rust 校验：数字 → 限制范围后写回 counter 和文本；
+                // 非数字 → 恢复上一次的合法值，并用红色边框提示
+                HorizontalLayout {
+                    spacing: 10px;
+
+                    Text {
+                        text: "输入:";
+                        color: theme == "light" ? #333333 : #ffffff;
+                        vertical-alignment: center;
+                    }
+
+                    Rectangle {
+                        border-width: 2px;
+                        border-radius: 4px;
+                        border-color: counter-text-invalid ? #cc0000 : transparent;
+
+                        LineEdit {
+                            text <=> counter-text;
+                            edited(text) => { set-counter-text(text); }
+                        }
+                    }
+                }
+
+                if counter-text-invalid: Text {
+                    text: "请输入 -999 ~ 999 之间的整数";
+                    font-size: 12px;
+                    color: #cc0000;
+                }
+
+                // ============================================================
+                // 计数器历史柱状图 - for 渲染 Rust 推过来的时间序列
+                // ============================================================
+                // 每个值一根柱子：高度 = |值| / history-max × 图表高度
+                // 正数绿色、负数红色；模型变化时 for 会自动增删柱子
+                Rectangle {
+                    height: 80px;
+                    border-width: 1px;
+                    border-color: theme == "light" ? #cccccc : #555555;
+
+                    HorizontalLayout {
+                        padding: 4px;
+                        spacing: 2px;
+                        alignment: start;
+
+                        for value in history: Rectangle {
+                            width: 8px;
+
+                            Rectangle {
+                                height: parent.height * abs(value) / max(history-max, 1);
+                                y: parent.height - self.height;
+                                background: value >= 0 ? #009900 : #cc0000;
+                            }
+                        }
+                    }
+                }
+
+                Button {
+                    text: "清除历史";
+                    clicked => { clear-history(); }
+                }
+
                 // ============================================================
                 // 状态组合显示
                 // ============================================================
@@ -163,9 +298,165 @@ slint::slint! {
                     // 嵌套的条件表达式
                     color: theme == "light" ? #666666 : #cccccc;
                 }
+
+                // ============================================================
+                // 保存 / 重置按钮 - 由 Rust 端处理
+                // ============================================================
+                // 按钮只负责调用回调，真正的逻辑在 Rust 的 on_save_settings / on_reset 中
+                HorizontalLayout {
+                    spacing: 10px;
+
+                    Button {
+                        text: "保存";
+                        clicked => { save-settings(); }
+                    }
+
+                    Button {
+                        text: "重置";
+                        clicked => { reset(); }
+                    }
+                }
+            }
+        }
+
+        // ====================================================================
+        // 回调声明
+        // ====================================================================
+        // reset: 把所有状态恢复为默认值
+        // Rust 端通过 app.on_reset(...) 实现
+        callback reset();
+
+        // save-settings: 把当前所有状态写入 settings.toml
+        // Rust 端通过 app.on_save_settings(...) 实现
+        callback save-settings();
+
+        // set-counter-text: 输入框被编辑时调用，参数是新文本
+        // Rust 端通过 app.on_set_counter_text(...) 校验并写回 counter / counter-text
+        callback set-counter-text(string);
+
+        // counter-changed: counter 每次变化时调用，参数是新值
+        // Rust 端通过 app.on_counter_changed(...) 追加到历史
+        callback counter-changed(int);
+
+        // clear-history: 清空计数器历史
+        // Rust 端通过 app.on_clear_history(...) 实现
+        callback clear-history();
+
+        // notifications-changed: 通知开关被切换时调用，参数是新的状态
+        // Rust 端通过 app.on_notifications_changed(...) 实现
+        callback notifications-changed(bool);
+    }
+}
+
+// ============================================================================
+// Settings - 持久化到 settings.toml 的状态
+// ============================================================================
+// 字段和 Slint 端的 4 个属性一一对应，serde 负责和 TOML 互转：
+//
+// theme = "dark"
+// language = "zh-CN"
+// notifications = true
+// counter = 3
+//
+// #[serde(default)]：文件里缺某个字段时用 Default 里的值补上
+// （比如旧版本保存的文件没有 counter）
+const SETTINGS_FILE: &str = "settings.toml";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Settings {
+    theme: String,
+    language: String,
+    notifications: bool,
+    counter: i32,
+}
+
+// 默认值与 .slint 里属性的初始值保持一致
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            theme: "light".into(),
+            language: "zh-CN".into(),
+            notifications: true,
+            counter: 0,
+        }
+    }
+}
+
+impl Settings {
+    // 读取设置文件；文件不存在 / 读取失败 / 解析失败都回退到默认值
+    // 除了"文件不存在"（第一次运行）以外，其他情况都打印日志
+    fn load(path: &Path) -> Settings {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Settings::default(),
+            Err(err) => {
+                eprintln!("读取 {} 失败，使用默认设置: {}", path.display(), err);
+                return Settings::default();
             }
+        };
+        toml::from_str(&text).unwrap_or_else(|err| {
+            eprintln!("解析 {} 失败，使用默认设置: {}", path.display(), err);
+            Settings::default()
+        })
+    }
+
+    // 写入设置文件（覆盖）
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    // 从 UI 属性读出当前状态
+    fn from_ui(app: &StateManagement) -> Settings {
+        Settings {
+            theme: app.get_theme().into(),
+            language: app.get_language().into(),
+            notifications: app.get_notifications(),
+            counter: app.get_counter(),
         }
     }
+
+    // 把设置写回 UI 属性，UI 自动刷新
+    fn apply(&self, app: &StateManagement) {
+        app.set_theme(self.theme.as_str().into());
+        app.set_language(self.language.as_str().into());
+        app.set_notifications(self.notifications);
+        app.set_counter(self.counter);
+    }
+}
+
+// ============================================================================
+// 计数器输入校验
+// ============================================================================
+// 输入框允许的范围；超出范围的数字会被限制到边界
+const COUNTER_MIN: i32 = -999;
+const COUNTER_MAX: i32 = 999;
+
+// 把输入文本解析成计数器的值
+// - 合法整数 → Some(限制到 COUNTER_MIN..=COUNTER_MAX 后的值)
+// - 非数字（包括空字符串）→ None，由调用方恢复上一次的合法值
+fn parse_counter_text(text: &str) -> Option<i32> {
+    text.trim()
+        .parse::<i64>()
+        .ok()
+        .map(|value| value.clamp(COUNTER_MIN as i64, COUNTER_MAX as i64) as i32)
+}
+
+// ============================================================================
+// 计数器历史
+// ============================================================================
+// 柱状图最多显示多少个值，超出后丢掉最旧的
+const HISTORY_LEN: usize = 40;
+
+// 追加一个值到历史（超出 HISTORY_LEN 时删掉最旧的），返回历史中最大的绝对值
+// VecModel 的 push / remove 会通知 UI，for 渲染的柱子跟着增删
+fn record_history(history: &VecModel<i32>, value: i32) -> i32 {
+    history.push(value);
+    while history.row_count() > HISTORY_LEN {
+        history.remove(0);
+    }
+    history.iter().map(i32::abs).max().unwrap_or(0)
 }
 
 // ============================================================================
@@ -174,6 +465,13 @@ slint::slint! {
 fn main() {
     let app = StateManagement::new().unwrap();
 
+    // 计数器历史模型：Rust 持有一份 Rc，UI 持有另一份，两边看到的是同一个模型
+    let history = Rc::new(VecModel::<i32>::default());
+    app.set_history(ModelRc::from(history.clone()));
+
+    // 启动时自动加载上次保存的设置
+    Settings::load(Path::new(SETTINGS_FILE)).apply(&app);
+
     // ------------------------------------------------------------------------
     // 从 Rust 端读取和修改状态
     // ------------------------------------------------------------------------
@@ -188,6 +486,86 @@ fn main() {
     // app.set_theme("dark".into());
     // app.set_counter(100);
 
+    // ------------------------------------------------------------------------
+    // 完整的往返：Slint 按钮 -> Rust 回调 -> 多个 set_* 调用
+    // ------------------------------------------------------------------------
+    // 点击"重置"时，Slint 调用 reset()，这里的闭包被执行
+    // 闭包里用弱引用拿回组件，再一次性把所有状态设回默认值
+    // UI 会随属性变化自动刷新，无需手动重绘
+    let app_weak = app.as_weak();
+    app.on_reset(move || {
+        if let Some(app) = app_weak.upgrade() {
+            Settings::default().apply(&app);
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 数字输入框校验
+    // ------------------------------------------------------------------------
+    // 合法：写回限制后的 counter，再把文本改成规范形式（如 "0012" → "12"，"5000" → "999"）
+    // 非法：counter 不变，文本恢复成当前 counter，并标红
+    let app_weak = app.as_weak();
+    app.on_set_counter_text(move |text| {
+        if let Some(app) = app_weak.upgrade() {
+            match parse_counter_text(&text) {
+                Some(value) => {
+                    app.set_counter(value);
+                    app.set_counter_text(value.to_string().into());
+                    app.set_counter_text_invalid(false);
+                }
+                None => {
+                    app.set_counter_text(app.get_counter().to_string().into());
+                    app.set_counter_text_invalid(true);
+                }
+            }
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 计数器历史
+    // ------------------------------------------------------------------------
+    // Slint 端 changed counter 时调用 counter-changed(新值)
+    let app_weak = app.as_weak();
+    let history_for_record = history.clone();
+    app.on_counter_changed(move |value| {
+        let max = record_history(&history_for_record, value);
+        if let Some(app) = app_weak.upgrade() {
+            app.set_history_max(max);
+        }
+    });
+
+    // 清除历史：清空模型，柱状图随之清空
+    let app_weak = app.as_weak();
+    app.on_clear_history(move || {
+        history.set_vec(Vec::new());
+        if let Some(app) = app_weak.upgrade() {
+            app.set_history_max(1);
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 保存设置
+    // ------------------------------------------------------------------------
+    // 点击"保存"时，从属性读出当前状态写入 settings.toml
+    let app_weak = app.as_weak();
+    app.on_save_settings(move || {
+        if let Some(app) = app_weak.upgrade() {
+            match Settings::from_ui(&app).save(Path::new(SETTINGS_FILE)) {
+                Ok(()) => println!("设置已保存到 {}", SETTINGS_FILE),
+                Err(err) => eprintln!("保存 {} 失败: {}", SETTINGS_FILE, err),
+            }
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 监听通知开关
+    // ------------------------------------------------------------------------
+    // Slint 端切换开关后调用 notifications-changed(新值)
+    // 这里只打印日志；实际应用可以在这里保存设置或注册系统通知
+    app.on_notifications_changed(|enabled| {
+        println!("通知已{}", if enabled { "开启" } else { "关闭" });
+    });
+
     app.run().unwrap();
 }
 
@@ -209,20 +587,107 @@ fn main() {
 //    - UI 属性可以绑定到状态表达式
 //    - 状态变化 -> 表达式重新计算 -> UI 更新
 //    - 多个 UI 元素可以依赖同一状态
+//    - if 条件: 元素 → 状态决定元素是否存在（如通知横幅）
 //
 // 4. 状态修改
 //    - 在事件回调中：property = new_value
 //    - 从 Rust 端：app.set_property(value)
+//    - Slint 调用 callback，Rust 在 on_xxx 中批量 set_*（如重置按钮）
 //    - 修改会自动触发所有依赖项更新
 //
-// 5. 最佳实践
+// 5. 带校验的双向绑定
+//    - LineEdit { text <=> counter-text; } 输入框和属性双向同步
+//    - edited 时交给 Rust 校验，Rust 再写回 counter 和规范化后的文本
+//    - changed counter => { ... } 让按钮改动的值也同步到输入框
+//
+// 6. Rust 驱动的时间序列
+//    - VecModel<i32> 放在 Rc 里，Rust 和 UI 共享同一个模型
+//    - changed counter 时回调 Rust push，for value in history 自动增删柱子
+//    - 归一化用的最大值也由 Rust 算好再 set（Slint 表达式里不方便对数组求最大值）
+//
+// 7. 状态持久化
+//    - Settings 结构体 + serde：属性 ↔ Rust 结构体 ↔ TOML 文件
+//    - 启动时 load + apply，点击保存时 from_ui + save
+//    - 文件损坏时回退默认值并打印日志，而不是让程序崩溃
+//
+// 8. 最佳实践
 //    - 状态集中管理（在组件顶层定义）
 //    - 状态命名清晰（描述含义而非用途）
 //    - 避免过多状态（考虑是否可以派生计算）
 //    - 使用条件表达式而非重复代码
 //
-// 6. 与 React/Vue 的对比
+// 9. 与 React/Vue 的对比
 //    - Slint 属性 ≈ React state / Vue ref
 //    - Slint 条件表达式 ≈ React 条件渲染 / Vue v-if
 //    - Slint 自动更新 ≈ React re-render / Vue 响应式
 // ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 每个测试用自己的临时文件，避免并行运行时互相覆盖
+    fn temp_settings_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("slint-04-{}-{}.toml", name, std::process::id()))
+    }
+
+    #[test]
+    fn counter_text_is_parsed_and_clamped() {
+        assert_eq!(parse_counter_text("42"), Some(42));
+        assert_eq!(parse_counter_text(" -7 "), Some(-7));
+        assert_eq!(parse_counter_text("5000"), Some(COUNTER_MAX));
+        assert_eq!(parse_counter_text("-99999999999"), Some(COUNTER_MIN));
+        assert_eq!(parse_counter_text("abc"), None);
+        assert_eq!(parse_counter_text(""), None);
+    }
+
+    #[test]
+    fn history_keeps_latest_values_and_max_abs() {
+        let history = VecModel::<i32>::default();
+        assert_eq!(record_history(&history, 3), 3);
+        assert_eq!(record_history(&history, -8), 8);
+
+        for value in 0..HISTORY_LEN as i32 {
+            record_history(&history, value);
+        }
+        // 超出容量后最旧的 3 和 -8 被丢掉，剩下的按时间顺序排列
+        assert_eq!(history.row_count(), HISTORY_LEN);
+        assert_eq!(history.row_data(0), Some(0));
+        assert_eq!(
+            history.row_data(HISTORY_LEN - 1),
+            Some(HISTORY_LEN as i32 - 1)
+        );
+        assert_eq!(record_history(&history, 1), HISTORY_LEN as i32 - 1);
+    }
+
+    #[test]
+    fn settings_round_trip_through_toml() {
+        let path = temp_settings_path("round-trip");
+        let settings = Settings {
+            theme: "dark".into(),
+            language: "en-US".into(),
+            notifications: false,
+            counter: -7,
+        };
+
+        settings.save(&path).unwrap();
+        let loaded = Settings::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn broken_or_partial_file_falls_back_to_defaults() {
+        let path = temp_settings_path("broken");
+
+        fs::write(&path, "theme = ").unwrap();
+        assert_eq!(Settings::load(&path), Settings::default());
+
+        // 缺少的字段用默认值补上
+        fs::write(&path, "counter = 3").unwrap();
+        let loaded = Settings::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, Settings { counter: 3, ..Settings::default() });
+    }
+}
@@ -18,11 +18,11 @@
 
 slint::slint! {
     // 导入标准组件
-    import { Button, ScrollView } from "std-widgets.slint";
+    import { Button, LineEdit, ListView, ScrollView } from "std-widgets.slint";
 
     export component ListRendering inherits Window {
         width: 400px;
-        height: 400px;
+        height: 460px;
         title: "列表渲染示例";
 
         // ====================================================================
@@ -40,6 +40,22 @@ slint::slint! {
         // 用于显示计数（数组长度的替代方案）
         in-out property <int> item-count: 3;
 
+        // 当前是否显示大列表屏幕
+        in-out property <bool> show-large: false;
+
+        // 大列表数据：由 Rust 端用 VecModel 填充（上万条）
+        in property <[string]> large-items;
+
+        // 重新生成大列表数据，Rust 端通过 app.on_regenerate(...) 实现
+        callback regenerate();
+
+        // 搜索关键字：为空时显示全部项目
+        in-out property <string> filter: "";
+
+        // 添加项目 / 关键字变化，由 Rust 端更新模型
+        callback add-item();
+        callback filter-changed(string);
+
         VerticalLayout {
             padding: 20px;
             spacing: 15px;
@@ -51,92 +67,164 @@ slint::slint! {
                 horizontal-alignment: center;
             }
 
-            // 显示项目数量
-            Text {
-                text: "共 " + item-count + " 个项目";
-                font-size: 14px;
-                color: #666;
-                horizontal-alignment: center;
-            }
-
-            // 添加按钮
+            // ================================================================
+            // 屏幕切换：小列表（for + ScrollView）/ 大列表（ListView）
+            // ================================================================
             Button {
-                text: "添加项目";
-                clicked => {
-                    // 更新计数
-                    // 注意：在 Slint UI 层面修改数组比较复杂
-                    // 通常需要通过 Rust 端操作
-                    item-count = item-count + 1;
-                }
+                text: show-large ? "返回小列表" : "查看大列表 (ListView)";
+                clicked => { show-large = !show-large; }
             }
 
             // ================================================================
-            // ScrollView - 可滚动容器
+            // 屏幕一：for-in + ScrollView
             // ================================================================
-            // 当列表内容超出可视区域时，提供滚动功能
-            ScrollView {
-                width: 100%;
-                height: 200px;
-
-                VerticalLayout {
-                    spacing: 8px;
-                    padding: 10px;
-
-                    // ========================================================
-                    // for-in 循环 - 列表渲染核心语法
-                    // ========================================================
-                    // 语法：for variable[index] in array: Element { ... }
-                    //
-                    // 解释：
-                    // - item: 当前元素的值
-                    // - [index]: 可选，当前元素的索引（从 0 开始）
-                    // - items: 要遍历的数组
-                    // - Rectangle { ... }: 为每个元素创建的 UI
-                    //
-                    // 【原理】
-                    // 1. Slint 遍历 items 数组
-                    // 2. 为每个元素创建一个 Rectangle 实例
-                    // 3. item 和 index 在 Element 内可用
-                    // 4. 当 items 变化时，自动更新 UI
-                    for item[index] in items: Rectangle {
-                        width: 100%;
-                        height: 40px;
-
-                        // 根据索引设置不同的背景色
-                        // 展示条件表达式与索引的结合使用
-                        background: index == 0 ? #e3f2fd :
-                                   (index == 1 ? #f3e5f5 : #e8f5e9);
-
-                        border-radius: 8px;
-
-                        HorizontalLayout {
-                            padding-left: 15px;
-                            padding-right: 15px;
-
-                            Text {
-                                // 显示当前项的文本
-                                // item 是 string 类型
-                                text: item;
-                                font-size: 16px;
-                                color: #333;
-                                vertical-alignment: center;
+            if !show-large: VerticalLayout {
+                spacing: 15px;
+
+                // 显示项目数量：items.length 是过滤后的数量，item-count 是总数
+                Text {
+                    text: "显示 " + items.length + " / 共 " + item-count + " 个项目";
+                    font-size: 14px;
+                    color: #666;
+                    horizontal-alignment: center;
+                }
+
+                // 搜索框：每次输入都把关键字交给 Rust 端重新过滤
+                LineEdit {
+                    text: filter;
+                    placeholder-text: "搜索项目";
+                    edited(text) => {
+                        filter = text;
+                        filter-changed(text);
+                    }
+                }
+
+                // 添加按钮
+                Button {
+                    text: "添加项目";
+                    clicked => {
+                        // 在 Slint UI 层面修改数组比较复杂
+                        // 交给 Rust 端往 VecModel 里 push
+                        add-item();
+                    }
+                }
+
+                // ================================================================
+                // ScrollView - 可滚动容器
+                // ================================================================
+                // 当列表内容超出可视区域时，提供滚动功能
+                ScrollView {
+                    width: 100%;
+                    height: 200px;
+
+                    VerticalLayout {
+                        spacing: 8px;
+                        padding: 10px;
+
+                        // ========================================================
+                        // for-in 循环 - 列表渲染核心语法
+                        // ========================================================
+                        // 语法：for variable[index] in array: Element { ... }
+                        //
+                        // 解释：
+                        // - item: 当前元素的值
+                        // - [index]: 可选，当前元素的索引（从 0 开始）
+                        // - items: 要遍历的数组
+                        // - Rectangle { ... }: 为每个元素创建的 UI
+                        //
+                        // 【原理】
+                        // 1. Slint 遍历 items 数组
+                        // 2. 为每个元素创建一个 Rectangle 实例
+                        // 3. item 和 index 在 Element 内可用
+                        // 4. 当 items 变化时，自动更新 UI
+                        for item[index] in items: Rectangle {
+                            width: 100%;
+                            height: 40px;
+
+                            // 根据索引设置不同的背景色
+                            // 展示条件表达式与索引的结合使用
+                            background: index == 0 ? #e3f2fd :
+                                       (index == 1 ? #f3e5f5 : #e8f5e9);
+
+                            border-radius: 8px;
+
+                            HorizontalLayout {
+                                padding-left: 15px;
+                                padding-right: 15px;
+
+                                Text {
+                                    // 显示当前项的文本
+                                    // item 是 string 类型
+                                    text: item;
+                                    font-size: 16px;
+                                    color: #333;
+                                    vertical-alignment: center;
+                                }
                             }
                         }
                     }
                 }
+
+                // 说明文字
+                Text {
+                    text: "提示: 列表使用 for...in 语法渲染";
+                    font-size: 12px;
+                    color: #999;
+                    horizontal-alignment: center;
+                }
             }
 
-            // 说明文字
-            Text {
-                text: "提示: 列表使用 for...in 语法渲染";
-                font-size: 12px;
-                color: #999;
-                horizontal-alignment: center;
+            // ================================================================
+            // 屏幕二：ListView - 虚拟化列表
+            // ================================================================
+            // 【元素实例化的区别】
+            // - ScrollView 里的 for：有多少条数据就创建多少个 Rectangle，
+            //   10,000 条就是 10,000 个元素，全部参与布局，内存和启动时间随数据量线性增长
+            // - ListView 里的 for：只为**当前可见**的行（再加少量缓冲）创建元素，
+            //   滚动时复用这些元素、换上新行的数据 → 元素数量只和窗口高度有关
+            // 所以大数据量一定要用 ListView，且每行高度最好固定，便于计算可见范围
+            if show-large: VerticalLayout {
+                spacing: 15px;
+
+                Text {
+                    text: "共 " + large-items.length + " 个项目（虚拟化渲染）";
+                    font-size: 14px;
+                    color: #666;
+                    horizontal-alignment: center;
+                }
+
+                // 由 Rust 端重新生成整批数据
+                Button {
+                    text: "重新生成数据";
+                    clicked => { regenerate(); }
+                }
+
+                ListView {
+                    height: 220px;
+
+                    for item[index] in large-items: Rectangle {
+                        height: 30px;
+                        // 斑马纹：偶数行、奇数行不同背景
+                        background: mod(index, 2) == 0 ? #ffffff : #f5f5f5;
+
+                        Text {
+                            x: 15px;
+                            text: item;
+                            font-size: 14px;
+                            color: #333;
+                            vertical-alignment: center;
+                        }
+                    }
+                }
             }
         }
     }
 }
 
+use slint::{FilterModel, Model, ModelRc, SharedString, VecModel};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 // ============================================================================
 // main 函数
 // ============================================================================
@@ -164,9 +252,66 @@ fn main() {
     // 4. 监听数组变化：
     //    VecModel 实现了 Model trait，支持 row_count(), row_data() 等
 
+    // ------------------------------------------------------------------------
+    // 小列表：VecModel（全部数据）+ FilterModel（过滤后的视图）
+    // ------------------------------------------------------------------------
+    // - source: 完整数据，添加项目时往这里 push
+    // - filtered: 包在 source 外面的"派生模型"，只暴露包含关键字的行
+    //   UI 绑定的是 filtered，source 变化会自动反映到 filtered
+    // - 关键字存在 Rc<RefCell<String>> 里，过滤闭包和回调共享同一份
+    //   关键字变化后调用 reset()，让 FilterModel 重新跑一遍过滤条件
+    let source = Rc::new(VecModel::from(
+        app.get_items().iter().collect::<Vec<SharedString>>(),
+    ));
+    let filter_text = Rc::new(RefCell::new(String::new()));
+    let filtered = Rc::new(FilterModel::new(source.clone(), {
+        let filter_text = filter_text.clone();
+        move |item: &SharedString| item.contains(filter_text.borrow().as_str())
+    }));
+    app.set_items(ModelRc::from(filtered.clone()));
+
+    app.on_filter_changed(move |text| {
+        *filter_text.borrow_mut() = text.to_string();
+        // 清空搜索框 → 关键字为空 → 所有项目都匹配，列表恢复完整
+        filtered.reset();
+    });
+
+    let app_weak = app.as_weak();
+    app.on_add_item(move || {
+        source.push(format!("项目 {}", source.row_count() + 1).into());
+        if let Some(app) = app_weak.upgrade() {
+            app.set_item_count(source.row_count() as i32);
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 大列表：VecModel + ListView
+    // ------------------------------------------------------------------------
+    // Rc<VecModel> 自己保留一份，重新生成时直接替换内容，
+    // UI 端持有同一个模型，收到变更通知后自动刷新
+    let generation = Rc::new(Cell::new(1));
+    let large_model = Rc::new(VecModel::from(generate_items(generation.get(), LARGE_LIST_SIZE)));
+    app.set_large_items(ModelRc::from(large_model.clone()));
+
+    app.on_regenerate(move || {
+        generation.set(generation.get() + 1);
+        // set_vec: 一次性替换全部数据，只发一次"模型重置"通知
+        large_model.set_vec(generate_items(generation.get(), LARGE_LIST_SIZE));
+    });
+
     app.run().unwrap();
 }
 
+// 大列表的数据量
+const LARGE_LIST_SIZE: usize = 10_000;
+
+// 生成一批列表数据；generation 区分每次重新生成的结果
+fn generate_items(generation: u32, count: usize) -> Vec<SharedString> {
+    (1..=count)
+        .map(|i| format!("第 {} 批 · 项目 {}", generation, i).into())
+        .collect()
+}
+
 // ============================================================================
 // 【知识点总结】
 // ============================================================================
@@ -199,8 +344,15 @@ fn main() {
 //    - Slint 使用 diff 算法优化更新
 //    - 只更新变化的元素
 //    - 大列表建议使用虚拟化 (ListView)
+//    - ScrollView + for：每条数据都创建元素
+//    - ListView：只创建可见行，滚动时复用（上万条依然流畅）
+//
+// 7. 派生模型
+//    - FilterModel::new(源模型, 过滤闭包)：不复制数据的过滤视图
+//    - 源模型变化自动同步；过滤条件变化后调用 reset()
+//    - 类似的还有 MapModel、SortModel
 //
-// 7. 与其他框架对比
+// 8. 与其他框架对比
 //    - Slint for-in ≈ React array.map() / Vue v-for
 //    - Slint index ≈ React key / Vue :key
 // ============================================================================
@@ -25,16 +25,28 @@ slint::slint! {
     import { Button } from "std-widgets.slint";
 
     export component LayoutStyling inherits Window {
-        width: 500px;
-        height: 450px;
+        // 首选尺寸而不是固定尺寸：窗口可以被拖动改变大小
+        preferred-width: 500px;
+        preferred-height: 450px;
+        min-width: 280px;
         title: "布局样式示例";
 
         // 选项卡状态，用于切换不同的演示
         in-out property <int> active-tab: 0;
 
+        // ====================================================================
+        // 响应式断点
+        // ====================================================================
+        // 窗口宽度小于 compact-breakpoint 时进入"紧凑模式"：
+        // - 选项卡按钮改为竖向排列
+        // - 内容区的内边距缩小
+        // compact 绑定到 root.width，拖动窗口时自动重新计算 → UI 自动重排
+        property <length> compact-breakpoint: 420px;
+        property <bool> compact: root.width < compact-breakpoint;
+
         VerticalLayout {
-            padding: 20px;
-            spacing: 15px;
+            padding: compact ? 8px : 20px;
+            spacing: compact ? 8px : 15px;
 
             Text {
                 text: "布局与样式示例";
@@ -50,7 +62,9 @@ slint::slint! {
             // ================================================================
             // 选项卡导航
             // ================================================================
-            HorizontalLayout {
+            // Slint 不能在运行时改变布局容器的类型，
+            // 所以用两个互斥的 if：宽屏用 HorizontalLayout，窄屏用 VerticalLayout
+            if !compact: HorizontalLayout {
                 spacing: 8px;
                 // alignment: 子元素在布局中的对齐方式
                 // - start: 起始位置
@@ -73,6 +87,24 @@ slint::slint! {
                 }
             }
 
+            // 紧凑模式：按钮竖向堆叠，每个按钮占满一行，方便点击
+            if compact: VerticalLayout {
+                spacing: 6px;
+
+                Button {
+                    text: "水平布局";
+                    clicked => { active-tab = 0; }
+                }
+                Button {
+                    text: "垂直布局";
+                    clicked => { active-tab = 1; }
+                }
+                Button {
+                    text: "样式演示";
+                    clicked => { active-tab = 2; }
+                }
+            }
+
             // 内容区域
             Rectangle {
                 width: 100%;
@@ -90,7 +122,8 @@ slint::slint! {
                 // 水平布局演示
                 // ============================================================
                 if active-tab == 0: HorizontalLayout {
-                    padding: 20px;
+                    // 紧凑模式下缩小内边距，给内容留出更多空间
+                    padding: compact ? 10px : 20px;
                     spacing: 15px;
 
                     // 固定宽度元素
@@ -149,7 +182,8 @@ slint::slint! {
                 // 垂直布局演示
                 // ============================================================
                 if active-tab == 1: VerticalLayout {
-                    padding: 20px;
+                    // 紧凑模式下缩小内边距，给内容留出更多空间
+                    padding: compact ? 10px : 20px;
                     spacing: 15px;
 
                     // 固定高度元素
@@ -188,7 +222,8 @@ slint::slint! {
                 // 样式演示
                 // ============================================================
                 if active-tab == 2: VerticalLayout {
-                    padding: 20px;
+                    // 紧凑模式下缩小内边距，给内容留出更多空间
+                    padding: compact ? 10px : 20px;
                     spacing: 15px;
 
                     // --------------------------------------------------------
@@ -320,4 +355,7 @@ fn main() {
 //    - 使用 % 单位相对于父元素
 //    - 使用拉伸因子分配空间
 //    - 设置最小/最大尺寸限制
+//    - 断点：property <bool> compact: root.width < 断点宽度
+//    - 用互斥的 if 切换 HorizontalLayout / VerticalLayout
+//    - Window 用 preferred-width 而不是 width，窗口才能拖动改变大小
 // ============================================================================
@@ -18,9 +18,11 @@
 // ============================================================================
 
 slint::slint! {
+    import { Button } from "std-widgets.slint";
+
     export component CrossPlatform inherits Window {
         width: 400px;
-        height: 350px;
+        height: 420px;
         title: "跨平台构建示例";
 
         // ====================================================================
@@ -33,6 +35,13 @@ slint::slint! {
         in property <string> os-version: "";      // 系统版本
         in property <string> architecture: "";    // CPU 架构
 
+        // 复制结果提示："已复制" 或错误信息；为空时不显示
+        in-out property <string> copy-status: "";
+        in-out property <bool> copy-failed: false;
+
+        // 复制平台信息到系统剪贴板，由 Rust 端实现
+        callback copy-info();
+
         VerticalLayout {
             padding: 20px;
             spacing: 15px;
@@ -135,10 +144,36 @@ slint::slint! {
                     Text { text: "• 支持 Windows、macOS、Linux"; font-size: 12px; color: #666; }
                 }
             }
+
+            // ================================================================
+            // 复制平台信息 - 平台集成示例
+            // ================================================================
+            HorizontalLayout {
+                spacing: 10px;
+                alignment: center;
+
+                Button {
+                    text: "复制信息";
+                    clicked => { copy-info(); }
+                }
+
+                Text {
+                    text: copy-status;
+                    font-size: 12px;
+                    // 失败红色，成功绿色
+                    color: copy-failed ? #cc0000 : #009900;
+                    vertical-alignment: center;
+                }
+            }
         }
     }
 }
 
+use arboard::Clipboard;
+use slint::Timer;
+use std::cell::RefCell;
+use std::time::Duration;
+
 // ============================================================================
 // main 函数
 // ============================================================================
@@ -181,12 +216,63 @@ fn main() {
     // .into() 将 &str 转换为 SharedString
     app.set_platform_name(name.into());
     app.set_platform_icon(icon.into());
-    app.set_os_version(os_release.into());
-    app.set_architecture(arch.into());
+    app.set_os_version(os_release.as_str().into());
+    app.set_architecture(arch.as_str().into());
+
+    // ------------------------------------------------------------------------
+    // 复制平台信息到剪贴板
+    // ------------------------------------------------------------------------
+    // 剪贴板是各平台差异很大的系统功能（Win32 / NSPasteboard / X11 / Wayland）
+    // arboard crate 把这些差异封装成统一的 Clipboard API
+    let info = format!("平台: {}\n版本: {}\n架构: {}", name, os_release, arch);
+
+    // Linux 上剪贴板内容由 Clipboard 对象"持有"，对象被释放内容可能就没了
+    // 所以放在闭包里一直保留，第一次复制时才创建
+    let clipboard: RefCell<Option<Clipboard>> = RefCell::new(None);
+    let app_weak = app.as_weak();
+    app.on_copy_info(move || {
+        let Some(app) = app_weak.upgrade() else {
+            return;
+        };
+
+        // 失败（没有图形环境、剪贴板被占用等）只显示错误，不 panic
+        match copy_to_clipboard(&mut clipboard.borrow_mut(), &info) {
+            Ok(()) => {
+                app.set_copy_failed(false);
+                app.set_copy_status("已复制".into());
+
+                // 提示 2 秒后自动消失：Timer 在事件循环里触发，可以直接改 UI
+                let app_weak = app.as_weak();
+                Timer::single_shot(Duration::from_secs(2), move || {
+                    if let Some(app) = app_weak.upgrade() {
+                        if !app.get_copy_failed() {
+                            app.set_copy_status("".into());
+                        }
+                    }
+                });
+            }
+            Err(e) => {
+                app.set_copy_failed(true);
+                app.set_copy_status(format!("复制失败: {}", e).into());
+            }
+        }
+    });
 
     app.run().unwrap();
 }
 
+// ============================================================================
+// 写入系统剪贴板
+// ============================================================================
+// 第一次调用时创建 Clipboard，之后复用同一个对象
+// ? 把 arboard::Error 直接返回给调用方处理
+fn copy_to_clipboard(slot: &mut Option<Clipboard>, text: &str) -> Result<(), arboard::Error> {
+    if slot.is_none() {
+        *slot = Some(Clipboard::new()?);
+    }
+    slot.as_mut().unwrap().set_text(text)
+}
+
 // ============================================================================
 // 获取 CPU 架构信息
 // ============================================================================
@@ -241,6 +327,11 @@ fn get_arch() -> String {
 //    - sys-info crate: 获取系统信息
 //    - std::env::consts: 标准库常量
 //
+// 3.1 平台集成：剪贴板
+//    - arboard crate: 统一的跨平台剪贴板 API
+//    - Clipboard::new() / set_text() 都可能失败 → 返回 Result，显示错误而不是 panic
+//    - slint::Timer::single_shot: 延时执行，用于自动隐藏提示
+//
 // 4. Slint 渲染后端
 //    - femtovg: 基于 OpenGL 的矢量渲染
 //    - skia: Google 的 2D 图形库
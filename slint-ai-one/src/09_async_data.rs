@@ -16,6 +16,11 @@
 // - 使用 slint::invoke_from_event_loop() 从后台线程更新 UI
 // - 这保证了线程安全，避免数据竞争
 //
+// 【批量获取】
+// 输入多个城市（逗号分隔），每个城市一个工作线程同时请求（扇出 fan-out）：
+// - 先为每个城市放一行"加载中"，哪个城市先返回就先把哪一行改成结果
+// - 协调线程 join 所有工作线程（扇入 fan-in），全部完成后再恢复按钮
+//
 // 【为什么需要异步】
 // 如果在主线程执行耗时操作（如网络请求）：
 // - UI 会冻结，无法响应用户操作
@@ -24,11 +29,11 @@
 // ============================================================================
 
 slint::slint! {
-    import { Button, LineEdit } from "std-widgets.slint";
+    import { Button, LineEdit, ProgressIndicator } from "std-widgets.slint";
 
     export component AsyncData inherits Window {
         width: 400px;
-        height: 350px;
+        height: 620px;
         title: "异步数据示例";
 
         // ====================================================================
@@ -48,6 +53,15 @@ slint::slint! {
         // 用户输入
         in-out property <string> input-text: "北京";
 
+        // 进度：0.0 ~ 1.0，由工作线程分阶段更新
+        in-out property <float> progress: 0;
+
+        // 批量获取：输入（逗号分隔的城市）、每个城市一行的结果列表、是否还有城市没返回
+        // batch-results 在 Rust 端是 VecModel<SharedString>，工作线程返回一个就改一行
+        in-out property <string> batch-input: "北京, 上海, 广州, 深圳";
+        in property <[string]> batch-results;
+        in-out property <bool> batch-loading: false;
+
         VerticalLayout {
             padding: 20px;
             spacing: 15px;
@@ -85,6 +99,8 @@ slint::slint! {
                         // 更新状态，表示开始加载
                         is-loading = true;
                         status-message = "正在获取数据...";
+                        // 新的请求从 0 开始
+                        progress = 0;
 
                         // 调用回调，触发 Rust 端的异步操作
                         fetch-data(input-text);
@@ -100,6 +116,12 @@ slint::slint! {
                 horizontal-alignment: center;
             }
 
+            // 进度条：绑定 progress 属性，工作线程每次更新都会自动重绘
+            ProgressIndicator {
+                height: 8px;
+                progress: progress;
+            }
+
             // ================================================================
             // 条件渲染：只有当有结果时才显示结果卡片
             // ================================================================
@@ -129,6 +151,37 @@ slint::slint! {
                 }
             }
 
+            // ================================================================
+            // 批量获取：多个城市并发请求，结果逐个出现
+            // ================================================================
+            HorizontalLayout {
+                spacing: 10px;
+                alignment: center;
+
+                LineEdit {
+                    text: batch-input;
+                    width: 200px;
+                    edited => { batch-input = self.text; }
+                }
+
+                Button {
+                    text: batch-loading ? "批量加载中..." : "批量获取";
+                    enabled: !batch-loading && batch-input != "";
+                    clicked => {
+                        batch-loading = true;
+                        fetch-batch(batch-input);
+                    }
+                }
+            }
+
+            // 每个城市一行："⏳ 城市 加载中…" → "✓ 城市 …结果"
+            for row in batch-results: Text {
+                text: row;
+                font-size: 14px;
+                color: #333;
+                horizontal-alignment: center;
+            }
+
             // 说明文字
             Text {
                 text: "本示例演示异步数据处理:\n• 后台线程执行\n• UI 保持响应\n• 显示加载状态";
@@ -148,13 +201,52 @@ slint::slint! {
         // - Slint 端：调用 fetch-data(...)
         // - Rust 端：实现 on_fetch_data(|args| { ... })
         callback fetch-data(string);
+
+        // 批量获取：参数是原始输入（逗号分隔），拆分交给 Rust
+        callback fetch-batch(string);
     }
 }
 
-// 引入标准库的线程和时间模块
+// 示例共用的后台任务辅助函数（src/app_support.rs）
+mod app_support;
+use app_support::{post, spawn_bg};
+
+use slint::{Model, ModelRc, SharedString, VecModel};
+
+// 引入标准库的线程、时间、共享状态和集合模块
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+// ============================================================================
+// 批量获取的辅助函数
+// ============================================================================
+// 拆分输入：中英文逗号都算分隔符，去掉首尾空白、空项和重复的城市（保留第一次出现的顺序）
+fn parse_cities(input: &str) -> Vec<String> {
+    let mut cities: Vec<String> = Vec::new();
+    for city in input.split([',', '，']).map(str::trim) {
+        if !city.is_empty() && !cities.iter().any(|seen| seen == city) {
+            cities.push(city.to_string());
+        }
+    }
+    cities
+}
+
+// 模拟每个城市不同的网络延迟：这样返回顺序和输入顺序不一样，能看出"谁先到谁先显示"
+fn simulated_delay(city: &str, index: usize) -> Duration {
+    Duration::from_millis(300 + 400 * ((city.chars().count() + index * 3) % 4) as u64)
+}
+
+fn loading_row(city: &str) -> SharedString {
+    format!("⏳ {} 加载中…", city).into()
+}
+
+fn done_row(city: &str, elapsed: Duration) -> SharedString {
+    format!("✓ {} 22°C 晴朗（{} ms）", city, elapsed.as_millis()).into()
+}
+
 // ============================================================================
 // main 函数
 // ============================================================================
@@ -169,6 +261,15 @@ fn main() {
     // 在回调中使用弱引用避免循环引用
     let app_weak = app.as_weak();
 
+    // ------------------------------------------------------------------------
+    // 共享缓存：城市名 -> 查询结果
+    // ------------------------------------------------------------------------
+    // Arc<Mutex<HashMap>>：
+    // - Arc: 原子引用计数，回调闭包和工作线程各持有一份，共享同一个 HashMap
+    // - Mutex: 互斥锁，同一时刻只有一个线程能读写 HashMap
+    // 同一城市第二次查询直接命中缓存，不再启动工作线程
+    let cache: Arc<Mutex<HashMap<String, String>>> = Arc::new(Mutex::new(HashMap::new()));
+
     // ------------------------------------------------------------------------
     // 注册回调处理函数
     // ------------------------------------------------------------------------
@@ -184,50 +285,133 @@ fn main() {
         let query = query.to_string();
 
         // --------------------------------------------------------------------
-        // 创建新线程执行异步操作
+        // 先查缓存
+        // --------------------------------------------------------------------
+        // 命中：不启动线程，也不显示加载状态，直接把结果交给事件循环
+        // 单独一条 let 语句：锁在这一行结束时就释放，不会在下面的分支里一直占着
+        let cached = cache.lock().unwrap().get(&query).cloned();
+        if let Some(result) = cached {
+            // post: 把一次 UI 更新交给事件循环（内部完成 upgrade 检查）
+            post(&app_weak, move |app| {
+                app.set_result_data(result.into());
+                app.set_progress(1.0);
+                app.set_status_message("数据获取成功! (cached)".into());
+                app.set_is_loading(false);
+            });
+            return;
+        }
+
+        // 未命中：克隆一份 Arc 给工作线程，用于写回缓存
+        let cache = Arc::clone(&cache);
+
+        // 工作线程里汇报进度也要用弱引用，单独克隆一份
+        let progress_weak = app_weak.clone();
+
+        // --------------------------------------------------------------------
+        // 在后台线程执行异步操作
         // --------------------------------------------------------------------
-        // thread::spawn: 创建新线程
-        // move ||: 闭包获取外部变量的所有权
+        // spawn_bg(弱引用, 后台任务, 完成回调)：
+        // - 后台任务在新线程中运行，返回值就是结果
+        // - 完成回调在主线程中运行，拿到 (app, 结果) 直接 set_*
+        // thread::spawn + invoke_from_event_loop + upgrade 的样板代码
+        // 都封装在 app_support 里，见 app_support.rs
         //
         // 【为什么用线程而不是 async/await】
         // 1. 简单直接，不需要异步运行时
         // 2. Slint 主循环不是异步的
         // 3. 对于简单的异步任务足够了
-        thread::spawn(move || {
-            // 模拟网络延迟（实际应用中这里是真正的网络请求）
-            thread::sleep(Duration::from_secs(1));
-
-            // 模拟获取的数据
-            let result = format!("城市: {}\n温度: 22°C\n天气: 晴朗", query);
-
-            // ----------------------------------------------------------------
-            // 从后台线程更新 UI
-            // ----------------------------------------------------------------
-            // slint::invoke_from_event_loop: 在主线程事件循环中执行代码
-            //
-            // 【为什么需要这个函数】
-            // - Slint 的 UI 不是线程安全的
-            // - 只能在主线程修改 UI 属性
-            // - 这个函数将闭包调度到主线程执行
-            //
-            // 【原理】
-            // 1. 将闭包发送到主线程的消息队列
-            // 2. 主线程的事件循环取出闭包
-            // 3. 在主线程上下文中执行闭包
-            // 4. 此时修改属性是安全的
-            slint::invoke_from_event_loop(move || {
-                // upgrade(): 尝试将弱引用转换为强引用
-                // 如果组件已销毁，返回 None
-                if let Some(app) = app_weak.upgrade() {
-                    // 更新 UI 属性
-                    app.set_result_data(result.into());
-                    app.set_status_message("数据获取成功!".into());
-                    app.set_is_loading(false);
+        //
+        // 【为什么不能直接在线程里 set_*】
+        // - Slint 的 UI 不是线程安全的
+        // - 只能在主线程修改 UI 属性
+        // - invoke_from_event_loop 将闭包调度到主线程执行
+        spawn_bg(
+            app_weak,
+            move || {
+                // ------------------------------------------------------------
+                // 分阶段汇报进度
+                // ------------------------------------------------------------
+                // 模拟网络延迟（实际应用中这里是真正的网络请求）：
+                // 总共 1 秒，分 4 步，每步结束后把当前进度推给 UI
+                for step in 1..=4 {
+                    thread::sleep(Duration::from_millis(250));
+
+                    let progress = step as f32 / 4.0;
+                    post(&progress_weak, move |app| app.set_progress(progress));
                 }
-                // 如果 upgrade() 返回 None，说明窗口已关闭
-                // 此时什么也不做，安全退出
-            }).unwrap();
-        });
+
+                // 模拟获取的数据
+                let result = format!("城市: {}\n温度: 22°C\n天气: 晴朗", query);
+
+                // 写入缓存，下次同一城市直接命中
+                cache.lock().unwrap().insert(query, result.clone());
+                result
+            },
+            |app, result| {
+                // 已在主线程，窗口也确认还在 → 直接更新 UI 属性
+                app.set_result_data(result.into());
+                app.set_status_message("数据获取成功!".into());
+                app.set_is_loading(false);
+            },
+        );
+    });
+
+    // ------------------------------------------------------------------------
+    // 批量获取：扇出（每个城市一个线程）+ 扇入（join 全部线程）
+    // ------------------------------------------------------------------------
+    let batch_weak = app.as_weak();
+    app.on_fetch_batch(move |input| {
+        let cities = parse_cities(&input);
+        let Some(app) = batch_weak.upgrade() else {
+            return;
+        };
+        if cities.is_empty() {
+            app.set_batch_loading(false);
+            return;
+        }
+
+        // VecModel 只能在主线程使用（Rc，不是 Send）：
+        // 这里先为每个城市放一行"加载中"，交给 UI 持有
+        let rows: Vec<SharedString> = cities.iter().map(|city| loading_row(city)).collect();
+        app.set_batch_results(ModelRc::from(Rc::new(VecModel::from(rows))));
+
+        let worker_weak = batch_weak.clone();
+        spawn_bg(
+            batch_weak.clone(),
+            move || {
+                // 扇出：所有城市同时开始
+                let handles: Vec<_> = cities
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, city)| {
+                        let row_weak = worker_weak.clone();
+                        thread::spawn(move || {
+                            let delay = simulated_delay(&city, index);
+                            thread::sleep(delay);
+                            let row = done_row(&city, delay);
+
+                            // 回到主线程后再从组件里取出模型：
+                            // get_batch_results() 返回 ModelRc，as_any + downcast_ref 拿回 VecModel
+                            post(&row_weak, move |app| {
+                                let results = app.get_batch_results();
+                                if let Some(model) = results.as_any().downcast_ref::<VecModel<SharedString>>() {
+                                    model.set_row_data(index, row);
+                                }
+                            });
+                        })
+                    })
+                    .collect();
+
+                // 扇入：等所有城市都返回（单个线程 panic 不影响其它城市）
+                let total = handles.len();
+                let finished = handles.into_iter().filter_map(|handle| handle.join().ok()).count();
+                (finished, total)
+            },
+            |app, (finished, total)| {
+                app.set_status_message(format!("批量获取完成: {}/{} 个城市", finished, total).into());
+                app.set_batch_loading(false);
+            },
+        );
     });
 
     // 运行应用
@@ -262,15 +446,58 @@ fn main() {
 //    - is-loading: 加载状态
 //    - status-message: 提示信息
 //    - result-data: 结果数据
-//    - 三个状态覆盖完整的异步生命周期
+//    - progress: 进度（0.0 ~ 1.0）
+//    - 这几个状态覆盖完整的异步生命周期
 //
-// 6. 替代方案
+// 6. 中间状态推送
+//    - 工作线程可以多次调用 invoke_from_event_loop
+//    - 每次推送一个进度值（25% / 50% / 75% / 100%）
+//    - progress 属性绑定到 ProgressIndicator，UI 自动刷新
+//
+// 7. 共享状态 + 异步
+//    - Arc<Mutex<HashMap>>: 回调和工作线程共享同一份缓存
+//    - 命中缓存：直接 invoke_from_event_loop，不显示加载状态
+//    - 未命中：后台线程获取后写回缓存
+//
+// 8. 替代方案
 //    - tokio + slint: 使用 tokio 运行时
 //    - async-std: 另一个异步运行时
 //    - 信道 (channel): std::sync::mpsc 或 crossbeam
 //
-// 7. 错误处理
+// 9. 错误处理
 //    - 网络错误：设置 has-error 状态
 //    - 超时：设置超时状态
 //    - 取消：检查组件是否仍存在
+//
+// 10. 扇出 / 扇入（批量获取）
+//    - 扇出：每个城市一个 thread::spawn，同时进行，总耗时≈最慢的那个城市
+//    - 逐个更新：每个线程完成后各自 post 一次，set_row_data 只改自己那一行
+//    - 扇入：协调线程 join 所有 JoinHandle，全部完成后恢复按钮
+//    - VecModel 不是 Send：后台线程不直接持有它，回到主线程后用
+//      get_batch_results().as_any().downcast_ref::<VecModel<_>>() 取回
 // ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_cities_splits_trims_and_dedupes() {
+        assert_eq!(
+            parse_cities(" 北京, 上海，广州,, 北京 ,"),
+            vec!["北京", "上海", "广州"]
+        );
+        assert!(parse_cities(" , ，").is_empty());
+    }
+
+    #[test]
+    fn simulated_delays_differ_between_cities() {
+        let delays: Vec<Duration> = parse_cities("北京, 上海, 广州, 深圳")
+            .iter()
+            .enumerate()
+            .map(|(index, city)| simulated_delay(city, index))
+            .collect();
+        assert!(delays.iter().all(|delay| *delay >= Duration::from_millis(300)));
+        assert!(delays.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
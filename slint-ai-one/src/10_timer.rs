@@ -0,0 +1,232 @@
+// ============================================================================
+// 10_timer.rs - Slint 定时器示例
+// ============================================================================
+//
+// 【核心概念】
+// slint::Timer 是事件循环自带的定时器：
+// 1. Timer::start(TimerMode::Repeated, 间隔, 回调) —— 每隔一段时间触发一次
+// 2. Timer::stop() —— 取消定时器，之后不再触发
+// 3. Timer::single_shot(延时, 回调) —— 只触发一次（08_cross_platform 用过）
+//
+// 【原理说明】
+// 定时器回调由事件循环在主线程里调用：
+// - 回调里可以直接 set_* 修改 UI，不需要 invoke_from_event_loop
+// - 不需要后台线程，也就不需要 Arc / Mutex
+// - 对比 09_async_data：耗时任务放线程；"每隔 N 秒做一点小事"用 Timer 就够了
+//
+// 注意：回调不能阻塞，否则整个 UI 会跟着卡住
+// ============================================================================
+
+slint::slint! {
+    import { Button } from "std-widgets.slint";
+
+    export component TimerDemo inherits Window {
+        width: 360px;
+        height: 300px;
+        title: "定时器示例";
+
+        // ====================================================================
+        // 由 Rust 驱动的状态
+        // ====================================================================
+        // seconds: 已经走过的秒数，每秒由 Timer 回调加 1
+        // clock-text: 格式化后的 "MM:SS"（Rust 端用 format_clock 计算）
+        // running: 定时器是否在跑，用于切换按钮可用状态
+        in property <int> seconds: 0;
+        in property <string> clock-text: "00:00";
+        in property <bool> running: false;
+
+        // 按钮只负责通知 Rust，真正的 Timer 在 Rust 端
+        callback start();
+        callback stop();
+        callback reset();
+
+        Rectangle {
+            background: #f0f0f0;
+
+            VerticalLayout {
+                padding: 20px;
+                spacing: 15px;
+
+                Text {
+                    text: "定时器示例";
+                    font-size: 24px;
+                    color: #333;
+                    horizontal-alignment: center;
+                }
+
+                // 时钟
+                Text {
+                    text: clock-text;
+                    font-size: 56px;
+                    color: running ? #2196F3 : #999;
+                    horizontal-alignment: center;
+
+                    animate color { duration: 200ms; }
+                }
+
+                // 秒针进度条：一分钟走满一圈
+                // animate width 让每秒的跳动变成平滑移动
+                Rectangle {
+                    height: 8px;
+                    border-radius: 4px;
+                    background: #ddd;
+
+                    Rectangle {
+                        x: 0;
+                        width: parent.width * mod(seconds, 60) / 60;
+                        border-radius: 4px;
+                        background: #2196F3;
+
+                        animate width { duration: 300ms; easing: ease-out; }
+                    }
+                }
+
+                HorizontalLayout {
+                    spacing: 10px;
+
+                    Button {
+                        text: "开始";
+                        enabled: !running;
+                        clicked => { start(); }
+                    }
+
+                    Button {
+                        text: "停止";
+                        enabled: running;
+                        clicked => { stop(); }
+                    }
+
+                    Button {
+                        text: "清零";
+                        clicked => { reset(); }
+                    }
+                }
+
+                Text {
+                    text: running ? "计时中…（Timer 每秒触发一次）" : "已停止（Timer 已取消）";
+                    color: #666;
+                    horizontal-alignment: center;
+                }
+            }
+        }
+    }
+}
+
+use slint::{Timer, TimerMode};
+use std::rc::Rc;
+use std::time::Duration;
+
+// 定时器间隔
+const TICK: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// 秒数 → "MM:SS"
+// ============================================================================
+// 超过 99 分钟时分钟位数自然变多（"100:00"），不截断
+fn format_clock(seconds: i32) -> String {
+    let seconds = seconds.max(0);
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+// ============================================================================
+// main 函数
+// ============================================================================
+fn main() {
+    let app = TimerDemo::new().unwrap();
+
+    // ------------------------------------------------------------------------
+    // 定时器
+    // ------------------------------------------------------------------------
+    // Timer 被 drop 时会自动停止 → 必须让它活得和窗口一样久
+    // 三个回调都要用到同一个 Timer，用 Rc 共享（都在主线程，不需要 Arc）
+    let timer = Rc::new(Timer::default());
+
+    // ------------------------------------------------------------------------
+    // 开始：启动一个重复触发的定时器
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    let start_timer = Rc::clone(&timer);
+    app.on_start(move || {
+        // 已经在跑就不重复 start（start 会重置间隔，连点时钟会"卡"一下）
+        if start_timer.running() {
+            return;
+        }
+
+        let tick_weak = app_weak.clone();
+        start_timer.start(TimerMode::Repeated, TICK, move || {
+            // 回调在事件循环里执行 → 直接改属性
+            if let Some(app) = tick_weak.upgrade() {
+                let seconds = app.get_seconds() + 1;
+                app.set_seconds(seconds);
+                app.set_clock_text(format_clock(seconds).into());
+            }
+        });
+
+        if let Some(app) = app_weak.upgrade() {
+            app.set_running(true);
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 停止：取消定时器，已走的秒数保留
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    let stop_timer = Rc::clone(&timer);
+    app.on_stop(move || {
+        stop_timer.stop();
+        if let Some(app) = app_weak.upgrade() {
+            app.set_running(false);
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 清零：只重置秒数，不影响定时器是否在跑
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    app.on_reset(move || {
+        if let Some(app) = app_weak.upgrade() {
+            app.set_seconds(0);
+            app.set_clock_text(format_clock(0).into());
+        }
+    });
+
+    app.run().unwrap();
+}
+
+// ============================================================================
+// 【知识点总结】
+// ============================================================================
+//
+// 1. slint::Timer
+//    - Timer::default() 创建，start(mode, interval, callback) 启动
+//    - TimerMode::Repeated: 重复触发；TimerMode::SingleShot: 只触发一次
+//    - stop(): 取消；running(): 查询是否在跑
+//    - Timer 被 drop 时自动停止 → 要把它保存在足够长寿的地方
+//
+// 2. 与线程方案对比（09_async_data）
+//    - Timer 回调运行在主线程 → 可以直接 set_*，不需要 invoke_from_event_loop
+//    - 适合轻量的周期性更新（时钟、轮询、自动保存）
+//    - 耗时工作仍然要放到后台线程，否则会卡住 UI
+//
+// 3. 共享 Timer
+//    - 多个回调共用一个 Timer → Rc<Timer>
+//    - 全部在主线程 → Rc 就够了，不需要 Arc<Mutex<...>>
+//
+// 4. 动画
+//    - animate width / color { duration: ...; }：属性一变化就自动补间
+//    - Rust 每秒只改一次 seconds，平滑移动由 Slint 完成
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_clock_pads_minutes_and_seconds() {
+        assert_eq!(format_clock(0), "00:00");
+        assert_eq!(format_clock(59), "00:59");
+        assert_eq!(format_clock(61), "01:01");
+        assert_eq!(format_clock(6000), "100:00");
+        assert_eq!(format_clock(-5), "00:00");
+    }
+}
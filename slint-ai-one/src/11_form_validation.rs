@@ -0,0 +1,325 @@
+// ============================================================================
+// 11_form_validation.rs - Slint 表单校验示例
+// ============================================================================
+//
+// 【核心概念】
+// 一个"新建课程"表单：老师 ID + 课程名称，点"提交"时整体校验
+// 1. 每个字段一个错误属性（teacher-id-error / name-error），空字符串表示没有错误
+// 2. 字段下方显示行内错误，输入框边框标红（颜色随主题切换，沿用 04 的 theme 写法）
+// 3. 表单顶部显示校验汇总（"有 N 处需要修改"）
+// 4. 只有全部字段合法时，Rust 才会触发 submit(int, string)
+//
+// 【原理说明】
+// 校验规则写在 Rust 的纯函数 validate_course_form 里：
+// - Slint 只负责把输入框文本交给 Rust（submit-pressed 回调）
+// - Rust 校验后把错误写回属性，UI 自动刷新
+// - 纯函数不依赖 UI，可以直接写单元测试
+// ============================================================================
+
+slint::slint! {
+    import { Button, LineEdit } from "std-widgets.slint";
+
+    export component FormValidation inherits Window {
+        width: 420px;
+        height: 460px;
+        title: "表单校验示例";
+
+        // ====================================================================
+        // 主题（与 04_state_management 相同的写法）
+        // ====================================================================
+        in-out property <string> theme: "light";
+
+        // ====================================================================
+        // 表单字段与错误
+        // ====================================================================
+        // *-text: 输入框内容（双向绑定）
+        // *-error: 该字段的错误信息，空字符串表示合法（由 Rust 写入）
+        // summary: 顶部的校验汇总；submitted: 最近一次提交成功的提示
+        in-out property <string> teacher-id-text;
+        in-out property <string> name-text;
+        in-out property <string> teacher-id-error;
+        in-out property <string> name-error;
+        in property <string> summary;
+        in property <string> submitted;
+
+        // 点"提交"：把两个字段的原始文本交给 Rust 校验
+        callback submit-pressed(string, string);
+        // 全部合法时由 Rust 触发：老师 ID（已转成整数）+ 去掉首尾空白的课程名
+        callback submit(int, string);
+
+        // 错误颜色：浅色主题深红，深色主题浅红，保证两种背景下都看得清
+        property <color> error-color: theme == "light" ? #cc0000 : #ff7777;
+        property <color> text-color: theme == "light" ? #333333 : #ffffff;
+
+        Rectangle {
+            background: theme == "light" ? #ffffff : #333333;
+
+            VerticalLayout {
+                padding: 20px;
+                spacing: 10px;
+                alignment: start;
+
+                HorizontalLayout {
+                    spacing: 10px;
+
+                    Text {
+                        text: "新建课程";
+                        font-size: 24px;
+                        color: text-color;
+                        horizontal-stretch: 1;
+                    }
+
+                    Button {
+                        text: theme == "light" ? "深色" : "浅色";
+                        clicked => { theme = theme == "light" ? "dark" : "light"; }
+                    }
+                }
+
+                // 校验汇总：有错误时才显示
+                if summary != "": Rectangle {
+                    height: 36px;
+                    border-radius: 4px;
+                    background: theme == "light" ? #fde8e8 : #5a2a2a;
+
+                    Text {
+                        text: summary;
+                        color: error-color;
+                        vertical-alignment: center;
+                        horizontal-alignment: center;
+                    }
+                }
+
+                // ============================================================
+                // 字段 1：老师 ID
+                // ============================================================
+                Text {
+                    text: "老师 ID";
+                    color: text-color;
+                }
+
+                Rectangle {
+                    border-width: 2px;
+                    border-radius: 4px;
+                    border-color: teacher-id-error != "" ? error-color : transparent;
+
+                    LineEdit {
+                        text <=> teacher-id-text;
+                        placeholder-text: "例如 1";
+                        // 重新编辑时先去掉旧的错误提示，下次提交再校验
+                        edited(text) => { teacher-id-error = ""; }
+                    }
+                }
+
+                if teacher-id-error != "": Text {
+                    text: teacher-id-error;
+                    font-size: 12px;
+                    color: error-color;
+                }
+
+                // ============================================================
+                // 字段 2：课程名称
+                // ============================================================
+                Text {
+                    text: "课程名称";
+                    color: text-color;
+                }
+
+                Rectangle {
+                    border-width: 2px;
+                    border-radius: 4px;
+                    border-color: name-error != "" ? error-color : transparent;
+
+                    LineEdit {
+                        text <=> name-text;
+                        placeholder-text: "例如 Rust 入门";
+                        edited(text) => { name-error = ""; }
+                        // 在输入框里按回车也算提交
+                        accepted(text) => { submit-pressed(teacher-id-text, name-text); }
+                    }
+                }
+
+                if name-error != "": Text {
+                    text: name-error;
+                    font-size: 12px;
+                    color: error-color;
+                }
+
+                Button {
+                    text: "提交";
+                    clicked => { submit-pressed(teacher-id-text, name-text); }
+                }
+
+                if submitted != "": Text {
+                    text: submitted;
+                    color: theme == "light" ? #2e7d32 : #8bc34a;
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 校验规则
+// ============================================================================
+// 课程名称最大长度（按字符数，不是字节数）
+const NAME_MAX_CHARS: usize = 50;
+
+// 每个字段的错误；None 表示该字段合法
+#[derive(Debug, Default, PartialEq)]
+struct FormErrors {
+    teacher_id: Option<String>,
+    name: Option<String>,
+}
+
+impl FormErrors {
+    fn count(&self) -> usize {
+        [&self.teacher_id, &self.name]
+            .iter()
+            .filter(|error| error.is_some())
+            .count()
+    }
+
+    // 顶部汇总文字
+    fn summary(&self) -> String {
+        format!("有 {} 处需要修改", self.count())
+    }
+}
+
+// 校验整个表单：全部合法返回 (老师 ID, 去掉首尾空白的名称)，否则返回所有字段的错误
+// 不在第一个错误处停下：用户一次就能看到所有要改的地方
+fn validate_course_form(teacher_id: &str, name: &str) -> Result<(i32, String), FormErrors> {
+    let mut errors = FormErrors::default();
+
+    let teacher_id = teacher_id.trim();
+    let parsed_id = if teacher_id.is_empty() {
+        errors.teacher_id = Some("请输入老师 ID".into());
+        None
+    } else {
+        match teacher_id.parse::<i32>() {
+            Ok(id) if id > 0 => Some(id),
+            Ok(_) => {
+                errors.teacher_id = Some("老师 ID 必须大于 0".into());
+                None
+            }
+            Err(_) => {
+                errors.teacher_id = Some("老师 ID 必须是整数".into());
+                None
+            }
+        }
+    };
+
+    let name = name.trim();
+    if name.is_empty() {
+        errors.name = Some("课程名称不能为空".into());
+    } else if name.chars().count() > NAME_MAX_CHARS {
+        errors.name = Some(format!("课程名称不能超过 {} 个字符", NAME_MAX_CHARS));
+    }
+
+    match parsed_id {
+        Some(id) if errors.count() == 0 => Ok((id, name.to_string())),
+        _ => Err(errors),
+    }
+}
+
+// ============================================================================
+// main 函数
+// ============================================================================
+fn main() {
+    let app = FormValidation::new().unwrap();
+
+    // ------------------------------------------------------------------------
+    // 提交：校验 → 写回错误属性 → 全部合法才触发 submit
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    app.on_submit_pressed(move |teacher_id, name| {
+        let Some(app) = app_weak.upgrade() else {
+            return;
+        };
+
+        match validate_course_form(&teacher_id, &name) {
+            Ok((id, name)) => {
+                app.set_teacher_id_error("".into());
+                app.set_name_error("".into());
+                app.set_summary("".into());
+                // invoke_submit: 从 Rust 调用 Slint 里声明的回调，处理函数见下面的 on_submit
+                app.invoke_submit(id, name.into());
+            }
+            Err(errors) => {
+                app.set_teacher_id_error(errors.teacher_id.clone().unwrap_or_default().into());
+                app.set_name_error(errors.name.clone().unwrap_or_default().into());
+                app.set_summary(errors.summary().into());
+                app.set_submitted("".into());
+            }
+        }
+    });
+
+    // ------------------------------------------------------------------------
+    // 合法的表单：这里只打印出来（真实项目里就是 POST /courses）
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    app.on_submit(move |teacher_id, name| {
+        println!("新建课程: teacher_id = {}, name = {:?}", teacher_id, name.as_str());
+        if let Some(app) = app_weak.upgrade() {
+            app.set_submitted(format!("已提交：{}（老师 {}）", name, teacher_id).into());
+            app.set_name_text("".into());
+        }
+    });
+
+    app.run().unwrap();
+}
+
+// ============================================================================
+// 【知识点总结】
+// ============================================================================
+//
+// 1. 每个字段一个错误属性
+//    - 空字符串 = 合法，非空 = 要显示的错误信息
+//    - if xxx-error != "": Text { ... } 只在有错误时显示
+//    - border-color 绑定到错误属性，出错时标红
+//
+// 2. 校验放在 Rust 纯函数里
+//    - validate_course_form(&str, &str) -> Result<(i32, String), FormErrors>
+//    - 一次收集所有字段的错误，而不是遇到第一个就返回
+//    - 不依赖 UI，直接单元测试
+//
+// 3. 回调的两个方向
+//    - submit-pressed：Slint → Rust（按钮点击，交出原始文本）
+//    - submit：Rust 校验通过后用 invoke_submit 触发，参数已经是类型化的 int / string
+//
+// 4. 主题色
+//    - error-color / text-color 是依赖 theme 的私有属性
+//    - 切换主题时所有引用它们的地方一起更新
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_form_returns_typed_values() {
+        assert_eq!(
+            validate_course_form(" 7 ", "  Rust 入门 "),
+            Ok((7, "Rust 入门".to_string()))
+        );
+    }
+
+    #[test]
+    fn every_invalid_field_gets_its_own_error() {
+        let errors = validate_course_form("abc", "   ").unwrap_err();
+        assert_eq!(errors.teacher_id.as_deref(), Some("老师 ID 必须是整数"));
+        assert_eq!(errors.name.as_deref(), Some("课程名称不能为空"));
+        assert_eq!(errors.summary(), "有 2 处需要修改");
+
+        let errors = validate_course_form("", "ok").unwrap_err();
+        assert_eq!(errors.teacher_id.as_deref(), Some("请输入老师 ID"));
+        assert_eq!(errors.name, None);
+        assert_eq!(errors.count(), 1);
+
+        let errors = validate_course_form("0", &"课".repeat(NAME_MAX_CHARS + 1)).unwrap_err();
+        assert_eq!(errors.teacher_id.as_deref(), Some("老师 ID 必须大于 0"));
+        assert!(errors.name.unwrap().contains("50"));
+
+        // 正好 NAME_MAX_CHARS 个汉字（150 字节）仍然合法：按字符数而不是字节数
+        assert!(validate_course_form("1", &"课".repeat(NAME_MAX_CHARS)).is_ok());
+    }
+}
@@ -0,0 +1,253 @@
+// ============================================================================
+// 12_modal_dialog.rs - Slint 模态对话框示例
+// ============================================================================
+//
+// 【核心概念】
+// 课程列表 + "删除"确认框：
+// 1. 可复用的 ConfirmDialog 组件：message 属性 + accepted / cancelled 两个回调
+// 2. show-modal: bool 控制遮罩层是否存在（if show-modal: ...）
+// 3. 遮罩层盖在整个窗口上，里面的 TouchArea 吞掉所有点击 → 后面的内容点不到
+// 4. 只有"确定"会触发 confirmed()，由 Rust 真正删除数据；"取消"只关闭对话框
+//
+// 【原理说明】
+// Slint 里后声明的子元素画在上面，也先收到鼠标事件：
+// - 遮罩层写在窗口内容之后 → 叠在最上层
+// - 遮罩层里的 TouchArea 铺满整个窗口，点击在这里就被处理掉，不会再往下传
+// - if 条件为 false 时遮罩层整个不存在，下面的内容恢复可点击
+// ============================================================================
+
+slint::slint! {
+    import { Button } from "std-widgets.slint";
+
+    // ========================================================================
+    // 可复用的确认框
+    // ========================================================================
+    // 只负责显示和把按钮点击转成回调，不关心"确认的是什么"：
+    // 文字由使用方通过 message 传入，确认 / 取消之后做什么也由使用方决定
+    component ConfirmDialog inherits Rectangle {
+        in property <string> message: "确认删除?";
+        callback accepted();
+        callback cancelled();
+
+        width: 280px;
+        height: 140px;
+        border-radius: 8px;
+        background: #ffffff;
+        drop-shadow-blur: 12px;
+        drop-shadow-color: #00000060;
+
+        VerticalLayout {
+            padding: 20px;
+            spacing: 20px;
+
+            Text {
+                text: message;
+                font-size: 18px;
+                color: #333;
+                wrap: word-wrap;
+                horizontal-alignment: center;
+                vertical-alignment: center;
+            }
+
+            HorizontalLayout {
+                spacing: 10px;
+                alignment: end;
+
+                Button {
+                    text: "取消";
+                    clicked => { cancelled(); }
+                }
+
+                Button {
+                    text: "确定";
+                    primary: true;
+                    clicked => { accepted(); }
+                }
+            }
+        }
+    }
+
+    export component ModalDialog inherits Window {
+        width: 400px;
+        height: 360px;
+        title: "模态对话框示例";
+
+        // ====================================================================
+        // 数据与模态状态
+        // ====================================================================
+        // courses: 课程列表（Rust 端的 VecModel）
+        // show-modal: 对话框是否打开
+        // pending-index: 等待确认删除的行，对话框打开时才有意义
+        // status: 最近一次操作的结果（由 Rust 写入）
+        in property <[string]> courses;
+        in-out property <bool> show-modal: false;
+        in-out property <int> pending-index: -1;
+        in property <string> status;
+
+        // 只有点"确定"才触发，Rust 在这里删除 pending-index 对应的课程
+        callback confirmed();
+
+        // ====================================================================
+        // 第一层：窗口内容
+        // ====================================================================
+        Rectangle {
+            background: #f0f0f0;
+
+            VerticalLayout {
+                padding: 20px;
+                spacing: 10px;
+                alignment: start;
+
+                Text {
+                    text: "课程列表";
+                    font-size: 24px;
+                    color: #333;
+                }
+
+                for course[index] in courses: HorizontalLayout {
+                    spacing: 10px;
+
+                    Text {
+                        text: course;
+                        color: #333;
+                        vertical-alignment: center;
+                        horizontal-stretch: 1;
+                    }
+
+                    // 删除按钮只负责打开对话框，记下要删的是哪一行
+                    Button {
+                        text: "删除";
+                        clicked => {
+                            pending-index = index;
+                            show-modal = true;
+                        }
+                    }
+                }
+
+                if courses.length == 0: Text {
+                    text: "没有课程了";
+                    color: #999;
+                }
+
+                Text {
+                    text: status;
+                    color: #666;
+                }
+            }
+        }
+
+        // ====================================================================
+        // 第二层：遮罩 + 对话框（写在后面 → 画在上面）
+        // ====================================================================
+        if show-modal: Rectangle {
+            width: 100%;
+            height: 100%;
+            // 半透明黑色：后面的内容还看得见，但明显"被挡住了"
+            background: #00000080;
+
+            // 铺满遮罩的 TouchArea：点击、滚轮都在这里被处理掉，传不到后面的按钮
+            // 点遮罩空白处什么也不做（想"点外面关闭"可以在 clicked 里 show-modal = false）
+            TouchArea {
+                scroll-event(event) => { accept }
+            }
+
+            // 写在 TouchArea 之后 → 对话框里的按钮在 TouchArea 上面，仍然可以点
+            ConfirmDialog {
+                message: "确认删除「" + courses[pending-index] + "」?";
+                accepted => {
+                    show-modal = false;
+                    confirmed();
+                }
+                cancelled => {
+                    show-modal = false;
+                }
+            }
+        }
+    }
+}
+
+use slint::{Model, ModelRc, SharedString, VecModel};
+use std::rc::Rc;
+
+// ============================================================================
+// 删除一行
+// ============================================================================
+// index 来自 UI（int），可能是 -1 或者在确认期间已经越界 → 返回 None，不 panic
+fn remove_course(courses: &VecModel<SharedString>, index: i32) -> Option<SharedString> {
+    let index = usize::try_from(index).ok().filter(|&index| index < courses.row_count())?;
+    Some(courses.remove(index))
+}
+
+// ============================================================================
+// main 函数
+// ============================================================================
+fn main() {
+    let app = ModalDialog::new().unwrap();
+
+    let courses = Rc::new(VecModel::from(vec![
+        SharedString::from("Rust 入门"),
+        SharedString::from("Actix Web 实战"),
+        SharedString::from("Slint 桌面开发"),
+        SharedString::from("SQLx 与 PostgreSQL"),
+    ]));
+    app.set_courses(ModelRc::from(courses.clone()));
+
+    // ------------------------------------------------------------------------
+    // 确认删除：对话框已经在 Slint 端关闭，这里只处理数据
+    // ------------------------------------------------------------------------
+    let app_weak = app.as_weak();
+    app.on_confirmed(move || {
+        let Some(app) = app_weak.upgrade() else {
+            return;
+        };
+
+        let status = match remove_course(&courses, app.get_pending_index()) {
+            Some(name) => format!("已删除：{}", name),
+            None => "要删除的课程已经不存在".to_string(),
+        };
+        println!("{}", status);
+        app.set_status(status.into());
+        app.set_pending_index(-1);
+    });
+
+    app.run().unwrap();
+}
+
+// ============================================================================
+// 【知识点总结】
+// ============================================================================
+//
+// 1. 分层
+//    - 同一个父元素里，后声明的子元素画在上面、先收到鼠标事件
+//    - 遮罩层写在窗口内容之后，就自然盖住了整个窗口
+//
+// 2. 模态 = 条件渲染 + 吞掉事件
+//    - if show-modal: Rectangle { ... } 控制遮罩是否存在
+//    - 遮罩里铺满的 TouchArea 接住点击和滚轮，后面的内容收不到
+//
+// 3. 可复用组件
+//    - ConfirmDialog 只暴露 message / accepted / cancelled
+//    - 使用方决定文字内容，以及确认 / 取消之后做什么
+//
+// 4. UI 状态和数据分开
+//    - 打开 / 关闭对话框是纯 UI 状态，在 Slint 里直接改 show-modal
+//    - 真正删除数据的只有 confirmed()，由 Rust 处理
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_course_ignores_out_of_range_indices() {
+        let courses = VecModel::from(vec![SharedString::from("A"), SharedString::from("B")]);
+
+        assert_eq!(remove_course(&courses, -1), None);
+        assert_eq!(remove_course(&courses, 2), None);
+        assert_eq!(courses.row_count(), 2);
+
+        assert_eq!(remove_course(&courses, 0), Some(SharedString::from("A")));
+        assert_eq!(courses.row_count(), 1);
+        assert_eq!(courses.row_data(0), Some(SharedString::from("B")));
+    }
+}
@@ -0,0 +1,235 @@
+// ============================================================================
+// 13_window_resize.rs - Slint 窗口尺寸变化示例
+// ============================================================================
+//
+// 【核心概念】
+// 窗口尺寸是 Window 的 width / height 属性，用户拖动窗口边框时它们会变：
+// 1. changed width => { ... } / changed height => { ... } —— 属性变化回调
+// 2. 在变化回调里调用 resized(length, length)，把新尺寸交给 Rust
+// 3. Rust 打印日志、更新"当前尺寸"文字，并按宽度决定布局（LayoutMode）
+// 4. Slint 根据 layout-mode 切换三种布局：宽屏两栏 / 中等上下 / 窄屏只留内容
+//
+// 【原理说明】
+// - 窗口不写死 width / height，只给 preferred-* 和 min-*，才能被拖动改变大小
+// - changed 回调在属性值真正变化后触发（同一帧里多次变化只触发一次）
+// - 断点判断写在 Rust 的纯函数 layout_for 里，可以单元测试
+//   （纯 Slint 也能写 width < 400px ? ... : ...，这里是为了演示"Rust 感知尺寸"）
+// ============================================================================
+
+slint::slint! {
+    import { Button } from "std-widgets.slint";
+
+    // ========================================================================
+    // 布局模式：由 Rust 决定，Slint 只负责按模式显示
+    // ========================================================================
+    // Rust 端生成 enum LayoutMode { Narrow, Medium, Wide }
+    export enum LayoutMode { narrow, medium, wide }
+
+    // 侧边栏：宽屏在左边，中等宽度时放在顶部变成一行
+    component Sidebar inherits Rectangle {
+        in property <bool> horizontal;
+        background: #2c3e50;
+
+        if horizontal: HorizontalLayout {
+            padding: 8px;
+            spacing: 8px;
+            Button { text: "首页"; }
+            Button { text: "课程"; }
+            Button { text: "设置"; }
+        }
+
+        if !horizontal: VerticalLayout {
+            padding: 8px;
+            spacing: 8px;
+            alignment: start;
+            Button { text: "首页"; }
+            Button { text: "课程"; }
+            Button { text: "设置"; }
+        }
+    }
+
+    // 内容区：显示当前尺寸和布局模式
+    component Content inherits Rectangle {
+        in property <string> size-text;
+        in property <LayoutMode> mode;
+        background: #ffffff;
+
+        VerticalLayout {
+            padding: 20px;
+            spacing: 10px;
+            alignment: center;
+
+            Text {
+                text: size-text;
+                font-size: 22px;
+                color: #333;
+                horizontal-alignment: center;
+            }
+
+            Text {
+                text: mode == LayoutMode.wide ? "宽屏：侧边栏在左"
+                    : mode == LayoutMode.medium ? "中等：侧边栏在上"
+                    : "窄屏：只显示内容";
+                color: #2196F3;
+                horizontal-alignment: center;
+            }
+
+            Text {
+                text: "拖动窗口边框试试";
+                font-size: 12px;
+                color: #999;
+                horizontal-alignment: center;
+            }
+        }
+    }
+
+    export component WindowResize inherits Window {
+        // 不写死 width / height：窗口才能被拖动改变大小
+        preferred-width: 640px;
+        preferred-height: 360px;
+        min-width: 240px;
+        min-height: 200px;
+        title: "窗口尺寸示例";
+
+        // ====================================================================
+        // 由 Rust 写入的状态
+        // ====================================================================
+        in property <string> size-text: "当前尺寸：-";
+        in property <LayoutMode> layout-mode: LayoutMode.wide;
+
+        // 尺寸变化时通知 Rust（参数是逻辑像素）
+        callback resized(length, length);
+
+        // ====================================================================
+        // 属性变化回调：宽、高任何一个变了都把两者一起交给 Rust
+        // ====================================================================
+        changed width => { resized(self.width, self.height); }
+        changed height => { resized(self.width, self.height); }
+
+        // 窗口刚显示时也报一次初始尺寸
+        init => { resized(self.width, self.height); }
+
+        // ====================================================================
+        // 三种布局，同一时刻只有一种存在
+        // ====================================================================
+        if layout-mode == LayoutMode.wide: HorizontalLayout {
+            Sidebar {
+                width: 120px;
+                horizontal: false;
+            }
+            Content {
+                size-text: size-text;
+                mode: layout-mode;
+            }
+        }
+
+        if layout-mode == LayoutMode.medium: VerticalLayout {
+            Sidebar {
+                height: 56px;
+                horizontal: true;
+            }
+            Content {
+                size-text: size-text;
+                mode: layout-mode;
+            }
+        }
+
+        if layout-mode == LayoutMode.narrow: Content {
+            size-text: size-text;
+            mode: layout-mode;
+        }
+    }
+}
+
+// ============================================================================
+// 响应式断点
+// ============================================================================
+// 宽度（逻辑像素）达到 WIDE_MIN 用两栏，达到 MEDIUM_MIN 用上下布局，否则只留内容
+const WIDE_MIN: f32 = 560.0;
+const MEDIUM_MIN: f32 = 360.0;
+
+fn layout_for(width: f32) -> LayoutMode {
+    if width >= WIDE_MIN {
+        LayoutMode::Wide
+    } else if width >= MEDIUM_MIN {
+        LayoutMode::Medium
+    } else {
+        LayoutMode::Narrow
+    }
+}
+
+fn format_size(width: f32, height: f32) -> String {
+    format!("当前尺寸：{:.0} × {:.0}", width, height)
+}
+
+// ============================================================================
+// main 函数
+// ============================================================================
+fn main() {
+    let app = WindowResize::new().unwrap();
+
+    // ------------------------------------------------------------------------
+    // 尺寸变化：打印日志 → 更新文字 → 决定布局
+    // ------------------------------------------------------------------------
+    // length 在 Rust 里是 f32（逻辑像素，和 DPI 缩放无关）
+    // 拖动窗口时会连续触发，回调里只做很轻的工作
+    let app_weak = app.as_weak();
+    app.on_resized(move |width, height| {
+        let Some(app) = app_weak.upgrade() else {
+            return;
+        };
+
+        let mode = layout_for(width);
+        // 只在模式真正变化时打印，避免拖动时刷屏
+        if mode != app.get_layout_mode() {
+            println!("尺寸 {:.0}x{:.0} → 布局切换为 {:?}", width, height, mode);
+        }
+
+        app.set_size_text(format_size(width, height).into());
+        app.set_layout_mode(mode);
+    });
+
+    app.run().unwrap();
+}
+
+// ============================================================================
+// 【知识点总结】
+// ============================================================================
+//
+// 1. 可调整大小的窗口
+//    - 写死 width / height 的窗口不能拖动改变大小
+//    - 用 preferred-width / preferred-height 给初始大小，min-* 限制最小值
+//
+// 2. changed 回调
+//    - changed width => { ... }：属性变化后触发
+//    - 适合"属性变了要通知外部"，而不是计算另一个属性（那用绑定就够了）
+//    - init => { ... } 补上第一次的初始值
+//
+// 3. Slint 枚举
+//    - export enum LayoutMode { narrow, medium, wide }
+//    - Rust 端生成 LayoutMode::Narrow / Medium / Wide，可以直接 set_layout_mode
+//
+// 4. 响应式布局
+//    - 断点判断放在 Rust 纯函数 layout_for 里，方便测试
+//    - Slint 用 if layout-mode == ...: 切换整块布局
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_switches_at_breakpoints() {
+        assert_eq!(layout_for(800.0), LayoutMode::Wide);
+        assert_eq!(layout_for(WIDE_MIN), LayoutMode::Wide);
+        assert_eq!(layout_for(WIDE_MIN - 1.0), LayoutMode::Medium);
+        assert_eq!(layout_for(MEDIUM_MIN), LayoutMode::Medium);
+        assert_eq!(layout_for(MEDIUM_MIN - 1.0), LayoutMode::Narrow);
+        assert_eq!(layout_for(0.0), LayoutMode::Narrow);
+    }
+
+    #[test]
+    fn size_text_rounds_to_whole_pixels() {
+        assert_eq!(format_size(640.4, 359.6), "当前尺寸：640 × 360");
+    }
+}
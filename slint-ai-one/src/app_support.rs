@@ -0,0 +1,83 @@
+// ============================================================================
+// app_support.rs - 示例共用的后台任务辅助函数
+// ============================================================================
+//
+// 【核心概念】
+// 每个需要后台任务的示例都会重复同样的套路：
+// 1. as_weak() 拿到组件的弱引用
+// 2. thread::spawn 在新线程里干活
+// 3. invoke_from_event_loop 把结果送回主线程
+// 4. upgrade() 检查窗口是否还在，再调用 set_*
+//
+// 本模块把这套"管道"封装成两个泛型函数：
+// - post:     从任意线程向 UI 推送一次更新
+// - spawn_bg: 后台执行 work，完成后把结果交给 on_done 在主线程更新 UI
+//
+// 【使用方式】
+// 在示例文件顶部声明模块即可（与本文件同在 src/ 目录下）：
+//
+// mod app_support;
+// use app_support::{post, spawn_bg};
+//
+// spawn_bg(app.as_weak(), move || fetch(&city), |app, result| {
+//     app.set_result_data(result.into());
+// });
+// ============================================================================
+
+// 允许部分示例只用到其中一个函数
+#![allow(dead_code)]
+
+use slint::{ComponentHandle, Weak};
+use std::thread;
+
+// ============================================================================
+// post - 从任意线程向 UI 推送一次更新
+// ============================================================================
+// 泛型参数：
+// - C: 组件类型（slint! 生成的 AsyncData 等），必须实现 ComponentHandle
+// - U: 更新 UI 的闭包，拿到强引用 app 后调用 set_*
+//
+// 【为什么需要 Send】
+// 闭包要从工作线程发送到主线程的消息队列，所以必须能跨线程移动
+//
+// 【窗口已关闭】
+// upgrade() 返回 None 时直接忽略这次更新，不会 panic
+// 事件循环已经退出时 invoke_from_event_loop 返回 Err，同样忽略（没有界面可更新了）
+pub fn post<C, U>(app_weak: &Weak<C>, update: U)
+where
+    C: ComponentHandle + 'static,
+    U: FnOnce(C) + Send + 'static,
+{
+    let app_weak = app_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(app) = app_weak.upgrade() {
+            update(app);
+        }
+    });
+}
+
+// ============================================================================
+// spawn_bg - 后台执行任务，完成后回到主线程更新 UI
+// ============================================================================
+// 泛型参数：
+// - C: 组件类型
+// - T: 任务结果类型，要从工作线程送回主线程 → 必须 Send
+// - W: 后台任务，在新线程中执行，返回 T
+// - D: 完成回调，在主线程中执行，拿到 (app, 结果)
+//
+// 【执行顺序】
+// 1. thread::spawn 启动工作线程，运行 work()
+// 2. work 返回后，通过 post 把 on_done 调度回事件循环
+// 3. 主线程执行 on_done(app, result)，此时修改属性是安全的
+pub fn spawn_bg<C, T, W, D>(app_weak: Weak<C>, work: W, on_done: D)
+where
+    C: ComponentHandle + 'static,
+    T: Send + 'static,
+    W: FnOnce() -> T + Send + 'static,
+    D: FnOnce(C, T) + Send + 'static,
+{
+    thread::spawn(move || {
+        let result = work();
+        post(&app_weak, move |app| on_done(app, result));
+    });
+}
@@ -0,0 +1,14 @@
+-- 课程表：与 db/db.sql 保持一致
+-- id          int4       自增主键（GENERATED ALWAYS，插入时不能手动指定）
+-- teacher_id  int4       所属老师，可空，默认 0
+-- name        varchar    课程名，可空，默认 ''
+-- time        timestamp  创建时间，可空，默认 now()
+CREATE SCHEMA IF NOT EXISTS rust_test1;
+
+CREATE TABLE IF NOT EXISTS rust_test1.course (
+	id int4 GENERATED ALWAYS AS IDENTITY NOT NULL,
+	teacher_id int4 DEFAULT 0 NULL,
+	"name" varchar DEFAULT ''::character varying NULL,
+	"time" timestamp DEFAULT now() NULL,
+	CONSTRAINT course_pk PRIMARY KEY (id)
+);
@@ -0,0 +1,10 @@
+-- 持久化计数器：name 唯一，value 单调递增
+-- visits 行给 /health 的访问计数用（见 db_access::increment_visit_counter_db），重启服务不清零
+CREATE TABLE IF NOT EXISTS rust_test1.counters (
+	"name" varchar NOT NULL,
+	value int8 DEFAULT 0 NOT NULL,
+	CONSTRAINT counters_pk PRIMARY KEY ("name")
+);
+
+INSERT INTO rust_test1.counters ("name", value) VALUES ('visits', 0)
+ON CONFLICT ("name") DO NOTHING;
@@ -0,0 +1,43 @@
+// ========== 1. 审计日志（只追加的 JSON Lines） ==========
+// 每次课程增删都往 AUDIT_LOG 指向的文件追加一行：
+//   {"ts":"2026-01-07T12:00:00Z","event":"create","course":{...}}
+// 一行一个 JSON → 可以直接 tail -f / grep / jq，也方便导入日志系统
+// 文件路径来自 Config.audit_log（配置文件的 audit_log 或环境变量 AUDIT_LOG，见 config.rs）
+// 没配置 → 不记录（AppState.audit_log 为 None）
+use crate::models::Course;
+use crate::state::AppState;
+use chrono::Utc;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+// ========== 2. 写一行审计记录 ==========
+impl AppState {
+    // 2.1 handler 里调用：app_state.audit("create", &course)
+    //      写失败只打印到 stderr，不影响请求本身（审计是旁路功能）
+    pub fn audit(&self, event: &str, course: &Course) {
+        let Some(path) = &self.audit_log else {
+            return;
+        };
+        if let Err(err) = append_line(path, event, course) {
+            eprintln!("failed to write audit log {}: {}", path.display(), err);
+        }
+    }
+}
+
+// 2.2 先把整行拼好再一次 write_all：O_APPEND 模式下多个 worker 同时写也不会互相穿插
+fn append_line(path: &Path, event: &str, course: &Course) -> std::io::Result<()> {
+    let entry = serde_json::json!({
+        "ts": Utc::now().to_rfc3339(),
+        "event": event,
+        "course": course,
+    });
+    let mut line = entry.to_string();
+    line.push('\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
@@ -2,16 +2,17 @@
 // - `web`：用于处理请求参数、共享状态（Data）、路径配置等；
 // - `App`：代表一个 Web 应用实例；
 // - `HttpServer`：用于创建并运行 HTTP 服务器。
-use actix_web::{web, App, HttpServer};
+use actix_web::{middleware, web, App, HttpServer};
 
 // 引入标准库的 I/O 模块，用于处理如端口绑定失败等 I/O 错误。
 use std::io;
 
 // 引入标准库的互斥锁 Mutex，用于在多线程环境中安全地修改共享数据（如访问计数）。
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use dotenv::dotenv;
-use std::env;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgPool, PgPoolOptions};
 
 // 手动指定模块文件路径（不推荐常规使用，但可用于特殊项目结构）：
 // 将上一级目录中的 `handlers.rs` 文件作为本地模块 `handlers` 引入。
@@ -51,21 +52,67 @@ mod state;
 #[path = "../models.rs"]
 mod models;
 
+// 请求 ID 中间件（X-Request-Id），见 `request_id.rs`
+#[path = "../request_id.rs"]
+mod request_id;
+
+// 课程增删的审计日志（AUDIT_LOG），见 `audit.rs`
+#[path = "../audit.rs"]
+mod audit;
+
+// 游戏分数排行榜（内存），见 `scores.rs`
+#[path = "../scores.rs"]
+mod scores;
+
+// 服务配置：配置文件 + 环境变量覆盖，见 `config.rs`
+#[path = "../config.rs"]
+mod config;
+
 // 从 `routers` 模块中导入所有公开项（通常是路由配置函数，如 `general_routes`）。
 use routers::*;
 
 // 从 `state` 模块中导入 `AppState` 类型，用于构建应用的共享状态。
 use state::AppState;
 
+// 从 `config` 模块导入服务配置（监听地址、连接池、审计日志等）
+use config::Config;
+
+// ====== 路由装配 ======
+// main 里的 App 工厂和测试共用这一个函数 → 测试跑的就是线上同一套路由和兜底 404
+fn configure_app(cfg: &mut web::ServiceConfig) {
+    // 调用 `general_routes` 等函数来批量注册路由（这些函数在 `routers.rs` 中定义）
+    cfg.configure(general_routes)
+        .configure(course_routes)
+        .configure(teacher_routes)
+        .configure(score_routes)
+        // 兜底：没匹配上的路由统一返回 JSON 404
+        .default_service(web::to(handlers::not_found_handler));
+}
+
 // `#[actix_web::main]` 是 Actix Web 提供的宏，用于将 `async fn main` 转换为
 // 基于 Tokio 异步运行时的入口点。没有它，Rust 不允许 `main` 函数是异步的。
 #[actix_web::main]
 async fn main() -> io::Result<()> {
 
+    // .env 先写进环境变量，再读配置：.env 里的值和真实环境变量一样能覆盖配置文件
     dotenv().ok();
-    let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-    let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+    // 配置有误（文件格式、地址、数字写错、缺 database_url）→ 启动时直接报错退出
+    let config = Config::load()?;
+    // 监听地址先校验，数据库还没连就能发现写错的地址
+    let addr = config.socket_addr()?;
+    db_access::set_retry_attempts(config.db_retry_attempts);
+    let database_url = config.database_url()?.to_string();
 
+    // 默认：连不上数据库 / 迁移失败 → 直接退出，由进程管理器重启
+    // start_without_db：先空着连接池启动（/livez 200，/readyz 和数据库接口 503），后台重连
+    let db = OnceLock::new();
+    if !config.start_without_db {
+        let db_pool = connect_and_migrate(&config, &database_url)
+            .await
+            .map_err(io::Error::other)?;
+        db.set(db_pool).unwrap();
+    }
+    let ready = db.get().is_some();
 
     // 创建应用的全局共享状态实例，并用 `web::Data::new()` 包装。
     // `web::Data<T>` 是 Actix Web 提供的线程安全共享容器（内部基于 Arc），
@@ -77,30 +124,150 @@ async fn main() -> io::Result<()> {
             // 初始化访问计数器为 0，并用 Mutex 包裹以支持多线程安全修改
             // ⚠️ 注意：此处字段名必须与 `state.rs` 中定义的完全一致（建议拼写为 visit_count）
             visit_count: Mutex::new(0),
+            // 默认把访问计数存进数据库（config.persistent_visits / PERSISTENT_VISITS）
+            persistent_visits: config.persistent_visits,
             //let v1 = vec![];        // 宏展开 = Vec::new() 一样快
             //let v2 = Vec::new();    // 直接空 Vec
             //Rust 里根本没有 vec[] 这种写法，只有vec![] 和 Vec::new()
             courses: Mutex::new(vec![]),
-            db: db_pool
+            db,
+            // 迁移已在上面跑完 → 一启动就是就绪状态；还没连上数据库 → 等后台任务置 true
+            ready: AtomicBool::new(ready),
+            // 配置了 audit_log（或 AUDIT_LOG）才记录审计日志
+            audit_log: config.audit_log.clone(),
+            // 记下启动时刻，/info 据此报告运行时长
+            started_at: Instant::now(),
         }
     );
 
+    // 游戏分数排行榜：和 AppState 一样在闭包外创建一次，所有 worker 共享同一份
+    let score_board = web::Data::new(scores::ScoreBoard::default());
+
+    // 数据库还没连上 → 后台连接，失败每隔 db_reconnect_secs 重试，连上后设置连接池并标记就绪
+    let reconnect_task = (!ready).then(|| {
+        println!("starting without database, connecting in background every {:?}", config.reconnect_interval());
+        actix_web::rt::spawn(reconnect_in_background(
+            share_data.clone(),
+            config.clone(),
+            database_url,
+        ))
+    });
+
+    // 信号任务里要用来把 ready 置 false；web::Data 克隆只是 Arc 计数 +1
+    let shutdown_state = share_data.clone();
+    let drain_period = config.drain_period();
+
     // 定义一个闭包 `app`，用于生成新的 `App` 实例。
     // 使用 `move ||` 表示该闭包“获取”外部变量 `share_data` 的所有权。
     // 因为服务器可能启动多个 worker 线程，每个线程都会调用此闭包一次，
     // 所以需要能多次克隆 `share_data`（`web::Data` 实现了 Clone）。
     let app = move || {
         App::new()
+            // 每个请求生成 X-Request-Id，错误 JSON 里也带上同一个 id
+            .wrap(middleware::from_fn(request_id::request_id_middleware))
             // 将共享状态 `share_data` 注册到应用中，使所有 handler 都能通过参数注入访问它
             .app_data(share_data.clone())
-            // 调用 `general_routes` 函数来批量注册路由（该函数应在 `routers.rs` 中定义）
-            .configure(general_routes)
-            .configure(course_routes)
+            .app_data(score_board.clone())
+            // 请求体 JSON：大小限制 + 出错时返回 MyErrorNew 风格的 JSON
+            .app_data(errors::json_config())
+            // 注册全部路由和兜底 404（见下面的 configure_app）
+            .configure(configure_app)
     };
 
+    // 启动前打印监听地址方便确认
+    println!("teacher-service listening on http://{}", addr);
+
     // 启动 HTTP 服务器：
     // 1. `HttpServer::new(app)`：传入上面定义的应用工厂闭包；
-    // 2. `.bind("127.0.0.1:3339")?`：尝试绑定到本地 3339 端口，若失败则返回错误（`?` 传播）；
-    // 3. `.run().await`：异步启动服务器并阻塞等待其结束（通常直到 Ctrl+C 终止）。
-    HttpServer::new(app).bind("127.0.0.1:3339")?.run().await
-}
\ No newline at end of file
+    // 2. `.disable_signals()`：关掉 actix 自带的信号处理，改由下面的 shutdown_signal 接管；
+    // 3. `.bind(addr)?`：绑定到配置的 bind_addr（默认 127.0.0.1:3339），失败时带上地址返回错误（`?` 传播）；
+    // 4. `.run()`：得到 Server（一个 Future），先拿 handle 再 await。
+    let server = HttpServer::new(app)
+        .disable_signals()
+        .bind(addr)
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to bind {}: {}", addr, e)))?
+        .run();
+
+    // 优雅停机：收到信号 → /readyz 变 503 → 等负载均衡摘流量 → 处理完在途请求后停止
+    let handle = server.handle();
+    actix_web::rt::spawn(async move {
+        shutdown_signal().await;
+        if let Some(task) = reconnect_task {
+            task.abort();
+        }
+        shutdown_state.ready.store(false, Ordering::SeqCst);
+        println!("shutdown signal received, draining for {:?}", drain_period);
+        actix_web::rt::time::sleep(drain_period).await;
+        handle.stop(true).await;
+    });
+
+    server.await
+}
+
+// ====== 连接数据库 ======
+// 建连接池 + 跑迁移（migrations/ 目录），空库也能直接跑起来
+async fn connect_and_migrate(config: &Config, database_url: &str) -> Result<PgPool, String> {
+    let db_pool = PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .acquire_timeout(config.acquire_timeout())
+        .connect(database_url)
+        .await
+        .map_err(|e| e.to_string())?;
+    db_access::migrate(&db_pool).await.map_err(|e| e.to_string())?;
+    Ok(db_pool)
+}
+
+// start_without_db 模式：启动时数据库不可用，后台一直重试直到连上
+// 连上后把连接池放进 AppState.db（OnceLock 只能 set 一次），再把 ready 置 true
+// 停机时信号任务会先 abort 这个任务，避免排空期间刚好连上又把 ready 改回 true
+async fn reconnect_in_background(state: web::Data<AppState>, config: Config, database_url: String) {
+    let interval = config.reconnect_interval();
+    loop {
+        match connect_and_migrate(&config, &database_url).await {
+            Ok(db_pool) => {
+                let _ = state.db.set(db_pool);
+                state.ready.store(true, Ordering::SeqCst);
+                println!("database connected, service is ready");
+                return;
+            }
+            Err(err) => {
+                eprintln!("database still unavailable: {} (retrying in {:?})", err, interval);
+                actix_web::rt::time::sleep(interval).await;
+            }
+        }
+    }
+}
+
+// ====== 优雅停机 ======
+// 标记未就绪后等待 Config.drain_period（默认 5 秒）：给 Kubernetes / 负载均衡留出几次 readiness 探测的窗口
+// 等待 Ctrl+C（本地开发）或 SIGTERM（kubectl delete / 滚动更新），哪个先来都算
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use actix_web::rt::signal::unix::{signal, SignalKind};
+        use std::future::Future;
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                // 没有直接依赖 tokio，用 poll_fn 手写一个“两个 future 谁先完成”
+                let mut ctrl_c = std::pin::pin!(actix_web::rt::signal::ctrl_c());
+                let mut term = std::pin::pin!(term.recv());
+                std::future::poll_fn(|cx| {
+                    if ctrl_c.as_mut().poll(cx).is_ready() || term.as_mut().poll(cx).is_ready() {
+                        std::task::Poll::Ready(())
+                    } else {
+                        std::task::Poll::Pending
+                    }
+                })
+                .await;
+            }
+            // 注册 SIGTERM 失败时退回只监听 Ctrl+C
+            Err(_) => {
+                let _ = actix_web::rt::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = actix_web::rt::signal::ctrl_c().await;
+    }
+}
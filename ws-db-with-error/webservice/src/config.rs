@@ -0,0 +1,291 @@
+// ========== 1. 服务配置（配置文件 + 环境变量覆盖） ==========
+// 之前每个参数各读各的环境变量（BIND_ADDR、DATABASE_URL、AUDIT_LOG、DB_RETRY_ATTEMPTS …），
+// 散落在 main / audit / db_access 里，不好管理。现在统一收进 Config：
+//   1) 默认值：不写配置文件也能跑（只有 database_url 必须提供）
+//   2) 配置文件：--config <path> 或 CONFIG_FILE 指定；都没给就找当前目录的 config.toml / config.json
+//      按扩展名选格式：.toml → TOML，其它 → JSON；文件里只写想改的字段，其余用默认值
+//   3) 环境变量：优先级最高，覆盖配置文件（容器 / CI 里改一个参数不用改文件）
+// 示例见 config.example.toml
+use serde::Deserialize;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// 1.1 没有显式指定时，按顺序在当前目录找这些文件
+const DEFAULT_FILES: [&str; 2] = ["config.toml", "config.json"];
+
+// 1.2 配置项；#[serde(default)]：文件里缺的字段用 Default 的值
+//      deny_unknown_fields：字段名拼错直接报错，而不是悄悄被忽略
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub bind_addr: String,                // 环境变量 BIND_ADDR
+    pub database_url: Option<String>,     // DATABASE_URL（必填，文件或环境变量二选一）
+    pub max_connections: u32,             // DB_MAX_CONNECTIONS：连接池最大连接数
+    pub acquire_timeout_secs: u64,        // DB_ACQUIRE_TIMEOUT_SECS：从池里拿连接最多等多久
+    pub db_retry_attempts: u32,           // DB_RETRY_ATTEMPTS：瞬时错误最多执行几次
+    pub audit_log: Option<PathBuf>,       // AUDIT_LOG：审计日志文件，不设置就不记录
+    pub drain_period_secs: u64,           // DRAIN_PERIOD_SECS：停机前摘流量的等待时间
+    pub persistent_visits: bool,          // PERSISTENT_VISITS：/health 访问计数存数据库（重启不清零）
+    pub start_without_db: bool,           // START_WITHOUT_DB：数据库连不上也先启动，后台重连
+    pub db_reconnect_secs: u64,           // DB_RECONNECT_SECS：后台重连的间隔
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bind_addr: "127.0.0.1:3339".to_string(),
+            database_url: None,
+            max_connections: 10, // 与 sqlx 默认值一致
+            acquire_timeout_secs: 30,
+            db_retry_attempts: crate::db_access::DEFAULT_RETRY_ATTEMPTS,
+            audit_log: None,
+            drain_period_secs: 5,
+            persistent_visits: true,
+            start_without_db: false, // 默认连不上数据库就退出（和原来一样）
+            db_reconnect_secs: 5,
+        }
+    }
+}
+
+// ========== 2. 加载 ==========
+impl Config {
+    // 2.1 main 里调用：读命令行参数和真实环境变量
+    pub fn load() -> io::Result<Config> {
+        let env = |key: &str| std::env::var(key).ok();
+        let path = config_path(std::env::args().skip(1), &env);
+        Config::from_sources(path.as_deref(), &env)
+    }
+
+    // 2.2 默认值 → 配置文件 → 环境变量，后面的覆盖前面的
+    //      env 以闭包传入：测试里用 HashMap 模拟，不用改真实环境变量
+    pub fn from_sources(path: Option<&Path>, env: &dyn Fn(&str) -> Option<String>) -> io::Result<Config> {
+        let mut config = match path {
+            Some(path) => Config::from_file(path)?,
+            None => Config::default(),
+        };
+        config.apply_env(env)?;
+        Ok(config)
+    }
+
+    // 2.3 读文件并按扩展名解析；错误信息带上文件路径
+    fn from_file(path: &Path) -> io::Result<Config> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("cannot read config {}: {}", path.display(), e)))?;
+        let parsed = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        };
+        parsed.map_err(|e| invalid(format!("invalid config {}: {}", path.display(), e)))
+    }
+
+    // 2.4 环境变量覆盖；空字符串当作没设置（和原来的 AUDIT_LOG 行为一致）
+    //      数字写错直接报错：启动时就发现，而不是悄悄用回默认值
+    fn apply_env(&mut self, env: &dyn Fn(&str) -> Option<String>) -> io::Result<()> {
+        let get = |key: &str| env(key).filter(|v| !v.is_empty());
+
+        if let Some(v) = get("BIND_ADDR") {
+            self.bind_addr = v;
+        }
+        if let Some(v) = get("DATABASE_URL") {
+            self.database_url = Some(v);
+        }
+        if let Some(v) = get("DB_MAX_CONNECTIONS") {
+            self.max_connections = parse_env("DB_MAX_CONNECTIONS", &v)?;
+        }
+        if let Some(v) = get("DB_ACQUIRE_TIMEOUT_SECS") {
+            self.acquire_timeout_secs = parse_env("DB_ACQUIRE_TIMEOUT_SECS", &v)?;
+        }
+        if let Some(v) = get("DB_RETRY_ATTEMPTS") {
+            self.db_retry_attempts = parse_env("DB_RETRY_ATTEMPTS", &v)?;
+        }
+        if let Some(v) = get("AUDIT_LOG") {
+            self.audit_log = Some(PathBuf::from(v));
+        }
+        if let Some(v) = get("DRAIN_PERIOD_SECS") {
+            self.drain_period_secs = parse_env("DRAIN_PERIOD_SECS", &v)?;
+        }
+        if let Some(v) = get("PERSISTENT_VISITS") {
+            self.persistent_visits = parse_env("PERSISTENT_VISITS", &v)?;
+        }
+        if let Some(v) = get("START_WITHOUT_DB") {
+            self.start_without_db = parse_env("START_WITHOUT_DB", &v)?;
+        }
+        if let Some(v) = get("DB_RECONNECT_SECS") {
+            self.db_reconnect_secs = parse_env("DB_RECONNECT_SECS", &v)?;
+        }
+        Ok(())
+    }
+
+    // ========== 3. 取值（带校验 / 换算） ==========
+    // 3.1 先 parse 成 SocketAddr：写错了启动时就给出清楚的错误，而不是等 bind 时才失败
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        self.bind_addr.parse().map_err(|e| {
+            invalid(format!(
+                "invalid bind_addr {:?}: {} (expected ip:port, e.g. 0.0.0.0:8080)",
+                self.bind_addr, e
+            ))
+        })
+    }
+
+    // 3.2 数据库地址是唯一没有默认值的配置
+    pub fn database_url(&self) -> io::Result<&str> {
+        self.database_url
+            .as_deref()
+            .ok_or_else(|| invalid("database_url not set (config file or DATABASE_URL)".to_string()))
+    }
+
+    pub fn acquire_timeout(&self) -> Duration {
+        Duration::from_secs(self.acquire_timeout_secs)
+    }
+
+    pub fn drain_period(&self) -> Duration {
+        Duration::from_secs(self.drain_period_secs)
+    }
+
+    pub fn reconnect_interval(&self) -> Duration {
+        Duration::from_secs(self.db_reconnect_secs)
+    }
+}
+
+// ========== 4. 辅助函数 ==========
+// 4.1 配置文件路径：--config <path> / --config=<path> → CONFIG_FILE → 当前目录的默认文件 → 没有
+fn config_path(
+    mut args: impl Iterator<Item = String>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+    if let Some(path) = env("CONFIG_FILE").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    DEFAULT_FILES.iter().map(PathBuf::from).find(|path| path.is_file())
+}
+
+fn parse_env<T: std::str::FromStr>(key: &str, value: &str) -> io::Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| invalid(format!("invalid {} {:?}: {}", key, value, e)))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+// ========== 5. 单元测试 ==========
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    // 5.1 写一个临时配置文件，返回路径（文件名带进程号，避免并行测试互相覆盖）
+    fn write_temp(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn env_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| map.get(key).cloned()
+    }
+
+    // 5.2 没有配置文件、没有环境变量 → 全是默认值，只是缺数据库地址
+    #[test]
+    fn defaults_when_no_file() {
+        let config = Config::from_sources(None, &env_from(&[])).unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.socket_addr().unwrap().port(), 3339);
+        assert!(config.database_url().is_err());
+    }
+
+    // 5.3 JSON 和 TOML 都能解析；没写的字段保持默认
+    #[test]
+    fn parses_json_and_toml_files() {
+        let json = write_temp(
+            "config.json",
+            r#"{"bind_addr": "0.0.0.0:8080", "database_url": "postgres://json", "max_connections": 4}"#,
+        );
+        let toml = write_temp(
+            "config.toml",
+            "bind_addr = \"0.0.0.0:9090\"\ndatabase_url = \"postgres://toml\"\naudit_log = \"audit.log\"\n",
+        );
+
+        let from_json = Config::from_sources(Some(&json), &env_from(&[])).unwrap();
+        assert_eq!(from_json.bind_addr, "0.0.0.0:8080");
+        assert_eq!(from_json.database_url().unwrap(), "postgres://json");
+        assert_eq!(from_json.max_connections, 4);
+        assert_eq!(from_json.drain_period(), Duration::from_secs(5));
+
+        let from_toml = Config::from_sources(Some(&toml), &env_from(&[])).unwrap();
+        assert_eq!(from_toml.socket_addr().unwrap().port(), 9090);
+        assert_eq!(from_toml.audit_log, Some(PathBuf::from("audit.log")));
+        assert_eq!(from_toml.max_connections, 10);
+
+        std::fs::remove_file(json).ok();
+        std::fs::remove_file(toml).ok();
+    }
+
+    // 5.4 环境变量 > 配置文件 > 默认值；空环境变量不覆盖
+    #[test]
+    fn env_overrides_take_precedence() {
+        let path = write_temp(
+            "override.json",
+            r#"{"bind_addr": "0.0.0.0:8080", "database_url": "postgres://file", "db_retry_attempts": 7}"#,
+        );
+        let env = env_from(&[
+            ("BIND_ADDR", "127.0.0.1:4000"),
+            ("DB_MAX_CONNECTIONS", "2"),
+            ("DATABASE_URL", ""),
+            ("PERSISTENT_VISITS", "false"),
+            ("START_WITHOUT_DB", "true"),
+        ]);
+        let config = Config::from_sources(Some(&path), &env).unwrap();
+        std::fs::remove_file(path).ok();
+
+        assert_eq!(config.bind_addr, "127.0.0.1:4000"); // 环境变量覆盖文件
+        assert_eq!(config.max_connections, 2); // 环境变量覆盖默认值
+        assert_eq!(config.db_retry_attempts, 7); // 文件覆盖默认值
+        assert_eq!(config.database_url().unwrap(), "postgres://file"); // 空值不覆盖
+        assert!(!config.persistent_visits); // 布尔值用 true / false
+        assert!(config.start_without_db);
+        assert_eq!(config.reconnect_interval(), Duration::from_secs(5));
+    }
+
+    // 5.5 配置写错 → 启动时报错，信息里指出是哪一项
+    #[test]
+    fn rejects_bad_values() {
+        let err = Config::from_sources(None, &env_from(&[("DB_MAX_CONNECTIONS", "many")])).unwrap_err();
+        assert!(err.to_string().contains("DB_MAX_CONNECTIONS"));
+
+        let path = write_temp("typo.json", r#"{"bind_adr": "0.0.0.0:1"}"#);
+        let err = Config::from_sources(Some(&path), &env_from(&[])).unwrap_err();
+        std::fs::remove_file(path).ok();
+        assert!(err.to_string().contains("bind_adr"));
+
+        let missing = Path::new("/definitely/not/here.toml");
+        assert!(Config::from_sources(Some(missing), &env_from(&[])).is_err());
+    }
+
+    // 5.6 路径来源的优先级：--config 参数 > CONFIG_FILE
+    #[test]
+    fn config_path_prefers_command_line() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        let env = env_from(&[("CONFIG_FILE", "from-env.json")]);
+
+        assert_eq!(config_path(args(&["--config", "a.toml"]), &env), Some(PathBuf::from("a.toml")));
+        assert_eq!(config_path(args(&["--config=b.json"]), &env), Some(PathBuf::from("b.json")));
+        assert_eq!(config_path(args(&[]), &env), Some(PathBuf::from("from-env.json")));
+    }
+}
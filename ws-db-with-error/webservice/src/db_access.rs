@@ -2,23 +2,166 @@
 use super::errors::MyErrorNew;
 use super::models::*; // 引入本地定义的 Course 结构体
 use sqlx::postgres::PgPool; // PostgreSQL 异步连接池（比单连接快 10×）
+use std::env;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
 
-// ========== 2. 根据老师 ID 查所有课程 ==========
+// ========== 1.1 瞬时错误重试 ==========
+// 重试次数默认 3 次；服务启动时由 Config（db_retry_attempts / DB_RETRY_ATTEMPTS）设置
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+// 第一次重试前等待 50ms，之后每次翻倍（50 → 100 → 200 …）
+const RETRY_BASE_DELAY_MS: u64 = 50;
+
+// 启动时设置一次，之后只读；OnceLock 保证多个 worker 线程读到同一个值
+static RETRY_ATTEMPTS: OnceLock<u32> = OnceLock::new();
+
+// main 读完配置后调用；至少执行 1 次
+pub fn set_retry_attempts(attempts: u32) {
+    let _ = RETRY_ATTEMPTS.set(attempts.max(1));
+}
+
+// 读取重试次数：启动时设置过就用配置的值；
+// 没设置过（比如单元测试）才直接读环境变量，没配或配错都回落到默认值
+pub fn retry_attempts() -> u32 {
+    if let Some(attempts) = RETRY_ATTEMPTS.get() {
+        return *attempts;
+    }
+    env::var("DB_RETRY_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_RETRY_ATTEMPTS)
+}
+
+// 只有 **连接/连接池** 类错误才值得重试；
+// RowNotFound、SQL 语法错误等 **重试也不会变好**，直接返回
+fn is_transient(err: &sqlx::Error) -> bool {
+    matches!(
+        err,
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed
+    )
+}
+
+// 写操作（INSERT 等）只能在 **语句还没发出去** 时重试：
+// Io 错误可能发生在服务器已经提交、RETURNING 还没传回来的时候，重试就会多插一行；
+// PoolTimedOut 是连接都没拿到，语句肯定没执行过
+fn is_unsent(err: &sqlx::Error) -> bool {
+    matches!(err, sqlx::Error::PoolTimedOut)
+}
+
+// 把一次数据库操作 f 最多执行 attempts 次，瞬时错误之间做 **指数退避**
+// f 是 FnMut：每次调用都重新生成一个 Future（Future 只能 await 一次）
+// 只用于 **幂等** 的读操作（SELECT）；写操作用 with_write_retry
+pub async fn with_retry<F, Fut, T>(attempts: u32, f: F) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    retry_when(attempts, is_transient, f).await
+}
+
+// 写操作的重试：只重试 is_unsent 的错误，其余（包括 Io）直接返回
+pub async fn with_write_retry<F, Fut, T>(attempts: u32, f: F) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    retry_when(attempts, is_unsent, f).await
+}
+
+// 重试循环本身：retryable 决定哪些错误值得再试一次
+async fn retry_when<F, Fut, T>(
+    attempts: u32,
+    retryable: fn(&sqlx::Error) -> bool,
+    mut f: F,
+) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Err(err) if attempt < attempts && retryable(&err) => {
+                let delay = RETRY_BASE_DELAY_MS << (attempt - 1);
+                println!("数据库瞬时错误（第 {} 次）: {}，{}ms 后重试", attempt, err, delay);
+                actix_web::rt::time::sleep(Duration::from_millis(delay)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+// ========== 1.2 启动时建表（数据库迁移） ==========
+// sqlx::migrate!() 在 **编译期** 把 migrations/ 目录下的 SQL 打包进二进制，
+// 运行时按文件名顺序执行，已执行过的记录在 _sqlx_migrations 表里，**重复启动不会重复建表**。
+// 期望的表结构 rust_test1.course：
+//   id          int4       GENERATED ALWAYS 自增主键
+//   teacher_id  int4       所属老师（可空，默认 0）
+//   name        varchar    课程名（可空，默认 ''）
+//   time        timestamp  创建时间（可空，默认 now()）
+// 以及 rust_test1.counters（name 主键 + value 计数，见 1.4）
+pub async fn migrate(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!().run(pool).await
+}
+
+// ========== 1.3 就绪检查：数据库可达 + 迁移已全部执行 ==========
+// _sqlx_migrations 里成功执行的条数 ≥ 二进制里打包的迁移数 → 表结构是新的
+// 连不上库 / 表不存在都会返回 Err，调用方统一当作“未就绪”
+pub async fn migrations_applied(pool: &PgPool) -> Result<bool, sqlx::Error> {
+    let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await?;
+    Ok(applied as usize >= sqlx::migrate!().iter().count())
+}
+
+// ========== 1.4 持久化访问计数 ==========
+// 一条 UPDATE ... RETURNING 完成"加一并读出新值"：数据库行锁保证并发请求不会丢计数
+// 返回 Ok(None)：表在但没有 visits 这一行；表不存在（迁移没跑）→ Err，由调用方退回内存计数
+// 用运行时 query_scalar 而不是 query! 宏：编译期不要求数据库里已经有这张表
+pub async fn increment_visit_counter_db(pool: &PgPool) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar("UPDATE rust_test1.counters SET value = value + 1 WHERE name = 'visits' RETURNING value")
+        .fetch_optional(pool)
+        .await
+}
+
+// ========== 2. 根据老师 ID 分页查课程 ==========
 pub async fn get_courses_for_teacher_db(
     pool: &PgPool,   // 2.1 **借用连接池** → 不转移所有权，**零成本**
     teacher_id: i32, // 2.2 **i32** ↔ SQL **integer**，**类型必须对**
+    q: Option<&str>, // 2.2.1 课程名关键字（ILIKE，不区分大小写），None = 不过滤
+    sort: &str,      // 2.2.2 排序字段：id / name / time（调用方已校验）
+    limit: i64,      // 2.2.3 每页条数 → SQL LIMIT（BIGINT → i64）
+    offset: i64,     // 2.2.4 跳过条数 → SQL OFFSET
 ) -> Result<Vec<Course>, MyErrorNew> {
     // 2.3 返回 **Vec<Course>** → **零成本返回**（只是指针移动）
 
     // 2.4 **编译期检查 SQL**（sqlx::query! 宏）
     //     **占位符 $1** → PostgreSQL 风格；**参数类型必须对**（i32）
-    let rows = sqlx::query!(
-        r#"SELECT * FROM rust_test1.course WHERE teacher_id = $1"#,
-        teacher_id
-    )
-    .fetch_all(pool)                 // 2.5 **异步取全部行** → **返回 Vec<PgRow>**
+    //     ORDER BY 列名不能用占位符 → 用 CASE 按 $3 选择排序列，不匹配的 CASE 全是 NULL、不影响顺序
+    //     最后再按 id → 分页必须有稳定顺序，否则翻页可能重复/漏行
+    let rows = with_retry(retry_attempts(), || {
+        sqlx::query!(
+            r#"SELECT * FROM rust_test1.course
+               WHERE teacher_id = $1
+                 AND ($2::text IS NULL OR name ILIKE '%' || $2 || '%')
+               ORDER BY
+                 CASE WHEN $3 = 'name' THEN name END,
+                 CASE WHEN $3 = 'time' THEN time END NULLS LAST,
+                 id
+               LIMIT $4 OFFSET $5"#,
+            teacher_id,
+            q,
+            sort,
+            limit,
+            offset
+        )
+        .fetch_all(pool) // 2.5 **异步取全部行** → **返回 Vec<PgRow>**
+    })
     .await                            // 2.6 **等待 IO 完成** → **不会阻塞线程**
-    ?; // 2.7 **简化错误**（测试可接受，生产用 ?）
+    ?; // 2.7 **? 把 sqlx::Error 转成 MyErrorNew 返回**（From<sqlx::Error> 在 errors.rs）
 
     // 2.8 **Vec<Course>** 准备装结构体（零成本，只是指针数组）
     let courses:Vec<Course> = rows
@@ -32,10 +175,30 @@ pub async fn get_courses_for_teacher_db(
         })
         .collect(); // 2.14 **Vec<Course>** → **零成本收集**
 
-    match courses.len() {
-        0 => Err(MyErrorNew::NotFound("course not found ".into())),
-        _ => Ok(courses),
-    }
+    // 2.15 某一页为空不算错误（可能只是翻过了头）→ “老师没有课程”由调用方结合总数判断
+    Ok(courses)
+}
+
+// ========== 2.16 统计老师的课程总数（分页用） ==========
+// q 与分页查询的过滤条件一致，None = 老师的全部课程
+pub async fn count_courses_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    q: Option<&str>,
+) -> Result<i64, MyErrorNew> {
+    // `"total!"` → COUNT(*) 不会是 NULL，直接生成 i64
+    let row = with_retry(retry_attempts(), || {
+        sqlx::query!(
+            r#"SELECT COUNT(*) AS "total!" FROM rust_test1.course
+               WHERE teacher_id = $1
+                 AND ($2::text IS NULL OR name ILIKE '%' || $2 || '%')"#,
+            teacher_id,
+            q
+        )
+        .fetch_one(pool)
+    })
+    .await?;
+    Ok(row.total)
 }
 
 // ========== 3. 根据老师 ID + 课程 ID 查单条课程 ==========
@@ -43,7 +206,7 @@ pub async fn get_course_detail_db(
     pool: &PgPool,   // 3.1 **借用连接池** → **零成本**
     teacher_id: i32, // 3.2 **i32 ↔ integer**
     course_id: i32,  // 3.3 **i32 ↔ integer**
-) -> Course {
+) -> Result<Course, MyErrorNew> {
     // 3.4 返回 **单个 Course** → **零成本返回**
 
     // 3.5 **编译期检查 SQL** → **双条件查询**
@@ -54,41 +217,338 @@ pub async fn get_course_detail_db(
     )
     .fetch_one(pool) // 3.6 **异步取一行** → **返回 PgRow**
     .await
-    .unwrap(); // 3.7 **unwrap()** → **测试可接受，生产用 ?**
+    // 3.7 **没有这一行 → NotFound（404）**；其它错误走 From<sqlx::Error>
+    .map_err(|err| match err {
+        sqlx::Error::RowNotFound => MyErrorNew::NotFound(format!(
+            "course {} of teacher {} not found",
+            course_id, teacher_id
+        )),
+        other => other.into(),
+    })?;
 
     // 3.8 **直接构造 Course** → **零成本映射**
-    Course {
+    Ok(Course {
         id: row.id,
         teacher_id: row.teacher_id.unwrap_or(0),
         name: row.name.clone().unwrap_or_default(),
         time: row.time,
-    }
+    })
 }
 
 // ========== 4. 插入新课程并返回刚插入的行 ==========
 pub async fn post_new_course_db(
     pool: &PgPool,      // 4.1 **借用连接池** → **零成本**
     new_course: Course, // 4.2 **Course 整体 move 进来** → **零成本（只是指针移动）**
-) -> Course {
+) -> Result<Course, MyErrorNew> {
     // 4.3 返回 **刚插入的完整行** → **零成本返回**
 
     // 4.4 **编译期检查 SQL** → **INSERT … VALUES ($1,$2)**
     //     **不插入 id**：id 是 GENERATED ALWAYS（自增列），由数据库生成
     //     **fetch_one()** → **PostgreSQL 支持 RETURNING** → **返回刚插入的行**
-    let row = sqlx::query!(
-        r#"INSERT INTO rust_test1.course (teacher_id, name) VALUES ($1, $2) RETURNING *"#,
-        new_course.teacher_id,
-        new_course.name
-    )
-    .fetch_one(pool) // 4.5 **RETURNING * → 返回刚插入的行**
-    .await
-    .unwrap(); // 4.6 **unwrap()** → **测试可接受**
+    //     with_write_retry：只在连接池拿不到连接（语句还没发出去）时重试；
+    //     连接中途断开（Io）不重试：服务器可能已经提交，重试会多插一行
+    let row = with_write_retry(retry_attempts(), || {
+        sqlx::query!(
+            r#"INSERT INTO rust_test1.course (teacher_id, name) VALUES ($1, $2) RETURNING *"#,
+            new_course.teacher_id,
+            new_course.name
+        )
+        .fetch_one(pool) // 4.5 **RETURNING * → 返回刚插入的行**
+    })
+    .await?; // 4.6 **重试用完 / 违反约束 → ? 转成 MyErrorNew 返回**（连接池超时 → 503）
 
     // 4.7 **直接构造返回的 Course** → **零成本映射**
-    Course {
+    Ok(Course {
         id: row.id,
         teacher_id: row.teacher_id.unwrap_or(0),
         name: row.name.clone().unwrap_or_default(),
         time: row.time,
+    })
+}
+
+// ========== 4.5 按老师统计课程数 ==========
+pub async fn get_teacher_summary_db(pool: &PgPool) -> Result<Vec<TeacherSummary>, MyErrorNew> {
+    // 4.5.1 **GROUP BY 交给数据库** → 只传回每个老师一行
+    //       `"teacher_id!"` / `"course_count!"` → 告诉 sqlx 这两列**不会是 NULL**，
+    //       直接生成 i32 / i64 而不是 Option
+    //       ORDER BY teacher_id → 结果按老师 ID 升序
+    let rows = with_retry(retry_attempts(), || {
+        sqlx::query!(
+            r#"SELECT teacher_id AS "teacher_id!", COUNT(*) AS "course_count!"
+               FROM rust_test1.course
+               WHERE teacher_id IS NOT NULL
+               GROUP BY teacher_id
+               ORDER BY teacher_id"#
+        )
+        .fetch_all(pool)
+    })
+    .await?;
+
+    // 4.5.2 **逐行映射**成 TeacherSummary
+    Ok(rows
+        .into_iter()
+        .map(|r| TeacherSummary {
+            teacher_id: r.teacher_id,
+            course_count: r.course_count,
+        })
+        .collect())
+}
+
+// ========== 4.6 删除老师（感知级联） ==========
+// 目前还没有单独的 teacher 表，老师只以 course.teacher_id 的形式存在：
+// - 名下没有课程 → 视为老师不存在 → NotFound
+// - 名下还有课程且 cascade = false → **拒绝**（ValidationError），避免留下孤儿课程
+// - cascade = true → 在**同一个事务**里删除全部课程，返回被删除的课程（审计日志要用）
+pub async fn delete_teacher_db(
+    pool: &PgPool,
+    teacher_id: i32,
+    cascade: bool,
+) -> Result<Vec<Course>, MyErrorNew> {
+    // 4.6.1 **开启事务** → 计数和删除看到的是同一份数据，中途出错自动回滚（tx 被 drop）
+    let mut tx = pool.begin().await?;
+
+    // 4.6.2 &mut *tx → 把事务当作执行器（Executor）传给 query
+    let row = sqlx::query!(
+        r#"SELECT COUNT(*) AS "total!" FROM rust_test1.course WHERE teacher_id = $1"#,
+        teacher_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    match (row.total, cascade) {
+        (0, _) => Err(MyErrorNew::NotFound(format!("teacher {} not found", teacher_id))),
+        (n, false) => Err(MyErrorNew::ValidationError(format!(
+            "teacher {} still has {} courses, use ?cascade=true to delete them",
+            teacher_id, n
+        ))),
+        (_, true) => {
+            // 4.6.3 先删课程（以后有 teacher 表时，再在这里删老师本身）
+            //       RETURNING * → 把删掉的行带回来
+            let rows = sqlx::query!(
+                r#"DELETE FROM rust_test1.course WHERE teacher_id = $1 RETURNING *"#,
+                teacher_id
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+            let deleted = rows
+                .into_iter()
+                .map(|row| Course {
+                    id: row.id,
+                    teacher_id: row.teacher_id.unwrap_or(0),
+                    name: row.name.unwrap_or_default(),
+                    time: row.time,
+                })
+                .collect();
+
+            // 4.6.4 **提交事务** → 到这里删除才真正生效
+            tx.commit().await?;
+            Ok(deleted)
+        }
+    }
+}
+
+// ========== 4.7 最近创建的课程（跨所有老师） ==========
+pub async fn get_recent_courses_db(pool: &PgPool, limit: i64) -> Result<Vec<Course>, sqlx::Error> {
+    // 4.7.1 ORDER BY time DESC → 最新的在前；NULLS LAST → 没有时间的课程排到最后
+    //       （PostgreSQL 的 DESC 默认把 NULL 放最前面，必须显式写）
+    let rows = with_retry(retry_attempts(), || {
+        sqlx::query!(
+            r#"SELECT * FROM rust_test1.course ORDER BY time DESC NULLS LAST LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+    })
+    .await?;
+
+    // 4.7.2 **逐行映射**成 Course（和第 2 节一致）
+    Ok(rows
+        .iter()
+        .map(|r| Course {
+            id: r.id,
+            teacher_id: r.teacher_id.unwrap_or(0),
+            name: r.name.clone().unwrap_or_default(),
+            time: r.time,
+        })
+        .collect())
+}
+
+// ========== 5. 单元测试 ==========
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::maybe_pool; // 连不上数据库 → 跳过 DB 测试
+    use std::cell::Cell;
+
+    // 5.1 前两次返回连接池超时，第三次成功 → 应该拿到结果，共调用 3 次
+    #[actix_web::test]
+    async fn with_retry_recovers_after_transient_errors() {
+        let calls = Cell::new(0);
+        let result = with_retry(3, || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move {
+                if n < 3 {
+                    Err(sqlx::Error::PoolTimedOut)
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.get(), 3);
+    }
+
+    // 5.2 次数用完仍失败 → 返回最后一次的错误
+    #[actix_web::test]
+    async fn with_retry_gives_up_after_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<i32, sqlx::Error> = with_retry(2, || {
+            calls.set(calls.get() + 1);
+            async { Err(sqlx::Error::PoolTimedOut) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(calls.get(), 2);
+    }
+
+    // 5.3 RowNotFound 不是瞬时错误 → 不重试
+    #[actix_web::test]
+    async fn with_retry_does_not_retry_row_not_found() {
+        let calls = Cell::new(0);
+        let result: Result<i32, sqlx::Error> = with_retry(3, || {
+            calls.set(calls.get() + 1);
+            async { Err(sqlx::Error::RowNotFound) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(calls.get(), 1);
+    }
+
+    // 5.3.1 写操作：Io 错误不重试（语句可能已经执行），PoolTimedOut 照常重试
+    #[actix_web::test]
+    async fn with_write_retry_only_retries_unsent_errors() {
+        let calls = Cell::new(0);
+        let result: Result<i32, sqlx::Error> = with_write_retry(3, || {
+            calls.set(calls.get() + 1);
+            async { Err(sqlx::Error::Io(std::io::ErrorKind::ConnectionReset.into())) }
+        })
+        .await;
+        assert!(matches!(result, Err(sqlx::Error::Io(_))));
+        assert_eq!(calls.get(), 1);
+
+        calls.set(0);
+        let result: Result<i32, sqlx::Error> = with_write_retry(3, || {
+            calls.set(calls.get() + 1);
+            async { Err(sqlx::Error::PoolTimedOut) }
+        })
+        .await;
+        assert!(matches!(result, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(calls.get(), 3);
+    }
+
+    // 5.4 迁移可以重复执行（第二次什么都不做），执行后课程表可查询
+    #[actix_web::test]
+    async fn migrate_is_idempotent() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        migrate(&db_pool).await.unwrap();
+        migrate(&db_pool).await.unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rust_test1.course")
+            .fetch_one(&db_pool)
+            .await
+            .unwrap();
+        assert!(count >= 0);
+    }
+
+    // 5.5 三门课分属两个老师 → 分组计数正确，且按 teacher_id 升序
+    #[actix_web::test]
+    async fn teacher_summary_groups_by_teacher() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        // 用两个专用的老师 ID，先清掉上次运行留下的数据，保证计数可重复
+        let (first, second) = (842_001, 842_002);
+        sqlx::query("DELETE FROM rust_test1.course WHERE teacher_id = ANY($1)")
+            .bind(vec![first, second])
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        for (teacher_id, name) in [(second, "a"), (first, "b"), (second, "c")] {
+            post_new_course_db(
+                &db_pool,
+                Course {
+                    id: 0,
+                    teacher_id,
+                    name: name.into(),
+                    time: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let summary: Vec<TeacherSummary> = get_teacher_summary_db(&db_pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.teacher_id == first || s.teacher_id == second)
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                TeacherSummary { teacher_id: first, course_count: 1 },
+                TeacherSummary { teacher_id: second, course_count: 2 },
+            ]
+        );
+
+        let ids = get_teacher_summary_db(&db_pool).await.unwrap();
+        assert!(ids.windows(2).all(|w| w[0].teacher_id < w[1].teacher_id));
+    }
+
+    // 5.6 两门课时间不同（放在遥远的未来，保证排在所有现有数据前面）→ 新的在前
+    #[actix_web::test]
+    async fn recent_courses_are_newest_first() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        // 专用老师 ID，先清掉上次运行留下的数据
+        let teacher_id = 866_001;
+        sqlx::query("DELETE FROM rust_test1.course WHERE teacher_id = $1")
+            .bind(teacher_id)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        for (name, time) in [("older", "2100-01-01 00:00:00"), ("newer", "2100-01-02 00:00:00")] {
+            sqlx::query(
+                "INSERT INTO rust_test1.course (teacher_id, name, time) VALUES ($1, $2, $3::timestamp)",
+            )
+            .bind(teacher_id)
+            .bind(name)
+            .bind(time)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        }
+
+        let recent = get_recent_courses_db(&db_pool, 2).await.unwrap();
+        let names: Vec<&str> = recent.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["newer", "older"]);
+
+        // 整体按时间降序，NULL 在最后
+        let all = get_recent_courses_db(&db_pool, 100).await.unwrap();
+        assert!(all.windows(2).all(|w| match (w[0].time, w[1].time) {
+            (Some(a), Some(b)) => a >= b,
+            (_, None) => true,
+            (None, Some(_)) => false,
+        }));
     }
 }
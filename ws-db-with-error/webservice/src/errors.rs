@@ -1,4 +1,4 @@
-use actix_web::{HttpResponse, Result, error, http::StatusCode};
+use actix_web::{HttpResponse, Result, error, http::{StatusCode, header}, web};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -8,12 +8,18 @@ pub enum MyErrorNew {
     DbError(String),    // 数据库错误
     ActixError(String), // 框架错误
     NotFound(String),   // 资源未找到
+    ValidationError(String), // 请求不合法（业务规则不允许）
+    PayloadTooLarge(String), // 请求体超过大小限制
+    ServiceBusy(String),     // 连接池耗尽 / 暂时不可用，稍后重试
 }
 
 // ========== 2. HTTP 响应结构体（可序列化） ==========
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MyErrorNewResponse {
     error_message: String, // 人类可读的错误信息
+    // 请求 ID（由 request_id 中间件填入），客户端报错时可以报上它；没有就不输出这个字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 // ========== 3. impl MyErrorNew → 自定义方法 ==========
@@ -24,8 +30,27 @@ impl MyErrorNew {
             MyErrorNew::DbError(msg) => format!("数据库错误: {}", msg),
             MyErrorNew::ActixError(msg) => format!("框架错误: {}", msg),
             MyErrorNew::NotFound(msg) => format!("资源未找到: {}", msg),
+            MyErrorNew::ValidationError(msg) => format!("请求不合法: {}", msg),
+            MyErrorNew::PayloadTooLarge(msg) => format!("请求体过大: {}", msg),
+            MyErrorNew::ServiceBusy(msg) => format!("服务繁忙，请稍后重试: {}", msg),
         }
     }
+
+    // 3.2 构造 JSON 错误响应；request_id 由中间件传入（见 request_id.rs），
+    //     ResponseError::error_response 拿不到请求，只能传 None
+    pub fn to_http_response(&self, request_id: Option<String>) -> HttpResponse {
+        let resp = MyErrorNewResponse {
+            error_message: self.error_response(), // 调用 3.1 的人类可读信息
+            request_id,
+        };
+        // build(status_code()) + json() → 返回 JSON + 状态码
+        let mut builder = HttpResponse::build(error::ResponseError::status_code(self));
+        // 503 带上 Retry-After（秒），客户端 / 网关据此退避重试
+        if let MyErrorNew::ServiceBusy(_) = self {
+            builder.insert_header((header::RETRY_AFTER, RETRY_AFTER_SECS.to_string()));
+        }
+        builder.json(resp)
+    }
 }
 
 // ========== 4. impl Display → 人类可读字符串 ==========
@@ -44,16 +69,15 @@ impl actix_web::error::ResponseError for MyErrorNew {
             MyErrorNew::DbError(_) => StatusCode::INTERNAL_SERVER_ERROR, // 500
             MyErrorNew::ActixError(_) => StatusCode::INTERNAL_SERVER_ERROR, // 500
             MyErrorNew::NotFound(_) => StatusCode::NOT_FOUND,            // 404
+            MyErrorNew::ValidationError(_) => StatusCode::BAD_REQUEST,   // 400
+            MyErrorNew::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE, // 413
+            MyErrorNew::ServiceBusy(_) => StatusCode::SERVICE_UNAVAILABLE, // 503
         }
     }
 
     // 5.2 &self = “借当前错误看一眼” → 返回 JSON 响应体
     fn error_response(&self) -> HttpResponse {
-        let resp = MyErrorNewResponse {
-            error_message: self.error_response(), // 调用 3.1 的人类可读信息
-        };
-        // build(status_code()) + json() → 返回 JSON + 状态码
-        HttpResponse::build(self.status_code()).json(resp)
+        self.to_http_response(None)
     }
 }
 
@@ -71,12 +95,49 @@ impl From<actix_web::error::Error> for MyErrorNew {
 impl From<sqlx::Error> for MyErrorNew {
     // 2.1 from(err) → 输入一个 SQLx 错误，输出一个 MyErrorNew
     fn from(err: sqlx::Error) -> Self {
-        // 2.2 **零成本转换** → 只拷字符串，不移动原错误
-        MyErrorNew::DbError(err.to_string())
+        match err {
+            // 2.2 连接池拿不到连接（池满等待超时 / 池已关闭）→ 不是 SQL 写错了，
+            //     而是暂时过载 → 503 + Retry-After，和真正的数据库错误（500）区分开
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => {
+                MyErrorNew::ServiceBusy("database connection pool exhausted".into())
+            }
+            // 2.3 其它都是真正的数据库错误 → 只拷字符串
+            other => MyErrorNew::DbError(other.to_string()),
+        }
     }
 }
 
-// ========== 8. 一键使用（? 运算符自动转换） ==========
+// 7.1 ServiceBusy 响应里 Retry-After 的秒数
+pub const RETRY_AFTER_SECS: u64 = 1;
+
+// ========== 8. 请求体 JSON 配置（全局） ==========
+// 请求体最大字节数：课程 JSON 只有几个字段，16 KiB 绰绰有余
+pub const JSON_LIMIT: usize = 16 * 1024;
+
+// 在 App 工厂里 .app_data(json_config()) 注册后，所有 web::Json<T> 提取器共用：
+// - 超过 JSON_LIMIT → PayloadTooLarge（413）
+// - 反序列化失败 → ValidationError（400），消息带上 serde 的原因（缺哪个字段、第几行第几列）
+// 两种情况都返回 {"error_message": ...}，而不是 actix 默认的纯文本错误
+pub fn json_config() -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(JSON_LIMIT)
+        .error_handler(|err, _req| {
+            let my_err = match err {
+                error::JsonPayloadError::Overflow { .. }
+                | error::JsonPayloadError::OverflowKnownLength { .. } => {
+                    MyErrorNew::PayloadTooLarge(format!("limit is {} bytes", JSON_LIMIT))
+                }
+                error::JsonPayloadError::Deserialize(e) => {
+                    MyErrorNew::ValidationError(format!("invalid JSON body: {}", e))
+                }
+                other => MyErrorNew::ValidationError(other.to_string()),
+            };
+            // MyErrorNew 实现了 ResponseError → into() 成 actix_web::Error
+            my_err.into()
+        })
+}
+
+// ========== 9. 一键使用（? 运算符自动转换） ==========
 // pub async fn demo() -> Result<String, MyErrorNew> {
 //     // 6.1 ? 运算符：如果 Err → 自动转成 MyErrorNew 并提前返回
 //     // 这里模拟数据库错误
@@ -86,3 +147,27 @@ impl From<sqlx::Error> for MyErrorNew {
 //“fn = 造函数；impl = 把函数（或 trait）装到类型上。”
 //From trait 就是 “零成本类型转换器”——
 //输入 A，输出 B，不移动原对象，编译器自动调用。
+
+// ========== 10. 单元测试（不需要数据库） ==========
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    // 10.1 连接池超时 → ServiceBusy → 503 + Retry-After；其它 sqlx 错误 → 500，不带 Retry-After
+    #[test]
+    fn pool_timeout_is_service_busy() {
+        let busy = MyErrorNew::from(sqlx::Error::PoolTimedOut);
+        assert!(matches!(busy, MyErrorNew::ServiceBusy(_)));
+        let resp = busy.to_http_response(None);
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            resp.headers().get(header::RETRY_AFTER).unwrap(),
+            &RETRY_AFTER_SECS.to_string()
+        );
+
+        let db = MyErrorNew::from(sqlx::Error::RowNotFound);
+        assert_eq!(db.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(db.to_http_response(None).headers().get(header::RETRY_AFTER).is_none());
+    }
+}
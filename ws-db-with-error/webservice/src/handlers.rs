@@ -7,50 +7,138 @@
 use super::db_access::*;
 use super::errors::MyErrorNew;
 use super::state::AppState; // 全局共享状态（带锁的容器）
-use crate::{ models::Course}; // 我们自己的课程结构体
+use crate::models::{Course, CourseQuery, DeleteTeacherQuery, Page, RecentQuery, ServiceInfo}; // 我们自己的课程结构体 + 查询参数类型
 use actix_web::body::MessageBody; //try_into_bytes 是 MessageBody 的方法 → 先 use actix_web::body::MessageBody; 再 .into_body().try_into_bytes()”
-use actix_web::{HttpResponse, web}; // Web 框架核心类型
+use actix_web::http::header::{self, Accept, ContentType, EntityTag, Header, IfNoneMatch}; // Accept / ETag / If-None-Match 类型化请求头
+use actix_web::{HttpRequest, HttpResponse, web}; // Web 框架核心类型
 use chrono::Utc; // 时间戳生成器（UTC 时间）
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::Ordering;
 
 // ========== 2. 健康检查 ==========
-pub async fn health_check_handler(app_state: web::Data<AppState>) -> HttpResponse {
+pub async fn health_check_handler(req: HttpRequest, app_state: web::Data<AppState>) -> HttpResponse {
     // 2.1 只读字段无需加锁，直接引用
     let health_check_response = &app_state.health_check_response;
 
-    // 2.2 计数器是 Mutex，必须加锁才能改；lock() 返回 MutexGuard<u32>
-    //      unwrap() 在 poison 时 panic（测试可接受，生产建议 match）
-    let mut visit_count = app_state.visit_count.lock().unwrap();
+    // 2.2 开启了持久化计数 → 数据库里加一，返回的新值减一 = 本次之前的访问次数
+    //      和内存计数、ws-db 的健康检查一样：第一次访问显示 "0 times"
+    //      表不存在（迁移没跑）/ 数据库出错 → 打印错误后返回 None，退回下面的内存计数，健康检查本身不失败
+    //      必须在拿 Mutex 之前 await：MutexGuard 不能跨 .await 持有
+    //      数据库还没连上（start_without_db 模式）也一样退回内存计数
+    let persisted = match app_state.db() {
+        Ok(db) if app_state.persistent_visits => match increment_visit_counter_db(db).await {
+            Ok(value) => value.map(|count| count - 1),
+            Err(err) => {
+                eprintln!("visit counter unavailable, using in-memory count: {}", err);
+                None
+            }
+        },
+        _ => None,
+    };
+
+    let visits = match persisted {
+        Some(value) => value,
+        None => {
+            // 2.3 计数器是 Mutex，必须加锁才能改；lock() 返回 MutexGuard<u32>
+            //      unwrap() 在 poison 时 panic（测试可接受，生产建议 match）
+            let mut visit_count = app_state.visit_count.lock().unwrap();
+            let current = i64::from(*visit_count);
+            // 2.4 自增必须在 guard 作用域里，否则编译器不让改
+            *visit_count += 1;
+            current
+            // 2.5 guard 离开作用域 → 自动解锁，其他线程可继续读
+        }
+    };
 
-    // 2.3 拼接响应文本；format! 不会阻塞，因为只读字段无锁
-    let response = format!("{}{} times", health_check_response, *visit_count);
+    // 拼接响应文本；format! 不会阻塞，因为只读字段无锁
+    let response = format!("{}{} times", health_check_response, visits);
 
-    // 2.4 自增必须在 guard 作用域里，否则编译器不让改
-    *visit_count += 1;
-    // 2.5 guard 离开作用域 → 自动解锁，其他线程可继续读
+    // 2.6 内容协商：Accept 里优先级最高的是 text/plain → 返回纯文本
+    //      其他情况（没带 Accept、*/*、application/json …）保持原来的 JSON 字符串
+    let wants_text = Accept::parse(&req)
+        .map(|accept| accept.preference().essence_str() == "text/plain")
+        .unwrap_or(false);
+    if wants_text {
+        return HttpResponse::Ok()
+            .content_type(ContentType::plaintext())
+            .body(response);
+    }
 
-    // 2.6 返回 JSON；&String 自动序列化成 JSON 字符串
+    // 2.7 返回 JSON；&String 自动序列化成 JSON 字符串
     HttpResponse::Ok().json(&response)
 }
 
+// ========== 2.1 存活 / 就绪探针（Kubernetes livenessProbe / readinessProbe） ==========
+// /livez：进程还能处理请求就是 200，不碰数据库（库挂了重启进程也没用）
+pub async fn livez_handler() -> HttpResponse {
+    HttpResponse::Ok().json("alive")
+}
+
+// /readyz：能不能接流量
+// 1. 正在停机（ready = false）→ 503，让负载均衡摘掉本实例
+// 2. 还没连上数据库（start_without_db 模式启动时数据库不可用）→ 503
+// 3. 数据库连不上 / 迁移没跑完 → 503
+// 4. 都没问题 → 200
+pub async fn readyz_handler(app_state: web::Data<AppState>) -> HttpResponse {
+    let Ok(db) = app_state.db() else {
+        return HttpResponse::ServiceUnavailable().json("database not connected");
+    };
+    if !app_state.ready.load(Ordering::SeqCst) {
+        return HttpResponse::ServiceUnavailable().json("shutting down");
+    }
+    match migrations_applied(db).await {
+        Ok(true) => HttpResponse::Ok().json("ready"),
+        Ok(false) => HttpResponse::ServiceUnavailable().json("migrations pending"),
+        Err(_) => HttpResponse::ServiceUnavailable().json("database unreachable"),
+    }
+}
+
+// ========== 2.2 服务信息 ==========
+// /info：运行时长 + 版本号，方便确认线上跑的是哪个构建、有没有被悄悄重启过
+// env!("CARGO_PKG_VERSION") 在编译期展开成 Cargo.toml 的 version，运行时不读文件
+pub async fn info_handler(app_state: web::Data<AppState>) -> HttpResponse {
+    HttpResponse::Ok().json(ServiceInfo {
+        uptime_secs: app_state.started_at.elapsed().as_secs(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+// ========== 2.3 路由清单 ==========
+// /routes：返回 routers::ROUTES，客户端不用翻代码就能知道有哪些接口
+pub async fn routes_handler() -> HttpResponse {
+    HttpResponse::Ok().json(crate::routers::ROUTES)
+}
+
 // ========== 3. 新建课程 ==========
 pub async fn new_course(
     new_course: web::Json<Course>,  // 3.1 请求体自动反序列化成 Course
     app_state: web::Data<AppState>, // 3.2 共享状态，内部是 Arc<AppState>
-) -> HttpResponse {
+) -> Result<HttpResponse, MyErrorNew> {
     println!("Received new course");
 
-    // 3.3 计算同一老师的已有课程数（用于生成自增 ID）
-    //     clone() 会把整表复制一份 → O(n) 内存，测试可接受；
-    //     生产环境建议 iter() + count()，避免整表克隆
-    let course_count = app_state
-        .courses
-        .lock()
-        .unwrap()
+    // 3.3 "查重 + 插入"只加一次锁 → 两个并发请求不可能同时通过查重
+    let mut courses = app_state.courses.lock().unwrap();
+
+    // 3.4 同一老师下课程名不能重复（不区分大小写）→ 400
+    let name = new_course.name.to_lowercase();
+    let duplicate = courses
+        .iter()
+        .any(|course| course.teacher_id == new_course.teacher_id && course.name.to_lowercase() == name);
+    if duplicate {
+        return Err(MyErrorNew::ValidationError(format!(
+            "teacher {} already has a course named {:?}",
+            new_course.teacher_id, new_course.name
+        )));
+    }
+
+    // 3.5 计算同一老师的已有课程数（用于生成自增 ID）
+    let course_count = courses
         .iter() // 只读迭代，无克隆
         .filter(|course| course.teacher_id == new_course.teacher_id)
         .count();
 
-    // 3.4 构建新 Course；id 用 count+1 模拟自增，time 用当前 UTC
+    // 3.6 构建新 Course；id 用 count+1 模拟自增，time 用当前 UTC
     let new_course = Course {
         teacher_id: new_course.teacher_id,
         id: 2,                              // 自增 ID
@@ -58,28 +146,31 @@ pub async fn new_course(
         time: Some(Utc::now().naive_utc()), // 时间戳
     };
 
-    // 3.5 再次加锁，把新课程 push 进 Vec
-    app_state.courses.lock().unwrap().push(new_course);
+    // 3.7 记审计日志，再 push 进 Vec；guard 离开作用域时自动解锁
+    app_state.audit("create", &new_course);
+    courses.push(new_course);
 
-    // 3.6 返回简单文本
-    HttpResponse::Ok().body("course add")
+    // 3.8 返回简单文本
+    Ok(HttpResponse::Ok().body("course add"))
 }
 
 pub async fn new_course_handle_db(
     new_course: web::Json<Course>,  // 3.1 请求体自动反序列化成 Course
     app_state: web::Data<AppState>, // 3.2 共享状态，内部是 Arc<AppState>
-) -> HttpResponse {
+) -> Result<HttpResponse, MyErrorNew> {
     println!("Received new course");
 
-    let course = post_new_course_db(&app_state.db, new_course.into()).await;
-    HttpResponse::Ok().json(course)
+    // 3.3 数据库还没连上 → 503
+    let course = post_new_course_db(app_state.db()?, new_course.into()).await?;
+    app_state.audit("create", &course);
+    Ok(HttpResponse::Ok().json(course))
 }
 // ========== 4. 根据老师 ID 查课程 ==========
 pub async fn get_courses_for_teacher(
     app_state: web::Data<AppState>,
     params: web::Path<(i32, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
 ) -> HttpResponse {
-    // 4.2 解压元组 → (usize, String)
+    // 4.2 解压元组 → (i32, String)
     let (teacher_id, _name) = params.into_inner();
 
     // 4.3 只读过滤：iter() 不克隆，filter 后 cloned() 把匹配项复制出来
@@ -102,47 +193,150 @@ pub async fn get_courses_for_teacher(
 
 pub async fn get_courses_for_teacher_handle_db(
     app_state: web::Data<AppState>,                   // 1.1 **共享状态** → **Arc<AppState>**，零成本借用
-    params: web::Path<(usize, String)>,              // 1.2 **路径参数** → `/courses/{teacher_id}/{name}` → **零成本借用**
+    params: web::Path<(i32, String)>,                // 1.2 **路径参数** → `/courses/{teacher_id}/{name}` → **零成本借用**
+    query: web::Query<CourseQuery>,                  // 1.2.1 **查询参数** → `?page=1&per_page=20&sort=name&q=rust`，可省略
 ) -> Result<HttpResponse, MyErrorNew> {              // 1.3 **返回 Result** → **Ok(Json) 或 Err(MyErrorNew)****
 
-    // 2.1 **解压元组** → (usize, String)
-    let teacher_id = i32::try_from(params.0).unwrap(); // 2.2 **usize → i32** → **数据库 integer 对齐**
+    // 1.4 **先校验查询参数** → 不合法直接 400，不碰数据库
+    query.validate()?;
+
+    // 2.1 **解压元组** → (i32, String)
+    let teacher_id = params.0; // 2.2 **直接按 i32 提取** → 与数据库 integer 对齐，无需转换
 
     // 3.1 **调用数据库函数** → **&Pool → 零成本借用**
     // 3.2 **.await** → **异步等待数据库 IO**，**不阻塞线程**
-    // 3.3 **.map(|courses| …)** → **Ok 路径 → 把 Vec<Course> 转成 JSON**
-    get_courses_for_teacher_db(&app_state.db, teacher_id)
-        .await
-        .map(|courses| HttpResponse::Ok().json(courses))   // 3.4 **Ok → JSON 响应**
+    // 3.3 **先查总数** → 老师一门课都没有 → 404（和分页前的行为一致）
+    let all = count_courses_db(app_state.db()?, teacher_id, None).await?;
+    if all == 0 {
+        return Err(MyErrorNew::NotFound("course not found ".into()));
+    }
+
+    // 3.3.1 **带关键字时** → total 是匹配关键字的条数（可以是 0，返回空页而不是 404）
+    let total = match query.q() {
+        Some(q) => count_courses_db(app_state.db()?, teacher_id, Some(q)).await?,
+        None => all,
+    };
+
+    // 3.4 **再查当前页** → 组装成 Page<Course>（items + total + 页码信息）
+    let (page, per_page) = (query.page(), query.per_page());
+    let items = get_courses_for_teacher_db(
+        app_state.db()?,
+        teacher_id,
+        query.q(),
+        query.sort(),
+        i64::from(per_page),
+        query.offset(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(Page {
+        items,
+        total,
+        page,
+        per_page,
+    }))
+}
+
+// ========== 4.5 课程详情的 ETag ==========
+// 用 id + name + time 算一个哈希当作 **强 ETag**：内容不变 → ETag 不变
+pub fn course_etag(course: &Course) -> EntityTag {
+    let mut hasher = DefaultHasher::new();
+    course.id.hash(&mut hasher);
+    course.name.hash(&mut hasher);
+    course.time.hash(&mut hasher);
+    EntityTag::new_strong(format!("{:x}", hasher.finish()))
 }
 
 pub async fn get_course_detail_handle_db(
+    req: HttpRequest, // 读取 If-None-Match 请求头
     app_state: web::Data<AppState>,
-    params: web::Path<(usize, usize)>,
-) -> HttpResponse {
-    let teacher_id = i32::try_from(params.0).unwrap();
-    let course_id = i32::try_from(params.1).unwrap();
-    let course = get_course_detail_db(&app_state.db, teacher_id, course_id).await;
-    HttpResponse::Ok().json(course)
+    params: web::Path<(i32, i32)>,
+) -> Result<HttpResponse, MyErrorNew> {
+    let (teacher_id, course_id) = params.into_inner();
+    let course = get_course_detail_db(app_state.db()?, teacher_id, course_id).await?;
+    let etag = course_etag(&course);
+
+    // 客户端带来的 ETag 和当前一致 → 304 Not Modified，不返回 body，省流量
+    // If-None-Match 用 **弱比较**（RFC 7232），`*` 匹配任意
+    let not_modified = match IfNoneMatch::parse(&req) {
+        Ok(IfNoneMatch::Any) => true,
+        Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        Err(_) => false,
+    };
+    if not_modified {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(header::ETag(etag))
+            .finish());
+    }
+
+    Ok(HttpResponse::Ok()
+        .insert_header(header::ETag(etag))
+        .json(course))
+}
+
+// ========== 4.6 按老师统计课程数 ==========
+// GET /teachers/summary → [{teacher_id, course_count}]，按 teacher_id 升序
+pub async fn get_teacher_summary_handle_db(
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse, MyErrorNew> {
+    get_teacher_summary_db(app_state.db()?)
+        .await
+        .map(|summary| HttpResponse::Ok().json(summary))
+}
+
+// ========== 4.7 删除老师 ==========
+// DELETE /teachers/{teacher_id}[?cascade=true]
+// 还有课程且没带 cascade → 400；带了 cascade → 课程一起删，返回删除数量
+pub async fn delete_teacher_handle_db(
+    app_state: web::Data<AppState>,
+    params: web::Path<i32>,
+    query: web::Query<DeleteTeacherQuery>,
+) -> Result<HttpResponse, MyErrorNew> {
+    let teacher_id = params.into_inner();
+    let cascade = query.cascade.unwrap_or(false);
+
+    let deleted = delete_teacher_db(app_state.db()?, teacher_id, cascade).await?;
+    // 每门被删的课程各记一条审计日志
+    for course in &deleted {
+        app_state.audit("delete", course);
+    }
+    Ok(HttpResponse::Ok().json(format!(
+        "deleted teacher {} and {} courses",
+        teacher_id,
+        deleted.len()
+    )))
+}
+
+// ========== 4.7.1 最近创建的课程 ==========
+// GET /courses/recent?limit=10 → 所有老师的课程按 time 降序，最多 limit 条
+pub async fn get_recent_courses_handle_db(
+    app_state: web::Data<AppState>,
+    query: web::Query<RecentQuery>,
+) -> Result<HttpResponse, MyErrorNew> {
+    // sqlx::Error → MyErrorNew 由 From 自动转换（? 运算符）
+    let courses = get_recent_courses_db(app_state.db()?, i64::from(query.limit())).await?;
+    Ok(HttpResponse::Ok().json(courses))
+}
+
+// ========== 4.8 兜底 404 ==========
+// 注册为 App 的 default_service：任何没匹配上的路径/方法都走这里，
+// 返回和其它 NotFound 一样的 JSON（{"error_message": ...}），而不是 actix 默认的空 404
+pub async fn not_found_handler() -> Result<HttpResponse, MyErrorNew> {
+    Err(MyErrorNew::NotFound("route not found".into()))
 }
 
 // ========== 5. 单元测试 ==========
 #[cfg(test)]
 mod tests {
     use super::*;
-    use actix_web::{App, http::StatusCode};
-    use dotenv::dotenv; // test里面新增
-    use sqlx::postgres::PgPoolOptions;
+    use actix_web::{App, ResponseError, http::StatusCode, test};
+    use crate::state::{maybe_pool, maybe_pool_with}; // 连不上数据库 → 跳过 DB 测试
+    use sqlx::postgres::{PgPool, PgPoolOptions};
     use std::env;
-    use std::sync::Mutex;
 
     // 5.1 测试：POST /courses 成功创建
     #[actix_web::test]
     async fn post_course_test() {
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
-
         // 5.2 造请求体
         let course = web::Json(Course {
             teacher_id: 1,
@@ -152,15 +346,10 @@ mod tests {
         });
 
         // 5.3 造空全局状态
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]),
-            db: db_pool,
-        });
+        let app_state = web::Data::new(AppState::for_test_without_db());
 
         // 5.4 直接调处理器（绕过 HTTP 层，速度最快）
-        let resp = new_course(course, app_state).await;
+        let resp = new_course(course, app_state).await.unwrap();
 
         // 5.5 断言
         assert_eq!(resp.status(), StatusCode::OK);
@@ -174,9 +363,9 @@ mod tests {
 
     #[actix_web::test]
     async fn post_course_test_db() {
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
 
         // 5.2 造请求体
         let course = web::Json(Course {
@@ -187,15 +376,10 @@ mod tests {
         });
 
         // 5.3 造空全局状态
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]),
-            db: db_pool,
-        });
+        let app_state = web::Data::new(AppState::for_test(db_pool));
 
         // 5.4 直接调处理器（绕过 HTTP 层，速度最快）
-        let resp = new_course_handle_db(course, app_state).await;
+        let resp = new_course_handle_db(course, app_state).await.unwrap();
 
         // 5.5 断言
         assert_eq!(resp.status(), StatusCode::OK);
@@ -212,15 +396,7 @@ mod tests {
     // 5.6 测试：GET /courses/{teacher_id}/{name} 空结果
     #[actix_web::test]
     async fn get_course_test() {
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]), // 空表 → 应返回 []
-            db: db_pool,
-        });
+        let app_state = web::Data::new(AppState::for_test_without_db());
 
         // 5.7 构造双段路径
         let params = web::Path::from((1, "asdf".to_string()));
@@ -232,4 +408,675 @@ mod tests {
         let body: Vec<Course> = serde_json::from_slice(&bytes).unwrap();
         assert!(body.is_empty());
     }
+
+    // 5.8 测试：课程详情返回 ETag，带上 If-None-Match 再请求 → 304 且无 body
+    #[actix_web::test]
+    async fn get_course_detail_etag_test() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        // 先插一条课程，保证详情一定查得到
+        let inserted = post_new_course_db(
+            &db_pool,
+            Course {
+                teacher_id: 1,
+                name: "etag course".into(),
+                id: 0,
+                time: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let app_state = web::Data::new(AppState::for_test(db_pool));
+        let params = || web::Path::from((1, inserted.id));
+
+        // 第一次请求：200 + ETag
+        let req = test::TestRequest::default().to_http_request();
+        let resp = get_course_detail_handle_db(req, app_state.clone(), params()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+
+        // 第二次请求带上 ETag：304，body 为空
+        let req = test::TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .to_http_request();
+        let resp = get_course_detail_handle_db(req, app_state.clone(), params()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        assert!(bytes.is_empty());
+
+        // 走完整路由：/courses/db/detail/{user_id}/{course_id} 能提取路径参数，ETag 一致 → 304
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+        let uri = format!("/courses/db/detail/1/{}", inserted.id);
+        let resp = test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        let req = test::TestRequest::get()
+            .uri(&uri)
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_MODIFIED);
+
+        // 详情有自己的 detail 前缀 → /courses/db/{user_id}/{name} 第二段是数字也还是分页列表
+        let uri = format!("/courses/db/1/{}", inserted.id);
+        let page: Page<Course> =
+            test::call_and_read_body_json(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+        assert!(page.total >= 1);
+    }
+
+    // 5.8.1 测试：课程不存在 → 404 + JSON 错误，worker 不会 panic
+    #[actix_web::test]
+    async fn get_course_detail_missing_returns_404() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::for_test(db_pool)))
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+
+        let uri = format!("/courses/db/detail/1/{}", i32::MAX);
+        let resp = test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error_message"].as_str().unwrap().contains("not found"));
+    }
+
+    // 5.9 测试：健康检查按 Accept 返回 JSON 或纯文本，两种请求都会让计数 +1
+    #[actix_web::test]
+    async fn health_check_content_negotiation_test() {
+        let app_state = web::Data::new(AppState {
+            health_check_response: "OK ".to_string(),
+            ..AppState::for_test_without_db()
+        });
+
+        // Accept: application/json → JSON 字符串（带引号）
+        let req = test::TestRequest::default()
+            .insert_header((header::ACCEPT, "application/json"))
+            .to_http_request();
+        let resp = health_check_handler(req, app_state.clone()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let body: String = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body, "OK 0 times");
+
+        // Accept: text/plain → 纯文本（不带引号）
+        let req = test::TestRequest::default()
+            .insert_header((header::ACCEPT, "text/plain"))
+            .to_http_request();
+        let resp = health_check_handler(req, app_state.clone()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/plain; charset=utf-8"
+        );
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        assert_eq!(&bytes[..], b"OK 1 times");
+
+        assert_eq!(*app_state.visit_count.lock().unwrap(), 2);
+    }
+
+    // 5.10 测试：分页查询返回 Page<Course>，total 是总数，items 只有当前页
+    #[actix_web::test]
+    async fn get_courses_paginated_test() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        // 专用老师 ID：先清空，再插 3 门课，保证计数可重复
+        let teacher_id = 845_001;
+        sqlx::query("DELETE FROM rust_test1.course WHERE teacher_id = $1")
+            .bind(teacher_id)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        for name in ["a", "b", "c"] {
+            post_new_course_db(
+                &db_pool,
+                Course {
+                    id: 0,
+                    teacher_id,
+                    name: name.into(),
+                    time: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let app_state = web::Data::new(AppState::for_test(db_pool));
+        let params = || web::Path::from((teacher_id, "asdf".to_string()));
+        let query = |page, per_page| {
+            web::Query(CourseQuery {
+                page: Some(page),
+                per_page: Some(per_page),
+                ..CourseQuery::default()
+            })
+        };
+
+        // 第 1 页：2 条
+        let resp = get_courses_for_teacher_handle_db(app_state.clone(), params(), query(1, 2))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let page: Page<Course> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!((page.page, page.per_page), (1, 2));
+
+        // 第 2 页：剩下 1 条
+        let resp = get_courses_for_teacher_handle_db(app_state, params(), query(2, 2))
+            .await
+            .unwrap();
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let page: Page<Course> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].name, "c");
+    }
+
+    // 5.11 删除老师的测试准备：专用老师 ID，先清空再插 2 门课
+    async fn seed_teacher(db_pool: &PgPool, teacher_id: i32) {
+        sqlx::query("DELETE FROM rust_test1.course WHERE teacher_id = $1")
+            .bind(teacher_id)
+            .execute(db_pool)
+            .await
+            .unwrap();
+        for name in ["a", "b"] {
+            post_new_course_db(
+                db_pool,
+                Course {
+                    id: 0,
+                    teacher_id,
+                    name: name.into(),
+                    time: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    // 5.12 测试：还有课程且没带 cascade → 400，课程原样保留
+    #[actix_web::test]
+    async fn delete_teacher_refuses_without_cascade() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        let teacher_id = 846_001;
+        seed_teacher(&db_pool, teacher_id).await;
+
+        let app_state = web::Data::new(AppState::for_test(db_pool.clone()));
+        let query = web::Query(DeleteTeacherQuery { cascade: None });
+        let err = delete_teacher_handle_db(app_state, web::Path::from(teacher_id), query)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, MyErrorNew::ValidationError(_)));
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(count_courses_db(&db_pool, teacher_id, None).await.unwrap(), 2);
+    }
+
+    // 5.13 测试：cascade=true → 课程全部删除；再删一次 → 404
+    #[actix_web::test]
+    async fn delete_teacher_cascades() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        let teacher_id = 846_002;
+        seed_teacher(&db_pool, teacher_id).await;
+
+        let app_state = web::Data::new(AppState::for_test(db_pool.clone()));
+        let cascade = || web::Query(DeleteTeacherQuery { cascade: Some(true) });
+        let resp = delete_teacher_handle_db(
+            app_state.clone(),
+            web::Path::from(teacher_id),
+            cascade(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let body: String = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body, "deleted teacher 846002 and 2 courses");
+        assert_eq!(count_courses_db(&db_pool, teacher_id, None).await.unwrap(), 0);
+
+        let err = delete_teacher_handle_db(app_state, web::Path::from(teacher_id), cascade())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+    }
+
+    // 5.14 测试：未注册的路径 → 走 configure_app 里的 default_service，返回 JSON 404
+    #[actix_web::test]
+    async fn unknown_route_returns_json_404() {
+        let app = test::init_service(App::new().configure(crate::configure_app)).await;
+
+        let req = test::TestRequest::get().uri("/nope").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error_message"]
+            .as_str()
+            .unwrap()
+            .contains("route not found"));
+    }
+
+    // 5.15 测试用 App：挂上全局 JsonConfig 和 POST /courses/（只用内存，不需要数据库）
+    async fn post_raw_course(body: Vec<u8>) -> actix_web::dev::ServiceResponse {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::for_test_without_db()))
+                .app_data(crate::errors::json_config())
+                .route("/courses/", web::post().to(new_course)),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/courses/")
+            .insert_header(ContentType::json())
+            .set_payload(body)
+            .to_request();
+        test::call_service(&app, req).await
+    }
+
+    // 5.16 测试：JSON 被截断 → 400，错误信息说明解析失败的原因
+    #[actix_web::test]
+    async fn truncated_json_returns_400() {
+        let resp = post_raw_course(br#"{"id": 1, "teacher_id": 1, "name": "ru"#.to_vec()).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error_message"]
+            .as_str()
+            .unwrap()
+            .contains("invalid JSON body"));
+    }
+
+    // 5.17 测试：缺字段 → 400，错误信息里带上字段名
+    #[actix_web::test]
+    async fn missing_field_names_the_field() {
+        let resp = post_raw_course(br#"{"id": 1, "teacher_id": 1}"#.to_vec()).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error_message"].as_str().unwrap().contains("`name`"));
+    }
+
+    // 5.18 测试：请求体超过 JSON_LIMIT → 413
+    #[actix_web::test]
+    async fn oversized_json_returns_413() {
+        let name = "x".repeat(crate::errors::JSON_LIMIT);
+        let payload = serde_json::to_vec(&serde_json::json!({
+            "id": 1,
+            "teacher_id": 1,
+            "name": name,
+        }))
+        .unwrap();
+        let resp = post_raw_course(payload).await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error_message"].as_str().unwrap().contains("limit"));
+    }
+
+    // 5.19 测试：同一老师重名（不区分大小写）→ 400；换个老师同名 → 允许
+    #[actix_web::test]
+    async fn new_course_rejects_duplicate_name_per_teacher() {
+        let app_state = web::Data::new(AppState::for_test_without_db());
+
+        let course = |teacher_id: i32, name: &str| {
+            web::Json(Course {
+                teacher_id,
+                name: name.into(),
+                id: 0,
+                time: None,
+            })
+        };
+
+        let resp = new_course(course(1, "Rust"), app_state.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let err = new_course(course(1, "rUST"), app_state.clone()).await.unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+
+        let resp = new_course(course(2, "Rust"), app_state.clone()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(app_state.courses.lock().unwrap().len(), 2);
+    }
+
+    // 5.20 测试：CourseQuery 的每种非法参数都被拒绝（400），合法参数通过
+    #[actix_web::test]
+    async fn course_query_validation() {
+        let query = |page, per_page, sort: Option<&str>| CourseQuery {
+            page,
+            per_page,
+            sort: sort.map(String::from),
+            q: None,
+        };
+        let rejected = [
+            query(Some(0), None, None),
+            query(Some(-1), None, None),
+            query(None, Some(0), None),
+            query(None, Some(101), None),
+            query(None, None, Some("price")),
+        ];
+        for q in rejected {
+            let err = q.validate().unwrap_err();
+            assert_eq!(err.status_code(), StatusCode::BAD_REQUEST, "{:?}", q);
+        }
+
+        let ok = query(Some(2), Some(100), Some("name"));
+        assert!(ok.validate().is_ok());
+        assert_eq!((ok.page(), ok.per_page(), ok.offset(), ok.sort()), (2, 100, 100, "name"));
+
+        let defaults = CourseQuery::default();
+        assert!(defaults.validate().is_ok());
+        assert_eq!((defaults.page(), defaults.per_page(), defaults.sort()), (1, 20, "id"));
+    }
+
+    // 5.21 测试：handler 先校验 → 非法参数 400；sort=name + q 过滤后按名字排序
+    #[actix_web::test]
+    async fn get_courses_sort_and_search_test() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+
+        let teacher_id = 872_001;
+        sqlx::query("DELETE FROM rust_test1.course WHERE teacher_id = $1")
+            .bind(teacher_id)
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        for name in ["rust web", "go", "Rust basics"] {
+            post_new_course_db(
+                &db_pool,
+                Course {
+                    id: 0,
+                    teacher_id,
+                    name: name.into(),
+                    time: None,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        let app_state = web::Data::new(AppState::for_test(db_pool));
+        let params = || web::Path::from((teacher_id, "asdf".to_string()));
+
+        let bad = web::Query(CourseQuery {
+            per_page: Some(500),
+            ..CourseQuery::default()
+        });
+        let err = get_courses_for_teacher_handle_db(app_state.clone(), params(), bad)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+
+        let search = web::Query(CourseQuery {
+            sort: Some("name".into()),
+            q: Some("RUST".into()),
+            ..CourseQuery::default()
+        });
+        let resp = get_courses_for_teacher_handle_db(app_state, params(), search)
+            .await
+            .unwrap();
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let page: Page<Course> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(page.total, 2);
+        let names: Vec<&str> = page.items.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Rust basics", "rust web"]);
+    }
+
+    // 5.22 测试：/livez 始终 200；/readyz 就绪时 200，ready 标志清掉后 503
+    #[actix_web::test]
+    async fn livez_and_readyz_test() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        migrate(&db_pool).await.unwrap();
+
+        let app_state = web::Data::new(AppState::for_test(db_pool));
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .configure(crate::routers::general_routes),
+        )
+        .await;
+
+        let get = |uri: &'static str| test::TestRequest::get().uri(uri).to_request();
+        assert_eq!(test::call_service(&app, get("/livez")).await.status(), StatusCode::OK);
+        assert_eq!(test::call_service(&app, get("/readyz")).await.status(), StatusCode::OK);
+
+        // 模拟收到 SIGTERM：信号任务做的就是这一步
+        app_state.ready.store(false, Ordering::SeqCst);
+        assert_eq!(
+            test::call_service(&app, get("/readyz")).await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        // 存活探针不受影响，否则 Kubernetes 会在排空期间直接杀进程
+        assert_eq!(test::call_service(&app, get("/livez")).await.status(), StatusCode::OK);
+    }
+
+    // 5.23 测试：连接池获取超时 → ServiceBusy（503 + Retry-After），普通 SQL 错误仍是 500
+    #[actix_web::test]
+    async fn pool_timeout_maps_to_service_busy() {
+        // 真实模拟：池里只有 1 个连接且被占着，再拿连接就会等到 acquire_timeout
+        let Some(db_pool) = maybe_pool_with(
+            PgPoolOptions::new()
+                .max_connections(1)
+                .acquire_timeout(std::time::Duration::from_millis(100)),
+        )
+        .await
+        else {
+            return;
+        };
+        let _held = db_pool.acquire().await.unwrap();
+        let err = get_recent_courses_db(&db_pool, 1).await.unwrap_err();
+
+        let busy: MyErrorNew = err.into();
+        assert!(matches!(busy, MyErrorNew::ServiceBusy(_)));
+        assert_eq!(busy.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        let resp = busy.error_response();
+        assert_eq!(
+            resp.headers().get(header::RETRY_AFTER).unwrap(),
+            &crate::errors::RETRY_AFTER_SECS.to_string()
+        );
+
+        let db: MyErrorNew = sqlx::Error::RowNotFound.into();
+        assert_eq!(db.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(db.error_response().headers().get(header::RETRY_AFTER).is_none());
+    }
+
+    // 5.23.1 测试：新建课程 / 课程详情的数据库错误也走 MyErrorNew → 连接池已关闭时返回 503 + Retry-After，不会 panic
+    //        connect_lazy 不会真的去连数据库 → 不需要 Postgres
+    #[actix_web::test]
+    async fn db_handlers_return_service_busy_when_pool_is_closed() {
+        let db_pool = PgPoolOptions::new()
+            .connect_lazy("postgres://unused@localhost/unused")
+            .unwrap();
+        db_pool.close().await;
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::for_test(db_pool)))
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+
+        let create = test::TestRequest::get()
+            .uri("/courses/db/")
+            .set_json(serde_json::json!({ "id": 0, "teacher_id": 1, "name": "busy" }))
+            .to_request();
+        let detail = test::TestRequest::get().uri("/courses/db/detail/1/1").to_request();
+        for req in [create, detail] {
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert!(resp.headers().contains_key(header::RETRY_AFTER));
+        }
+    }
+
+    // 5.24 测试：配置了审计日志 → 新建课程后文件里多一行可解析的 JSON，event = "create"
+    #[actix_web::test]
+    async fn create_writes_audit_line() {
+        let path = env::temp_dir().join(format!("audit-test-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let app_state = web::Data::new(AppState {
+            audit_log: Some(path.clone()),
+            ..AppState::for_test_without_db()
+        });
+
+        let course = web::Json(Course {
+            teacher_id: 883_001,
+            name: "audited course".into(),
+            id: 0,
+            time: None,
+        });
+        new_course(course, app_state).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["event"], "create");
+        assert_eq!(entry["course"]["teacher_id"], 883_001);
+        assert_eq!(entry["course"]["name"], "audited course");
+        assert!(entry["ts"].is_string());
+    }
+
+    // 5.25 测试：/info 返回运行时长和非空版本号；started_at 往前拨 5 秒 → uptime 至少 5
+    #[actix_web::test]
+    async fn info_reports_uptime_and_version() {
+        let started_at = std::time::Instant::now()
+            .checked_sub(std::time::Duration::from_secs(5))
+            .unwrap();
+        let app_state = web::Data::new(AppState {
+            started_at,
+            ..AppState::for_test_without_db()
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .configure(crate::routers::general_routes),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/info").to_request();
+        let info: ServiceInfo = test::call_and_read_body_json(&app, req).await;
+        assert!(info.uptime_secs >= 5);
+        assert!(!info.version.is_empty());
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    // 5.26 测试：/routes 列出 POST /courses/ 和 GET /health（不需要数据库）
+    #[actix_web::test]
+    async fn routes_listing_includes_core_endpoints() {
+        let app = test::init_service(App::new().configure(crate::routers::general_routes)).await;
+
+        let req = test::TestRequest::get().uri("/routes").to_request();
+        let routes: Vec<serde_json::Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(routes.len(), crate::routers::ROUTES.len());
+
+        let listed = |method: &str, path: &str| {
+            routes
+                .iter()
+                .any(|route| route["method"] == method && route["path"] == path && route["description"].is_string())
+        };
+        assert!(listed("POST", "/courses/"));
+        assert!(listed("GET", "/health"));
+        assert!(listed("GET", "/routes"));
+    }
+
+    // 5.27 测试：开启持久化计数后，两次健康检查返回的数据库计数递增，内存计数不动
+    //      counters 表是所有测试 / 实例共享的 → 只断言"变大"，不断言具体值
+    #[actix_web::test]
+    async fn persistent_visit_count_increases() {
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        migrate(&db_pool).await.unwrap();
+        let app_state = web::Data::new(AppState {
+            health_check_response: "OK ".to_string(),
+            persistent_visits: true,
+            ..AppState::for_test(db_pool)
+        });
+
+        let mut counts = Vec::new();
+        for _ in 0..2 {
+            let req = test::TestRequest::default()
+                .insert_header((header::ACCEPT, "text/plain"))
+                .to_http_request();
+            let resp = health_check_handler(req, app_state.clone()).await;
+            let bytes = resp.into_body().try_into_bytes().unwrap();
+            let text = std::str::from_utf8(&bytes).unwrap();
+            let count: i64 = text
+                .strip_prefix("OK ")
+                .and_then(|rest| rest.strip_suffix(" times"))
+                .unwrap()
+                .parse()
+                .unwrap();
+            counts.push(count);
+        }
+
+        assert!(counts[0] >= 0);
+        assert!(counts[1] > counts[0], "{:?}", counts);
+        assert_eq!(*app_state.visit_count.lock().unwrap(), 0);
+    }
+
+    // 5.28 测试：启动时数据库不可用（连接池为空）→ 数据库接口和 /readyz 503，/livez、/health 照常；
+    //      后台连上后 set 连接池、置 ready → 同一个 app 里的数据库接口恢复 200
+    #[actix_web::test]
+    async fn db_endpoints_unavailable_until_pool_is_set() {
+        let app_state = web::Data::new(AppState {
+            ready: std::sync::atomic::AtomicBool::new(false),
+            ..AppState::for_test_without_db()
+        });
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .configure(crate::routers::general_routes)
+                .configure(crate::routers::course_routes)
+                .configure(crate::routers::teacher_routes),
+        )
+        .await;
+
+        let get = |uri: &'static str| test::TestRequest::get().uri(uri).to_request();
+        for uri in ["/courses/recent", "/teachers/summary", "/readyz"] {
+            let resp = test::call_service(&app, get(uri)).await;
+            assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE, "{}", uri);
+        }
+        let resp = test::call_service(&app, get("/courses/recent")).await;
+        assert!(resp.headers().contains_key(header::RETRY_AFTER));
+        assert_eq!(test::call_service(&app, get("/livez")).await.status(), StatusCode::OK);
+        assert_eq!(test::call_service(&app, get("/health")).await.status(), StatusCode::OK);
+
+        // 模拟后台重连成功：teacher-service 的 reconnect_in_background 做的就是这两步
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        migrate(&db_pool).await.unwrap();
+        app_state.db.set(db_pool).unwrap();
+        app_state.ready.store(true, Ordering::SeqCst);
+
+        for uri in ["/courses/recent", "/teachers/summary", "/readyz"] {
+            let resp = test::call_service(&app, get(uri)).await;
+            assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+        }
+    }
 }
@@ -12,6 +12,9 @@ use chrono::NaiveDateTime;
 // - `Serialize`：允许将结构体序列化为 JSON 字符串（返回响应）
 use serde::{Deserialize, Serialize};
 
+// 查询参数校验失败时返回 ValidationError（400）
+use crate::errors::MyErrorNew;
+
 
 // === 定义 Course 结构体 ===
 //
@@ -21,8 +24,8 @@ use serde::{Deserialize, Serialize};
 // - `Clone`：允许复制整个结构体（因为所有字段都实现了 Clone）
 //
 // 设计说明：
-// - `teacher_id` 是必填项（usize），表示所属教师
-// - `id` 是可选项（Option<usize>），因为新建课程时数据库尚未分配 ID
+// - `teacher_id` 是必填项（i32 ↔ 数据库 INTEGER），表示所属教师
+// - `id`（i32）由数据库自增生成，新建课程时传入的值会被忽略
 // - `name` 是课程名称，必填（String）
 // - `time` 是创建/更新时间，可为空（Option<NaiveDateTime>），兼容数据库 NULL
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -34,6 +37,144 @@ pub struct Course {
 }
 
 
+// === 老师课程统计 ===
+//
+// GET /teachers/summary 的返回元素：每个老师有几门课
+// - `course_count` 对应 SQL 的 COUNT(*)，PostgreSQL 里是 BIGINT → i64
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TeacherSummary {
+    pub teacher_id: i32,
+    pub course_count: i64,
+}
+
+
+// === 服务信息 ===
+//
+// GET /info 的返回：进程运行了多久 + 构建版本（Cargo.toml 里的 version）
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ServiceInfo {
+    pub uptime_secs: u64,
+    pub version: String,
+}
+
+
+// === 通用分页响应 ===
+//
+// 列表接口不再直接返回裸数组，而是带上分页元数据：
+// - `items`：当前页的数据
+// - `total`：符合条件的**总条数**（SQL COUNT(*) → i64），前端据此算总页数
+// - `page` / `per_page`：当前页码（从 1 开始）和每页条数
+// 泛型 `T` → 以后任何列表接口都能复用：Page<Course>、Page<Teacher> …
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+// === 课程列表的查询参数 ===
+//
+// `?page=2&per_page=10&sort=name&q=rust`，全部可省略：
+// - page / per_page：默认第 1 页、每页 20 条
+// - sort：排序字段，只能是 SORT_KEYS 里的值，默认按 id
+// - q：课程名包含该关键字（不区分大小写）
+// 先 validate() 再使用：非法值统一返回 400，而不是悄悄改成默认值
+// page / per_page 用 i64 接收 → 负数也能反序列化进来，由 validate() 给出清楚的错误
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct CourseQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    pub sort: Option<String>,
+    pub q: Option<String>,
+}
+
+// === 最近课程的查询参数 ===
+//
+// `GET /courses/recent?limit=10`：省略时默认 10 条，最多 100 条
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct RecentQuery {
+    pub limit: Option<u32>,
+}
+
+pub const DEFAULT_RECENT_LIMIT: u32 = 10;
+pub const MAX_RECENT_LIMIT: u32 = 100;
+
+impl RecentQuery {
+    // 条数限制在 1..=MAX_RECENT_LIMIT
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_RECENT_LIMIT).clamp(1, MAX_RECENT_LIMIT)
+    }
+}
+
+// === 删除老师的查询参数 ===
+//
+// `DELETE /teachers/{teacher_id}?cascade=true`：老师名下还有课程时，
+// 只有显式带上 cascade=true 才会连课程一起删；省略 = false
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DeleteTeacherQuery {
+    pub cascade: Option<bool>,
+}
+
+pub const DEFAULT_PER_PAGE: u32 = 20;
+pub const MAX_PER_PAGE: u32 = 100;
+// 允许的排序字段；第一个是默认值
+pub const SORT_KEYS: [&str; 3] = ["id", "name", "time"];
+
+impl CourseQuery {
+    // 集中校验所有参数；handler 在查询前调用 validate()?
+    pub fn validate(&self) -> Result<(), MyErrorNew> {
+        if let Some(page) = self.page.filter(|&page| page < 1) {
+            return Err(MyErrorNew::ValidationError(format!(
+                "page must be at least 1, got {}",
+                page
+            )));
+        }
+        let per_page_range = 1..=i64::from(MAX_PER_PAGE);
+        if let Some(per_page) = self.per_page.filter(|per_page| !per_page_range.contains(per_page)) {
+            return Err(MyErrorNew::ValidationError(format!(
+                "per_page must be between 1 and {}, got {}",
+                MAX_PER_PAGE, per_page
+            )));
+        }
+        if let Some(sort) = self.sort.as_ref().filter(|sort| !SORT_KEYS.contains(&sort.as_str())) {
+            return Err(MyErrorNew::ValidationError(format!(
+                "unknown sort key {:?}, expected one of {:?}",
+                sort, SORT_KEYS
+            )));
+        }
+        Ok(())
+    }
+
+    // 以下取值方法假定已经 validate() 过
+
+    // 页码，默认 1
+    pub fn page(&self) -> u32 {
+        self.page.map_or(1, |page| page as u32)
+    }
+
+    // 每页条数，默认 DEFAULT_PER_PAGE
+    pub fn per_page(&self) -> u32 {
+        self.per_page.map_or(DEFAULT_PER_PAGE, |per_page| per_page as u32)
+    }
+
+    // 换算成 SQL 的 OFFSET
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page() - 1) * i64::from(self.per_page())
+    }
+
+    // 排序字段，默认 SORT_KEYS[0]
+    pub fn sort(&self) -> &str {
+        self.sort.as_deref().unwrap_or(SORT_KEYS[0])
+    }
+
+    // 搜索关键字；空白关键字等于没传
+    pub fn q(&self) -> Option<&str> {
+        self.q.as_deref().map(str::trim).filter(|q| !q.is_empty())
+    }
+}
+
+
 // === 关于 From<web::Json<Course>> for Course 的说明 ===
 //
 // ❌ 原始错误写法（已注释掉）：
@@ -68,19 +209,6 @@ impl From<web::Json<Course>> for Course {
     }
 }
 
-// === （可选）正确实现 From trait 的方式 ===
-//
-// 注意：此实现是冗余的，因为 `web::Json<Course>` 已经可以 `.into()` 转为 `Course`。
-// 仅在特殊场景（如泛型约束要求必须有 From 实现）才需要。
-// impl From<web::Json<Course>> for Course {
-//     fn from(course: web::Json<Course>) -> Self {
-//         // `web::Json<T>` 提供 `.into_inner()` 方法，安全地取出内部 T
-//         // 这是最推荐的方式，语义清晰且零成本
-//         course.into_inner()
-//     }
-// }
-
-
 // === 最佳实践建议（无需额外代码）===
 //
 // 在你的 handler 函数中，直接这样使用即可：
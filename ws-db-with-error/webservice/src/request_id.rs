@@ -0,0 +1,112 @@
+// ========== 1. 请求 ID（X-Request-Id） ==========
+// 每个请求进来先生成一个 UUID：
+// - 放进 request extensions → handler 里 req.extensions().get::<RequestId>() 就能拿到（打日志用）
+// - 写到响应头 X-Request-Id → 客户端报错时报上这个 id，服务端按 id 查日志
+// - 如果 handler 返回的是 MyErrorNew → 错误 JSON 里也带上 request_id
+use crate::errors::MyErrorNew;
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage};
+use uuid::Uuid;
+
+// 1.1 响应头名（HTTP/2 要求小写）
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+// 1.2 存进 extensions 的新类型：按类型取值，包一层避免和别的 String 冲突
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+// ========== 2. 中间件 ==========
+// App::new().wrap(middleware::from_fn(request_id_middleware)) 注册，
+// 所有路由（包括 default_service 兜底 404）都会经过这里
+pub async fn request_id_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    // 2.1 生成 id 并存进 extensions（extensions_mut 借用要在 next.call 之前释放）
+    let id = Uuid::new_v4().to_string();
+    req.extensions_mut().insert(RequestId(id.clone()));
+
+    // 2.2 调用后面的中间件 / handler
+    let res = next.call(req).await?.map_into_boxed_body();
+
+    // 2.3 错误响应：actix 在 response 里保留了原始错误 → 能还原成 MyErrorNew 的就重建 JSON，带上 id
+    //      其它错误（不是 MyErrorNew）和正常响应保持原样
+    let rebuilt = res
+        .response()
+        .error()
+        .and_then(|err| err.as_error::<MyErrorNew>())
+        .map(|my_err| my_err.to_http_response(Some(id.clone())));
+    let mut res = match rebuilt {
+        Some(resp) => res.into_response(resp),
+        None => res,
+    };
+
+    // 2.4 UUID 只含 [0-9a-f-]，一定是合法的头部值
+    res.headers_mut()
+        .insert(REQUEST_ID_HEADER, HeaderValue::from_str(&id).unwrap());
+    Ok(res)
+}
+
+// ========== 3. 单元测试 ==========
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::not_found_handler;
+    use actix_web::{App, HttpRequest, HttpResponse, http::StatusCode, middleware::from_fn, test, web};
+
+    // 3.1 触发一个 MyErrorNew（兜底 404）→ 响应头和错误 JSON 里是同一个 id
+    #[actix_web::test]
+    async fn request_id_in_header_and_error_body() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_id_middleware))
+                .default_service(web::to(not_found_handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/nope").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let header = resp
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .expect("X-Request-Id header")
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(Uuid::parse_str(&header).is_ok());
+
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["request_id"], header.as_str());
+        assert!(body["error_message"].as_str().unwrap().contains("route not found"));
+    }
+
+    // 3.2 正常响应也带头；handler 从 extensions 读到的 id 和响应头一致；每个请求 id 不同
+    #[actix_web::test]
+    async fn handler_sees_same_request_id() {
+        async fn echo_id(req: HttpRequest) -> HttpResponse {
+            let id = req.extensions().get::<RequestId>().cloned().unwrap();
+            HttpResponse::Ok().body(id.0)
+        }
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(request_id_middleware))
+                .route("/id", web::get().to(echo_id)),
+        )
+        .await;
+
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            let resp = test::call_service(&app, test::TestRequest::get().uri("/id").to_request()).await;
+            let header = resp.headers().get(REQUEST_ID_HEADER).unwrap().to_str().unwrap().to_string();
+            let body = test::read_body(resp).await;
+            assert_eq!(body, header.as_bytes());
+            seen.push(header);
+        }
+        assert_ne!(seen[0], seen[1]);
+    }
+}
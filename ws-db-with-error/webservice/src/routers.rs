@@ -1,9 +1,40 @@
 // 从父模块（通常是 main.rs 或 lib.rs 所在的上一级）导入所有公开的 handler 函数。
 // 这里假设 `health_check_handler` 在 `handlers.rs` 中被定义并标记为 `pub`。
 use super::handlers::*;
+use super::scores::{get_scores, post_score};
 
 // 引入 Actix Web 的 `web` 模块，用于访问路由构建器（如 `web::get`, `web::post` 等）。
 use actix_web::web;
+use serde::Serialize;
+
+// 路由清单里的一条：方法 + 完整路径 + 一句话说明（GET /routes 原样序列化成 JSON）
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct RouteInfo {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub description: &'static str,
+}
+
+// 已注册路由的静态清单
+// actix 不提供"列出所有路由"的接口，所以和下面的 *_routes 函数手工保持一致：
+// 新增 / 修改路由时，这里也要跟着改
+pub const ROUTES: &[RouteInfo] = &[
+    RouteInfo { method: "GET", path: "/health", description: "健康检查 + 访问计数" },
+    RouteInfo { method: "GET", path: "/livez", description: "存活探针（不访问数据库）" },
+    RouteInfo { method: "GET", path: "/readyz", description: "就绪探针（停机中 / 数据库不可用时 503）" },
+    RouteInfo { method: "GET", path: "/info", description: "运行时长和版本号" },
+    RouteInfo { method: "GET", path: "/routes", description: "本清单" },
+    RouteInfo { method: "POST", path: "/courses/", description: "新建课程（内存）" },
+    RouteInfo { method: "GET", path: "/courses/recent", description: "最近创建的课程，?limit=N" },
+    RouteInfo { method: "GET", path: "/courses/{user_id}/{name}", description: "某个老师的课程（内存）" },
+    RouteInfo { method: "GET", path: "/courses/db/{user_id}/{name}", description: "某个老师的课程，支持分页 / 排序 / 搜索" },
+    RouteInfo { method: "GET", path: "/courses/db/", description: "新建课程（数据库，请求体为 JSON）" },
+    RouteInfo { method: "GET", path: "/courses/db/detail/{user_id}/{course_id}", description: "课程详情，带 ETag / If-None-Match" },
+    RouteInfo { method: "GET", path: "/teachers/summary", description: "每个老师的课程数" },
+    RouteInfo { method: "DELETE", path: "/teachers/{teacher_id}", description: "删除老师，?cascade=true 连同课程一起删" },
+    RouteInfo { method: "POST", path: "/scores", description: "提交游戏分数 {name, score}（内存）" },
+    RouteInfo { method: "GET", path: "/scores", description: "游戏分数排行榜" },
+];
 
 // 定义一个公共函数 `general_routes`，用于集中配置应用的路由。
 // 参数 `cfg: &mut web::ServiceConfig` 是 Actix Web 提供的路由配置上下文，
@@ -15,6 +46,13 @@ pub fn general_routes(cfg: &mut web::ServiceConfig) {
     // - 通过 `.to(health_check_handler)` 绑定具体的处理函数
     // 注意：`health_check_handler` 必须是一个符合 Actix Web handler 签名的异步函数
     cfg.service(web::resource("/health").route(web::get().to(health_check_handler)));
+    // Kubernetes 探针：/livez 只看进程活着，/readyz 还要看数据库和停机状态
+    cfg.service(web::resource("/livez").route(web::get().to(livez_handler)));
+    cfg.service(web::resource("/readyz").route(web::get().to(readyz_handler)));
+    // 运行时长 + 版本号
+    cfg.service(web::resource("/info").route(web::get().to(info_handler)));
+    // 机器可读的路由清单（内容见上面的 ROUTES）
+    cfg.service(web::resource("/routes").route(web::get().to(routes_handler)));
 }
 
 // 引入 Actix Web 的 `web::ServiceConfig` 类型（通常已在上级模块引入，此处仅为上下文说明）
@@ -55,12 +93,40 @@ pub fn course_routes(cfg: &mut web::ServiceConfig) {
             // 注册 GET /courses/{user_id} 路由
             // - 路径：`/{user_id}`（完整路径为 `/courses/{user_id}`）
             // - HTTP 方法：GET（通过 `web::get()` 指定）
-            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<usize>`）
+            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<i32>`）
             // - 处理函数：`get_courses_for_teacher`，用于根据教师 ID 查询其所有课程
+            // 注册 GET /courses/recent?limit=10 → 最近创建的课程（数据库 ORDER BY time DESC）
+            .route("/recent", web::get().to(get_recent_courses_handle_db))
+
             .route("/{user_id}/{name}", web::get().to(get_courses_for_teacher))
 
+            // GET /courses/db/detail/{user_id}/{course_id} → 课程详情（带 ETag）
+            // - 单独的 detail 前缀，和 `/db/{user_id}/{name}` 段数不同、不会重叠：课程名是纯数字也照样走分页查询
+            .route("/db/detail/{user_id}/{course_id}", web::get().to(get_course_detail_handle_db))
             .route("/db/{user_id}/{name}", web::get().to(get_courses_for_teacher_handle_db))
-            .route("/db/", web::get().to(new_course_handle_db))
-            .route("/db/detail", web::get().to(get_course_detail_handle_db)),
+            .route("/db/", web::get().to(new_course_handle_db)),
     );
-}
\ No newline at end of file
+}
+
+// 注册老师相关路由：
+// - GET /teachers/summary → 每个老师的课程数（数据库 GROUP BY）
+// - DELETE /teachers/{teacher_id}[?cascade=true] → 删除老师（名下有课程时需 cascade）
+pub fn teacher_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/teachers")
+            .route("/summary", web::get().to(get_teacher_summary_handle_db))
+            .route("/{teacher_id}", web::delete().to(delete_teacher_handle_db)),
+    );
+}
+
+// 注册游戏分数路由（flappy 的 SCORE_ENDPOINT 指向这里，见 scores.rs）：
+// - POST /scores → 提交一条分数
+// - GET  /scores → 排行榜
+// 依赖 web::Data<ScoreBoard>，和 AppState 分开注册
+pub fn score_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/scores")
+            .route(web::post().to(post_score))
+            .route(web::get().to(get_scores)),
+    );
+}
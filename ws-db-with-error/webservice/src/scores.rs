@@ -0,0 +1,179 @@
+// ========== 1. 游戏分数排行榜（只在内存里） ==========
+// 和课程无关的小功能：flappy 游戏设置 SCORE_ENDPOINT 后，每局结束把分数 POST 过来
+//   POST /scores  {"name": "alice", "score": 12}  → 201 + 记录本身
+//   GET  /scores                                   → 分数最高的前 LEADERBOARD_SIZE 条
+// 数据放在进程内存里，重启清空；单独一个 web::Data<ScoreBoard>，不放进 AppState
+// → 不依赖数据库，测试不用连 Postgres
+use super::errors::MyErrorNew;
+use actix_web::{HttpResponse, web};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::sync::Mutex;
+
+// 1.1 排行榜只返回前 N 名；名字按字符数限制长度
+pub const LEADERBOARD_SIZE: usize = 10;
+const NAME_MAX_CHARS: usize = 32;
+
+// 1.2 请求体和排行榜条目用同一个结构体
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Score {
+    pub name: String,
+    pub score: i32,
+}
+
+// 1.3 只保留最高的 LEADERBOARD_SIZE 条（已按分数从高到低排好），Mutex 保护：多个 worker 线程会同时读写
+//     不保存全部历史 → 不管客户端提交多少次，内存占用都有上限
+#[derive(Default)]
+pub struct ScoreBoard {
+    scores: Mutex<Vec<Score>>,
+}
+
+// ========== 2. 排行榜操作 ==========
+impl ScoreBoard {
+    // 2.1 插入后排序再截断，都在同一把锁里完成；
+    //     分数从高到低（Reverse）；sort_by_key 是稳定排序 → 同分时先提交的排在前面
+    pub fn submit(&self, score: Score) {
+        let mut scores = self.scores.lock().unwrap();
+        scores.push(score);
+        scores.sort_by_key(|s| Reverse(s.score));
+        scores.truncate(LEADERBOARD_SIZE);
+    }
+
+    // 2.1.1 已经排好序，直接取前 limit 条
+    pub fn top(&self, limit: usize) -> Vec<Score> {
+        let scores = self.scores.lock().unwrap();
+        scores.iter().take(limit).cloned().collect()
+    }
+}
+
+// 2.2 校验：名字去掉首尾空白后不能为空、不能太长；分数不能是负数
+fn validate(score: &Score) -> Result<Score, MyErrorNew> {
+    let name = score.name.trim();
+    if name.is_empty() {
+        return Err(MyErrorNew::ValidationError("name must not be empty".into()));
+    }
+    if name.chars().count() > NAME_MAX_CHARS {
+        return Err(MyErrorNew::ValidationError(format!(
+            "name must be at most {} characters",
+            NAME_MAX_CHARS
+        )));
+    }
+    if score.score < 0 {
+        return Err(MyErrorNew::ValidationError(
+            "score must not be negative".into(),
+        ));
+    }
+    Ok(Score {
+        name: name.to_string(),
+        score: score.score,
+    })
+}
+
+// ========== 3. Handler ==========
+// 3.1 POST /scores：校验后记下，返回 201 Created + 保存的记录（名字已去掉首尾空白）
+pub async fn post_score(
+    board: web::Data<ScoreBoard>,
+    new_score: web::Json<Score>,
+) -> Result<HttpResponse, MyErrorNew> {
+    let score = validate(&new_score)?;
+    board.submit(score.clone());
+    Ok(HttpResponse::Created().json(score))
+}
+
+// 3.2 GET /scores：排行榜
+pub async fn get_scores(board: web::Data<ScoreBoard>) -> HttpResponse {
+    HttpResponse::Ok().json(board.top(LEADERBOARD_SIZE))
+}
+
+// ========== 4. 单元测试 ==========
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::StatusCode, test};
+
+    // 4.1 提交几条分数后，GET 按分数从高到低返回，同分先到先得，最多 LEADERBOARD_SIZE 条
+    #[actix_web::test]
+    async fn scores_are_listed_highest_first() {
+        let board = web::Data::new(ScoreBoard::default());
+        let app = test::init_service(
+            App::new()
+                .app_data(board.clone())
+                .configure(crate::routers::score_routes),
+        )
+        .await;
+
+        for (name, score) in [("alice", 3), ("bob", 12), ("carol", 7), ("dave", 12)] {
+            let req = test::TestRequest::post()
+                .uri("/scores")
+                .set_json(serde_json::json!({ "name": name, "score": score }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+        }
+
+        let req = test::TestRequest::get().uri("/scores").to_request();
+        let leaderboard: Vec<Score> = test::call_and_read_body_json(&app, req).await;
+        let names: Vec<&str> = leaderboard.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["bob", "dave", "carol", "alice"]);
+
+        for i in 0..LEADERBOARD_SIZE as i32 {
+            board.submit(Score {
+                name: format!("p{}", i),
+                score: 100 + i,
+            });
+        }
+        let req = test::TestRequest::get().uri("/scores").to_request();
+        let leaderboard: Vec<Score> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(leaderboard.len(), LEADERBOARD_SIZE);
+        assert_eq!(leaderboard[0].score, 100 + LEADERBOARD_SIZE as i32 - 1);
+    }
+
+    // 4.1.1 提交远多于 LEADERBOARD_SIZE 条 → 只留下最高的 LEADERBOARD_SIZE 条，低分直接丢掉
+    #[actix_web::test]
+    async fn board_keeps_only_the_best_scores() {
+        let board = ScoreBoard::default();
+        for i in 0..(LEADERBOARD_SIZE as i32 * 5) {
+            board.submit(Score {
+                name: format!("p{}", i),
+                score: i % 17,
+            });
+        }
+        assert_eq!(board.scores.lock().unwrap().len(), LEADERBOARD_SIZE);
+
+        let top = board.top(usize::MAX);
+        assert_eq!(top.len(), LEADERBOARD_SIZE);
+        assert!(top.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(top[0].score, 16);
+        // 比第 LEADERBOARD_SIZE 名还低的分数不会挤进来
+        board.submit(Score {
+            name: "late".into(),
+            score: 0,
+        });
+        assert!(board.top(LEADERBOARD_SIZE).iter().all(|s| s.name != "late"));
+    }
+
+    // 4.2 名字为空 / 分数为负 → 400，且不会进排行榜
+    #[actix_web::test]
+    async fn invalid_scores_are_rejected() {
+        let board = web::Data::new(ScoreBoard::default());
+        let app = test::init_service(
+            App::new()
+                .app_data(board.clone())
+                .configure(crate::routers::score_routes),
+        )
+        .await;
+
+        for body in [
+            serde_json::json!({ "name": "   ", "score": 5 }),
+            serde_json::json!({ "name": "eve", "score": -1 }),
+        ] {
+            let req = test::TestRequest::post()
+                .uri("/scores")
+                .set_json(body)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        }
+        assert!(board.top(LEADERBOARD_SIZE).is_empty());
+    }
+}
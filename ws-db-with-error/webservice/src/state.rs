@@ -1,8 +1,12 @@
 // 引入标准库中的 `Mutex` 类型。 /mju:teks/
 // `Mutex`（互斥锁）是一种用于在多线程环境中安全地共享和修改数据的同步原语。
 // 它确保同一时间只有一个线程可以访问被它保护的数据，从而避免数据竞争（data race）。
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::AtomicBool;
+use std::path::PathBuf;
+use std::time::Instant;
 use super::models::Course;  //需要在 teacher-service.rs 声明下mod 这里才能调用 否则报错
+use super::errors::MyErrorNew;
 use sqlx::postgres::PgPool;
 
 // 使用 `pub` 关键字声明一个公共的结构体 `AppState`。
@@ -27,6 +31,10 @@ pub struct AppState {
     // - `u32` 是基本类型，满足这些要求，因此 `Mutex<u32>` 可以安全地放在共享状态中。
     pub visit_count: Mutex<u32>,
 
+    // 访问计数是否存数据库（rust_test1.counters 的 visits 行，重启不清零）
+    // true 时 /health 先更新数据库，表不存在 / 查询失败才退回上面的内存计数
+    pub persistent_visits: bool,
+
     // 就是 “一个带锁的公共课程列表”——
     // Vec<Course> 是 真正的数据；Mutex 是 看门的大锁；pub 表示 谁都看得见；
     //| 片段            | 含义                            |
@@ -37,5 +45,93 @@ pub struct AppState {
     //| `Vec<Course>` | **动态数组**，里面存 **Course 结构体实例** |
     pub courses: Mutex<Vec<Course>>,
 
-    pub db: PgPool
+    // 数据库连接池：只设置一次（OnceLock），设置之后 handler 可以随便读，不用加锁
+    // 正常启动时连上数据库才建 AppState，一开始就有值；
+    // start_without_db 模式下一开始是空的，后台任务连上并跑完迁移后才 set 进来
+    // handler 统一通过 db() 取，还没连上 → 503
+    pub db: OnceLock<PgPool>,
+
+    // 就绪标志：/readyz 读它决定是否接流量
+    // 启动（迁移跑完）后置 true；start_without_db 模式下等后台连上数据库才置 true；
+    // 收到 Ctrl+C / SIGTERM 时置 false，
+    // 负载均衡看到 503 就不再转发新请求，已有请求慢慢排空
+    // AtomicBool 无需加锁，信号任务和 handler 线程可同时读写
+    pub ready: AtomicBool,
+
+    // 审计日志文件（环境变量 AUDIT_LOG），None = 不记录，见 audit.rs
+    pub audit_log: Option<PathBuf>,
+
+    // 进程启动时刻：/info 用 started_at.elapsed() 算运行时长
+    // Instant 是单调时钟，不受系统改时间影响（算时长用它，不用 Utc::now()）
+    pub started_at: Instant,
+}
+
+// 取数据库连接池：还没连上（start_without_db 模式启动时数据库不可用）
+// → ServiceBusy（503 + Retry-After），客户端稍后重试即可
+impl AppState {
+    pub fn db(&self) -> Result<&PgPool, MyErrorNew> {
+        self.db
+            .get()
+            .ok_or_else(|| MyErrorNew::ServiceBusy("database not connected yet".into()))
+    }
+}
+
+// 测试专用构造函数：只需要传入连接池，其余字段填默认值
+// （健康检查回 "OK"、计数 0、空课程表）
+// 测试里不再手写每个字段 → AppState 以后加字段，只需要改这一处
+#[cfg(test)]
+impl AppState {
+    pub fn for_test(db: PgPool) -> Self {
+        let state = AppState::for_test_without_db();
+        state.db.set(db).unwrap();
+        state
+    }
+
+    // 连接池是空的：只测内存数据的测试用它（没有 Postgres 也能跑）；
+    // 也用来模拟"启动时数据库不可用"，之后可以再 db.set(..)
+    pub fn for_test_without_db() -> Self {
+        AppState {
+            health_check_response: "OK".to_string(),
+            visit_count: Mutex::new(0),
+            persistent_visits: false,
+            courses: Mutex::new(vec![]),
+            db: OnceLock::new(),
+            ready: AtomicBool::new(true),
+            audit_log: None,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+// 测试专用：拿一个数据库连接池，拿不到就返回 None
+// 没配 DATABASE_URL 或连不上 → 打印跳过原因，测试自己 return（不 panic）
+// → 没有 Postgres 的机器（CI）上 cargo test 照样全绿，有数据库时 DB 测试照常跑
+// 用法：let Some(db_pool) = maybe_pool().await else { return; };
+#[cfg(test)]
+pub async fn maybe_pool() -> Option<PgPool> {
+    maybe_pool_with(sqlx::postgres::PgPoolOptions::new()).await
+}
+
+// 需要特殊连接池配置（比如只给 1 个连接）的测试用这个
+#[cfg(test)]
+pub async fn maybe_pool_with(options: sqlx::postgres::PgPoolOptions) -> Option<PgPool> {
+    dotenv::dotenv().ok();
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        eprintln!("skipping DB test: DATABASE_URL is not set");
+        return None;
+    };
+    // 先用单个连接探一下：连不上立刻报错
+    // （直接建连接池的话，sqlx 会一直重试到 acquire_timeout，默认 30 秒）
+    use sqlx::Connection;
+    if let Err(err) = sqlx::PgConnection::connect(&database_url).await {
+        eprintln!("skipping DB test: cannot connect to database: {}", err);
+        return None;
+    }
+    match options.connect(&database_url).await {
+        Ok(pool) => Some(pool),
+        Err(err) => {
+            eprintln!("skipping DB test: cannot connect to database: {}", err);
+            None
+        }
+    }
 }
@@ -57,6 +57,12 @@ use routers::*;
 // 从 `state` 模块中导入 `AppState` 类型，用于构建应用的共享状态。
 use state::AppState;
 
+// ====== 监听地址 ======
+// BIND_ADDR 的读取和校验三个 ws* 项目共用一份，见 ws-shared/bind_addr.rs
+#[path = "../../../../ws-shared/bind_addr.rs"]
+mod bind_addr;
+use bind_addr::bind_addr;
+
 // `#[actix_web::main]` 是 Actix Web 提供的宏，用于将 `async fn main` 转换为
 // 基于 Tokio 异步运行时的入口点。没有它，Rust 不允许 `main` 函数是异步的。
 #[actix_web::main]
@@ -98,9 +104,17 @@ async fn main() -> io::Result<()> {
             .configure(course_routes)
     };
 
+    // 解析监听地址，启动前打印出来方便确认
+    let addr = bind_addr("127.0.0.1:3339")?;
+    println!("teacher-service listening on http://{}", addr);
+
     // 启动 HTTP 服务器：
     // 1. `HttpServer::new(app)`：传入上面定义的应用工厂闭包；
-    // 2. `.bind("127.0.0.1:3339")?`：尝试绑定到本地 3339 端口，若失败则返回错误（`?` 传播）；
+    // 2. `.bind(addr)?`：绑定到 BIND_ADDR（默认 127.0.0.1:3339），失败时带上地址返回错误（`?` 传播）；
     // 3. `.run().await`：异步启动服务器并阻塞等待其结束（通常直到 Ctrl+C 终止）。
-    HttpServer::new(app).bind("127.0.0.1:3339")?.run().await
+    HttpServer::new(app)
+        .bind(addr)
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to bind {}: {}", addr, e)))?
+        .run()
+        .await
 }
\ No newline at end of file
@@ -39,20 +39,14 @@ pub async fn new_course(
     println!("Received new course");
 
     // 3.3 计算同一老师的已有课程数（用于生成自增 ID）
-    //     clone() 会把整表复制一份 → O(n) 内存，测试可接受；
-    //     生产环境建议 iter() + count()，避免整表克隆
-    let course_count = app_state
-        .courses
-        .lock()
-        .unwrap()
-        .iter() // 只读迭代，无克隆
-        .filter(|course| course.teacher_id == new_course.teacher_id)
-        .count();
+    //     不克隆整表，只读迭代计数（对比测试见 5.8）
+    let course_count =
+        count_courses_for_teacher(&app_state.courses.lock().unwrap(), new_course.teacher_id);
 
     // 3.4 构建新 Course；id 用 count+1 模拟自增，time 用当前 UTC
     let new_course = Course {
         teacher_id: new_course.teacher_id,
-        id: 2,         // 自增 ID
+        id: course_count as i32 + 1, // 自增 ID
         name: new_course.name.clone(),      // 克隆字段，避免 move
         time: Some(Utc::now().naive_utc()), // 时间戳
     };
@@ -64,6 +58,16 @@ pub async fn new_course(
     HttpResponse::Ok().body("course add")
 }
 
+// 3.7 统计某个老师的课程数
+//     iter() 只借用每个元素、比较 teacher_id → 不分配内存，O(n) 时间、O(1) 额外空间
+//     对比写法：courses.clone() 再过滤 → 要把 n 个 Course（连同每个 name 字符串）复制一遍
+fn count_courses_for_teacher(courses: &[Course], teacher_id: i32) -> usize {
+    courses
+        .iter() // 只读迭代，无克隆
+        .filter(|course| course.teacher_id == teacher_id)
+        .count()
+}
+
 pub async fn new_course_handle_db(
     new_course: web::Json<Course>,  // 3.1 请求体自动反序列化成 Course
     app_state: web::Data<AppState>, // 3.2 共享状态，内部是 Arc<AppState>
@@ -78,7 +82,7 @@ pub async fn get_courses_for_teacher(
     app_state: web::Data<AppState>,
     params: web::Path<(i32, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
 ) -> HttpResponse {
-    // 4.2 解压元组 → (usize, String)
+    // 4.2 解压元组 → (i32, String)
     let (teacher_id, _name) = params.into_inner();
 
     // 4.3 只读过滤：iter() 不克隆，filter 后 cloned() 把匹配项复制出来
@@ -101,17 +105,16 @@ pub async fn get_courses_for_teacher(
 
 pub async fn get_courses_for_teacher_handle_db(
     app_state: web::Data<AppState>,
-    params: web::Path<(usize, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
+    params: web::Path<(i32, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
 ) -> HttpResponse {
-    // 4.2 解压元组 → (usize, String)
-    let teacher_id = i32::try_from(params.0).unwrap();
+    // 4.2 路径参数直接按 i32 提取（与数据库 INTEGER 一致），非数字由 actix 返回 404
+    let teacher_id = params.0;
     let courses = get_courses_for_teacher_db(&app_state.db, teacher_id).await;
     HttpResponse::Ok().json(courses)
 }
 
-pub async fn get_course_detail_handle_db(app_state: web::Data<AppState>, params: web::Path<(usize, usize)>) -> HttpResponse {
-    let teacher_id = i32::try_from(params.0).unwrap();
-    let course_id = i32::try_from(params.1).unwrap();
+pub async fn get_course_detail_handle_db(app_state: web::Data<AppState>, params: web::Path<(i32, i32)>) -> HttpResponse {
+    let (teacher_id, course_id) = params.into_inner();
     let course = get_course_detail_db(&app_state.db, teacher_id, course_id).await;
     HttpResponse::Ok().json(course)
 }
@@ -120,19 +123,16 @@ pub async fn get_course_detail_handle_db(app_state: web::Data<AppState>, params:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::maybe_pool; // 连不上数据库 → 跳过 DB 测试
     use actix_web::{App, http::StatusCode};
-    use std::sync::Mutex;
-    use dotenv::dotenv;  // test里面新增
-    use std::env;
-    use sqlx::postgres::PgPoolOptions;
 
     // 5.1 测试：POST /courses 成功创建
     #[actix_web::test]
     async fn post_course_test() {
 
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
 
         // 5.2 造请求体
         let course = web::Json(Course {
@@ -143,12 +143,7 @@ mod tests {
         });
 
         // 5.3 造空全局状态
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]),
-            db: db_pool
-        });
+        let app_state = web::Data::new(AppState::for_test(db_pool));
 
         // 5.4 直接调处理器（绕过 HTTP 层，速度最快）
         let resp = new_course(course, app_state).await;
@@ -166,9 +161,9 @@ mod tests {
     #[actix_web::test]
     async fn post_course_test_db() {
 
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
 
         // 5.2 造请求体
         let course = web::Json(Course {
@@ -179,12 +174,7 @@ mod tests {
         });
 
         // 5.3 造空全局状态
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]),
-            db: db_pool
-        });
+        let app_state = web::Data::new(AppState::for_test(db_pool));
 
         // 5.4 直接调处理器（绕过 HTTP 层，速度最快）
         let resp = new_course_handle_db(course, app_state).await;
@@ -205,15 +195,10 @@ mod tests {
     // 5.6 测试：GET /courses/{teacher_id}/{name} 空结果
     #[actix_web::test]
     async fn get_course_test() {
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DatabaseUrl not found");
-        let db_pool = PgPoolOptions::new().connect(&database_url).await.unwrap();
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]), // 空表 → 应返回 []
-            db: db_pool
-        });
+        let Some(db_pool) = maybe_pool().await else {
+            return;
+        };
+        let app_state = web::Data::new(AppState::for_test(db_pool));
 
         // 5.7 构造双段路径
         let params = web::Path::from((1, "asdf".to_string()));
@@ -225,4 +210,57 @@ mod tests {
         let body: Vec<Course> = serde_json::from_slice(&bytes).unwrap();
         assert!(body.is_empty());
     }
+
+    // 5.8 微基准：克隆整表再数 vs 只读迭代计数
+    //     两种写法结果必须一致；耗时打印出来对比（cargo test -- --nocapture 可见）
+    //     不断言谁更快：CI 机器负载不稳定，时间断言容易误报
+    fn count_by_clone(courses: &[Course], teacher_id: i32) -> usize {
+        let all: Vec<Course> = courses.to_vec(); // 整表复制
+        all.into_iter()
+            .filter(|course| course.teacher_id == teacher_id)
+            .collect::<Vec<Course>>()
+            .len()
+    }
+
+    #[test]
+    fn count_by_iter_matches_clone_and_is_measured() {
+        use std::time::Instant;
+
+        let courses: Vec<Course> = (0..200_000)
+            .map(|i| Course {
+                teacher_id: i % 100,
+                id: i,
+                name: format!("course {}", i),
+                time: None,
+            })
+            .collect();
+
+        for teacher_id in [0, 42, 99, 1000] {
+            assert_eq!(
+                count_courses_for_teacher(&courses, teacher_id),
+                count_by_clone(&courses, teacher_id)
+            );
+        }
+        assert_eq!(count_courses_for_teacher(&courses, 42), 2_000);
+
+        const ROUNDS: u32 = 5;
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            std::hint::black_box(count_by_clone(&courses, std::hint::black_box(42)));
+        }
+        let clone_elapsed = start.elapsed() / ROUNDS;
+
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            std::hint::black_box(count_courses_for_teacher(&courses, std::hint::black_box(42)));
+        }
+        let iter_elapsed = start.elapsed() / ROUNDS;
+
+        println!(
+            "{} courses: clone + filter {:?}, iter + count {:?}",
+            courses.len(),
+            clone_elapsed,
+            iter_elapsed
+        );
+    }
 }
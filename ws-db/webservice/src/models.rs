@@ -21,8 +21,8 @@ use serde::{Deserialize, Serialize};
 // - `Clone`：允许复制整个结构体（因为所有字段都实现了 Clone）
 //
 // 设计说明：
-// - `teacher_id` 是必填项（usize），表示所属教师
-// - `id` 是可选项（Option<usize>），因为新建课程时数据库尚未分配 ID
+// - `teacher_id` 是必填项（i32 ↔ 数据库 INTEGER），表示所属教师
+// - `id`（i32）由数据库自增生成，新建课程时传入的值会被忽略
 // - `name` 是课程名称，必填（String）
 // - `time` 是创建/更新时间，可为空（Option<NaiveDateTime>），兼容数据库 NULL
 #[derive(Deserialize, Serialize, Debug, Clone)]
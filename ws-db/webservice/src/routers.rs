@@ -55,7 +55,7 @@ pub fn course_routes(cfg: &mut web::ServiceConfig) {
             // 注册 GET /courses/{user_id} 路由
             // - 路径：`/{user_id}`（完整路径为 `/courses/{user_id}`）
             // - HTTP 方法：GET（通过 `web::get()` 指定）
-            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<usize>`）
+            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<i32>`）
             // - 处理函数：`get_courses_for_teacher`，用于根据教师 ID 查询其所有课程
             .route("/{user_id}/{name}", web::get().to(get_courses_for_teacher))
 
@@ -39,3 +39,51 @@ pub struct AppState {
 
     pub db: PgPool
 }
+
+// 测试专用构造函数：只需要传入连接池，其余字段填默认值
+// （健康检查回 "OK"、计数 0、空课程表）
+// 测试里不再手写每个字段 → AppState 以后加字段，只需要改这一处
+#[cfg(test)]
+impl AppState {
+    pub fn for_test(db: PgPool) -> Self {
+        AppState {
+            health_check_response: "OK".to_string(),
+            visit_count: Mutex::new(0),
+            courses: Mutex::new(vec![]),
+            db,
+        }
+    }
+}
+
+// 测试专用：拿一个数据库连接池，拿不到就返回 None
+// 没配 DATABASE_URL 或连不上 → 打印跳过原因，测试自己 return（不 panic）
+// → 没有 Postgres 的机器（CI）上 cargo test 照样全绿，有数据库时 DB 测试照常跑
+// 用法：let Some(db_pool) = maybe_pool().await else { return; };
+#[cfg(test)]
+pub async fn maybe_pool() -> Option<PgPool> {
+    maybe_pool_with(sqlx::postgres::PgPoolOptions::new()).await
+}
+
+// 需要特殊连接池配置（比如只给 1 个连接）的测试用这个
+#[cfg(test)]
+pub async fn maybe_pool_with(options: sqlx::postgres::PgPoolOptions) -> Option<PgPool> {
+    dotenv::dotenv().ok();
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        eprintln!("skipping DB test: DATABASE_URL is not set");
+        return None;
+    };
+    // 先用单个连接探一下：连不上立刻报错
+    // （直接建连接池的话，sqlx 会一直重试到 acquire_timeout，默认 30 秒）
+    use sqlx::Connection;
+    if let Err(err) = sqlx::PgConnection::connect(&database_url).await {
+        eprintln!("skipping DB test: cannot connect to database: {}", err);
+        return None;
+    }
+    match options.connect(&database_url).await {
+        Ok(pool) => Some(pool),
+        Err(err) => {
+            eprintln!("skipping DB test: cannot connect to database: {}", err);
+            None
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// ====== 监听地址（ws / ws-db / ws-db-with-error 共用） ======
+// 不是单独的 crate：各个 bin 用 #[path = "../../../../ws-shared/bind_addr.rs"] mod bind_addr; 把这个文件当模块引入
+// 读环境变量 BIND_ADDR（容器里常用 0.0.0.0:8080），没设置就用默认地址；
+// 先 parse 成 SocketAddr 校验格式：写错了启动时就给出清楚的错误，而不是等 bind 时才失败
+use std::env;
+use std::io;
+use std::net::SocketAddr;
+
+pub fn bind_addr(default: &str) -> io::Result<SocketAddr> {
+    let raw = env::var("BIND_ADDR").unwrap_or_else(|_| default.to_string());
+    raw.parse().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid BIND_ADDR {:?}: {} (expected ip:port, e.g. 0.0.0.0:8080)", raw, e),
+        )
+    })
+}
@@ -23,6 +23,12 @@ pub async fn health_check_handler() -> impl Responder {
     HttpResponse::Ok().json("httpserver is running")
 }
 
+// ====== 监听地址 ======
+// BIND_ADDR 的读取和校验三个 ws* 项目共用一份，见 ws-shared/bind_addr.rs
+#[path = "../../../../ws-shared/bind_addr.rs"]
+mod bind_addr;
+use bind_addr::bind_addr;
+
 // ====== 入口：main ======
 //顶级目录 执行 cargo run -p webservice --bin=server1 
 //平级目录 webservice目录执行 cargo run --bin=server1
@@ -39,11 +45,16 @@ async fn main() -> io::Result<()> {
     // move 捕获空环境，保证闭包 Send + 'static
     let app = move || App::new().configure(general_routes);
 
+    // 解析监听地址（默认 127.0.0.1:9919），启动前打印出来方便确认
+    let addr = bind_addr("127.0.0.1:9919")?;
+    println!("server1 listening on http://{}", addr);
+
     // HttpServer 是 tokio 上的异步 TCP 服务器；new(app) 把工厂传进去
     // .bind() 返回 Result，? 把绑定失败（端口被占等）向上抛
     // .run() 生成 *Server* future；await 让它一直监听，直到进程被杀
     HttpServer::new(app)
-        .bind("127.0.0.1:9919")? // 监听本地 9919，成功则返回 Server，失败早抛
+        .bind(addr) // 监听解析好的地址，成功则返回 Server
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to bind {}: {}", addr, e)))? // 失败时带上地址再抛
         .run() // 启动 tokio task，开始 accept 连接
         .await // 阻塞在这里，永不返回（除非出错）
 }
@@ -48,12 +48,60 @@ mod models;
 use routers::*;
 
 // 从 `state` 模块中导入 `AppState` 类型，用于构建应用的共享状态。
-use state::AppState;
+use state::{AppState, CourseStore};
+
+use models::Course;
+
+// 读取种子数据文件（JSON 数组），作为服务启动时的初始课程列表。
+// - 文件不存在 / 内容为空：返回空 Vec，不算错误（方便第一次启动）
+// - JSON 格式错误：返回 InvalidData 错误，错误信息里带上文件路径，启动直接失败
+fn load_seed_courses(path: &str) -> io::Result<Vec<Course>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+    if content.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    serde_json::from_str(&content).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("seed file {} is not a valid course JSON array: {}", path, e),
+        )
+    })
+}
+
+// ====== 路由装配 ======
+// main 里的 App 工厂和集成测试共用这一个函数 → 测试跑的就是线上同一套路由
+fn configure_app(cfg: &mut web::ServiceConfig) {
+    // 调用 `general_routes` 等函数来批量注册路由（这些函数在 `routers.rs` 中定义）
+    cfg.configure(general_routes)
+        .configure(course_routes)
+        .configure(teacher_routes);
+}
+
+// ====== 监听地址 ======
+// BIND_ADDR 的读取和校验三个 ws* 项目共用一份，见 ws-shared/bind_addr.rs
+#[path = "../../../../ws-shared/bind_addr.rs"]
+mod bind_addr;
+use bind_addr::bind_addr;
 
 // `#[actix_web::main]` 是 Actix Web 提供的宏，用于将 `async fn main` 转换为
 // 基于 Tokio 异步运行时的入口点。没有它，Rust 不允许 `main` 函数是异步的。
 #[actix_web::main]
 async fn main() -> io::Result<()> {
+    // 可选：通过环境变量 SEED_FILE 指定种子数据文件（如 courses.json），
+    // 重启服务后仍能得到同一份开发数据；未设置时从空列表开始
+    let seed_courses = match std::env::var("SEED_FILE") {
+        Ok(path) => {
+            let courses = load_seed_courses(&path).inspect_err(|e| eprintln!("启动失败：{}", e))?;
+            println!("已从 {} 加载 {} 门课程", path, courses.len());
+            courses
+        }
+        Err(_) => vec![],
+    };
+
     // 创建应用的全局共享状态实例，并用 `web::Data::new()` 包装。
     // `web::Data<T>` 是 Actix Web 提供的线程安全共享容器（内部基于 Arc），
     // 允许多个 handler 安全地读取或修改该状态。
@@ -67,7 +115,7 @@ async fn main() -> io::Result<()> {
             //let v1 = vec![];        // 宏展开 = Vec::new() 一样快
             //let v2 = Vec::new();    // 直接空 Vec
             //Rust 里根本没有 vec[] 这种写法，只有vec![] 和 Vec::new()
-            courses: Mutex::new(vec![])
+            courses: Mutex::new(CourseStore::new(seed_courses))
         }
     );
 
@@ -79,14 +127,92 @@ async fn main() -> io::Result<()> {
         App::new()
             // 将共享状态 `share_data` 注册到应用中，使所有 handler 都能通过参数注入访问它
             .app_data(share_data.clone())
-            // 调用 `general_routes` 函数来批量注册路由（该函数应在 `routers.rs` 中定义）
-            .configure(general_routes)
-            .configure(course_routes)
+            // 注册全部路由（见上面的 configure_app）
+            .configure(configure_app)
     };
 
+    // 解析监听地址，启动前打印出来方便确认
+    let addr = bind_addr("127.0.0.1:3339")?;
+    println!("teacher-service listening on http://{}", addr);
+
     // 启动 HTTP 服务器：
     // 1. `HttpServer::new(app)`：传入上面定义的应用工厂闭包；
-    // 2. `.bind("127.0.0.1:3339")?`：尝试绑定到本地 3339 端口，若失败则返回错误（`?` 传播）；
+    // 2. `.bind(addr)?`：绑定到 BIND_ADDR（默认 127.0.0.1:3339），失败时带上地址返回错误（`?` 传播）；
     // 3. `.run().await`：异步启动服务器并阻塞等待其结束（通常直到 Ctrl+C 终止）。
-    HttpServer::new(app).bind("127.0.0.1:3339")?.run().await
-}
\ No newline at end of file
+    HttpServer::new(app)
+        .bind(addr)
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to bind {}: {}", addr, e)))?
+        .run()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test as actix_test;
+
+    // 在系统临时目录下写一个测试文件，返回路径
+    fn write_temp(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn seed_missing_or_empty_file_is_empty_list() {
+        assert!(load_seed_courses("/definitely/not/here/courses.json").unwrap().is_empty());
+        let path = write_temp("ws_seed_empty.json", "  \n");
+        assert!(load_seed_courses(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn seed_loads_courses_and_rejects_malformed_json() {
+        let path = write_temp(
+            "ws_seed_ok.json",
+            r#"[{"teacher_id":1,"id":1,"name":"rust","time":null}]"#,
+        );
+        let courses = load_seed_courses(&path).unwrap();
+        assert_eq!(courses.len(), 1);
+        assert_eq!(courses[0].name, "rust");
+
+        let path = write_temp("ws_seed_bad.json", "[{not json");
+        let err = load_seed_courses(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    // ====== 集成测试：启动完整的 App（路由 + 提取器），用 HTTP 请求驱动 ======
+    // 处理器单测绕过了路由层；这里走真实路径，路径写错、参数类型不匹配都会暴露出来
+    #[actix_web::test]
+    async fn app_routes_create_list_and_404() {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(AppState::for_test_inmem()))
+                .configure(configure_app),
+        )
+        .await;
+
+        // 新建：POST /courses/，JSON 请求体
+        let req = actix_test::TestRequest::post()
+            .uri("/courses/")
+            .set_json(serde_json::json!({"teacher_id": 1, "name": "rust"}))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body: String = actix_test::read_body_json(resp).await;
+        assert_eq!(body, "course add");
+
+        // 列表：GET /courses/{teacher_id}/{name}，刚建的课程能查到，id 由服务器生成
+        let req = actix_test::TestRequest::get().uri("/courses/1/rust").to_request();
+        let courses: Vec<Course> = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(courses.len(), 1);
+        assert_eq!((courses[0].teacher_id, courses[0].id), (1, Some(1)));
+        assert_eq!(courses[0].name, "rust");
+
+        // 404：未注册的路径；teacher_id 不是数字时 Path 提取失败也是 404
+        for uri in ["/nope", "/courses/abc/rust"] {
+            let req = actix_test::TestRequest::get().uri(uri).to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND, "{}", uri);
+        }
+    }
+}
@@ -5,9 +5,11 @@
 //想持久 → 都落盘（DB/Redis）；想共享 → 用进程外存储。
 // ========== 1. 依赖与模块导入 ==========
 use super::state::AppState; // 全局共享状态（带锁的容器）
-use crate::models::Course; // 我们自己的课程结构体
+use crate::models::{Course, CourseDto, RecentQuery, TeacherSummary}; // 我们自己的课程结构体
 use actix_web::{HttpResponse, web}; // Web 框架核心类型
 use chrono::Utc; // 时间戳生成器（UTC 时间）
+use std::cmp::Reverse; // 反转排序方向（降序）
+use std::collections::BTreeMap; // 有序 Map，按 key 排序
 use actix_web::body::MessageBody; //try_into_bytes 是 MessageBody 的方法 → 先 use actix_web::body::MessageBody; 再 .into_body().try_into_bytes()”
 
 // ========== 2. 健康检查 ==========
@@ -37,47 +39,41 @@ pub async fn new_course(
 ) -> HttpResponse {
     println!("Received new course");
 
-    // 3.3 计算同一老师的已有课程数（用于生成自增 ID）
-    //     clone() 会把整表复制一份 → O(n) 内存，测试可接受；
-    //     生产环境建议 iter() + count()，避免整表克隆
-    let course_count = app_state
-        .courses
-        .lock()
-        .unwrap()
-        .iter() // 只读迭代，无克隆
-        .filter(|course| course.teacher_id == new_course.teacher_id)
-        .count();
+    // 3.3 "数课程数 + 插入"只加一次锁 → 两个并发请求不会拿到同一个 ID
+    let mut courses = app_state.courses.lock().unwrap();
 
-    // 3.4 构建新 Course；id 用 count+1 模拟自增，time 用当前 UTC
+    // 3.4 计算同一老师的已有课程数（用于生成自增 ID），走按老师的索引，不扫全表
+    let course_count = courses.for_teacher(new_course.teacher_id).count();
+
+    // 3.5 构建新 Course；id 用 count+1 模拟自增，time 用当前 UTC
     let new_course = Course {
         teacher_id: new_course.teacher_id,
-        id: Some(course_count + 1),         // 自增 ID
+        id: Some(course_count as i32 + 1),  // 自增 ID
         name: new_course.name.clone(),      // 克隆字段，避免 move
         time: Some(Utc::now().naive_utc()), // 时间戳
     };
 
-    // 3.5 再次加锁，把新课程 push 进 Vec
-    app_state.courses.lock().unwrap().push(new_course);
+    // 3.6 push 会同步更新索引；guard 离开作用域时自动解锁
+    courses.push(new_course);
 
-    // 3.6 返回简单文本 JSON（生产建议给结构化对象）
+    // 3.7 返回简单文本 JSON（生产建议给结构化对象）
     HttpResponse::Ok().json("course add")
 }
 
 // ========== 4. 根据老师 ID 查课程 ==========
 pub async fn get_courses_for_teacher(
     app_state: web::Data<AppState>,
-    params: web::Path<(usize, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
+    params: web::Path<(i32, String)>, // 4.1 路径参数：/courses/{teacher_id}/{name}
 ) -> HttpResponse {
-    // 4.2 解压元组 → (usize, String)
+    // 4.2 解压元组 → (i32, String)
     let (teacher_id, _name) = params.into_inner();
 
-    // 4.3 只读过滤：iter() 不克隆，filter 后 cloned() 把匹配项复制出来
+    // 4.3 按老师的索引取课程：只访问这个老师的课程，cloned() 把它们复制出来
     let filtered_courses = app_state
         .courses
         .lock()
         .unwrap()
-        .iter()
-        .filter(|course| course.teacher_id == teacher_id)
+        .for_teacher(teacher_id)
         .cloned() // Course 需实现 Clone
         .collect::<Vec<Course>>();
 
@@ -89,11 +85,126 @@ pub async fn get_courses_for_teacher(
     }
 }
 
-// ========== 5. 单元测试 ==========
+// ========== 5. 导出全部课程 ==========
+// 调试用：把内存里的整张课程表原样序列化成 JSON 数组
+pub async fn export_courses(app_state: web::Data<AppState>) -> HttpResponse {
+    // 5.1 加锁后直接序列化 Vec<Course>；time 字段按 chrono 的 serde 格式输出
+    let courses = app_state.courses.lock().unwrap();
+    HttpResponse::Ok().json(courses.all())
+}
+
+// ========== 6. 导入课程（整表替换） ==========
+// 调试用：用请求体里的数组替换整张课程表
+pub async fn import_courses(
+    courses: web::Json<Vec<Course>>, // 6.1 请求体反序列化成 Vec<Course>
+    app_state: web::Data<AppState>,
+) -> HttpResponse {
+    let courses = courses.into_inner();
+
+    // 6.2 先逐条校验，全部通过才替换 → 任意一条不合法时原数据保持不变（原子性）
+    for (index, course) in courses.iter().enumerate() {
+        if let Err(reason) = course.validate_name() {
+            return HttpResponse::BadRequest().json(format!("course #{}: {}", index, reason));
+        }
+    }
+
+    // 6.3 整表替换（索引一起重建）；旧 Vec 在这里被 drop
+    let count = courses.len();
+    app_state.courses.lock().unwrap().replace_all(courses);
+
+    HttpResponse::Ok().json(format!("imported {} courses", count))
+}
+
+// ========== 7. 按老师统计课程数 ==========
+pub async fn get_teacher_summary(app_state: web::Data<AppState>) -> HttpResponse {
+    // 7.1 fold 进 BTreeMap：key 天然有序 → 结果自动按 teacher_id 升序
+    let counts = app_state
+        .courses
+        .lock()
+        .unwrap()
+        .all()
+        .iter()
+        .fold(BTreeMap::new(), |mut counts, course| {
+            *counts.entry(course.teacher_id).or_insert(0) += 1;
+            counts
+        });
+
+    // 7.2 转成 [{teacher_id, course_count}]
+    let summary = counts
+        .into_iter()
+        .map(|(teacher_id, course_count)| TeacherSummary {
+            teacher_id,
+            course_count,
+        })
+        .collect::<Vec<TeacherSummary>>();
+
+    HttpResponse::Ok().json(summary)
+}
+
+// ========== 8. 最近创建的课程 ==========
+// GET /courses/recent?limit=10 → 所有老师的课程按 time 降序，最多 limit 条
+pub async fn get_recent_courses(
+    app_state: web::Data<AppState>,
+    query: web::Query<RecentQuery>,
+) -> HttpResponse {
+    // 8.1 克隆一份再排序，锁只在 clone 这一行持有
+    let mut courses = app_state.courses.lock().unwrap().all().to_vec();
+
+    // 8.2 Reverse 把升序翻成降序；Option 里 None < Some → 没有时间的课程自然排到最后
+    courses.sort_by_key(|course| Reverse(course.time));
+    courses.truncate(query.limit() as usize);
+
+    HttpResponse::Ok().json(courses)
+}
+
+// ========== 9. 获取单门课程 ==========
+// GET /courses/detail/{teacher_id}/{course_id}
+pub async fn get_course_detail(
+    app_state: web::Data<AppState>,
+    params: web::Path<(i32, i32)>,
+) -> HttpResponse {
+    let (teacher_id, course_id) = params.into_inner();
+
+    // 9.1 只在这个老师的课程里找；找到就 clone 出来，锁在这条语句结束时释放
+    let course = app_state
+        .courses
+        .lock()
+        .unwrap()
+        .for_teacher(teacher_id)
+        .find(|course| course.id == Some(course_id))
+        .cloned();
+
+    match course {
+        Some(course) => HttpResponse::Ok().json(course),
+        None => HttpResponse::NotFound().json("course not found"),
+    }
+}
+
+// ========== 10. 按老师查课程（DTO 版） ==========
+// GET /courses/dto/{teacher_id} → 和第 4 节一样的数据，但用 CourseDto 输出（camelCase、省略 null）
+pub async fn get_courses_for_teacher_dto(
+    app_state: web::Data<AppState>,
+    teacher_id: web::Path<i32>,
+) -> HttpResponse {
+    // 10.1 克隆出来后再转换，锁在这条语句结束时释放
+    let courses = app_state
+        .courses
+        .lock()
+        .unwrap()
+        .for_teacher(teacher_id.into_inner())
+        .cloned()
+        .map(CourseDto::from)
+        .collect::<Vec<CourseDto>>();
+
+    HttpResponse::Ok().json(courses)
+}
+
+// ========== 11. 单元测试 ==========
 #[cfg(test)]
 mod tests {
     use super::*;
-    use actix_web::{App, http::StatusCode};
+    use crate::state::CourseStore;
+    use actix_web::{App, http::StatusCode, test};
     use std::sync::Mutex;
 
     // 5.1 测试：POST /courses 成功创建
@@ -108,11 +219,7 @@ mod tests {
         });
 
         // 5.3 造空全局状态
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]),
-        });
+        let app_state = web::Data::new(AppState::for_test_inmem());
 
         // 5.4 直接调处理器（绕过 HTTP 层，速度最快）
         let resp = new_course(course, app_state).await;
@@ -131,14 +238,10 @@ mod tests {
     // 5.6 测试：GET /courses/{teacher_id}/{name} 空结果
     #[actix_web::test]
     async fn get_course_test() {
-        let app_state = web::Data::new(AppState {
-            health_check_response: "OK".to_string(),
-            visit_count: Mutex::new(0),
-            courses: Mutex::new(vec![]), // 空表 → 应返回 []
-        });
+        let app_state = web::Data::new(AppState::for_test_inmem());
 
         // 5.7 构造双段路径
-        let params = web::Path::from((1usize, "asdf".to_string()));
+        let params = web::Path::from((1, "asdf".to_string()));
         let response = get_courses_for_teacher(app_state, params).await;
 
         assert_eq!(response.status(), StatusCode::OK);
@@ -147,4 +250,182 @@ mod tests {
         let body: Vec<Course> = serde_json::from_slice(&bytes).unwrap();
         assert!(body.is_empty());
     }
+
+    // 7.1 造一个带若干课程的全局状态
+    fn app_state_with(courses: Vec<Course>) -> web::Data<AppState> {
+        web::Data::new(AppState {
+            courses: Mutex::new(CourseStore::new(courses)),
+            ..AppState::for_test_inmem()
+        })
+    }
+
+    fn sample_courses() -> Vec<Course> {
+        vec![
+            Course {
+                teacher_id: 1,
+                id: Some(1),
+                name: "rust".into(),
+                // 固定时间戳，保证两次调用得到的数据完全相同
+                time: chrono::DateTime::from_timestamp(1_700_000_000, 0).map(|t| t.naive_utc()),
+            },
+            Course {
+                teacher_id: 2,
+                id: Some(1),
+                name: "actix".into(),
+                time: None, // Option<NaiveDateTime> 为 None 时也要能往返
+            },
+        ]
+    }
+
+    // 7.2 测试：导出后再导入，课程表保持不变
+    #[actix_web::test]
+    async fn export_then_import_round_trip() {
+        let source = app_state_with(sample_courses());
+        let exported = export_courses(source).await;
+        assert_eq!(exported.status(), StatusCode::OK);
+        let bytes = exported.into_body().try_into_bytes().unwrap();
+        let courses: Vec<Course> = serde_json::from_slice(&bytes).unwrap();
+
+        let target = app_state_with(vec![]);
+        let resp = import_courses(web::Json(courses), target.clone()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(target.courses.lock().unwrap().all(), sample_courses());
+    }
+
+    // 7.3 测试：任意一条不合法时整体拒绝，原数据不变
+    #[actix_web::test]
+    async fn import_rejects_invalid_entry_atomically() {
+        let original = sample_courses();
+        let app_state = app_state_with(original.clone());
+
+        let mut incoming = sample_courses();
+        incoming.push(Course {
+            teacher_id: 3,
+            id: Some(1),
+            name: "   ".into(),
+            time: None,
+        });
+
+        let resp = import_courses(web::Json(incoming), app_state.clone()).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(app_state.courses.lock().unwrap().all(), original);
+    }
+
+    // 8.1 测试：三门课分属两个老师 → 按 teacher_id 升序分组计数
+    #[actix_web::test]
+    async fn teacher_summary_groups_and_sorts() {
+        let course = |teacher_id: i32, name: &str| Course {
+            teacher_id,
+            id: None,
+            name: name.into(),
+            time: None,
+        };
+        let app_state = app_state_with(vec![course(2, "a"), course(1, "b"), course(2, "c")]);
+
+        let resp = get_teacher_summary(app_state).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let bytes = resp.into_body().try_into_bytes().unwrap();
+        let summary: Vec<TeacherSummary> = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            summary,
+            vec![
+                TeacherSummary { teacher_id: 1, course_count: 1 },
+                TeacherSummary { teacher_id: 2, course_count: 2 },
+            ]
+        );
+    }
+
+    // 9.1 测试：按时间降序，没有时间的排最后，limit 生效
+    #[actix_web::test]
+    async fn recent_courses_newest_first() {
+        let course = |name: &str, secs: Option<i64>| Course {
+            teacher_id: 1,
+            id: None,
+            name: name.into(),
+            time: secs.and_then(|s| chrono::DateTime::from_timestamp(s, 0)).map(|t| t.naive_utc()),
+        };
+        let app_state = app_state_with(vec![
+            course("old", Some(1_700_000_000)),
+            course("none", None),
+            course("new", Some(1_700_000_100)),
+        ]);
+
+        let names = |resp: HttpResponse| {
+            let bytes = resp.into_body().try_into_bytes().unwrap();
+            let courses: Vec<Course> = serde_json::from_slice(&bytes).unwrap();
+            courses.into_iter().map(|c| c.name).collect::<Vec<String>>()
+        };
+
+        let all = get_recent_courses(app_state.clone(), web::Query(RecentQuery::default())).await;
+        assert_eq!(names(all), vec!["new", "old", "none"]);
+
+        let top = get_recent_courses(app_state, web::Query(RecentQuery { limit: Some(1) })).await;
+        assert_eq!(names(top), vec!["new"]);
+    }
+
+    // 11.1 测试：GET /courses/detail/{teacher_id}/{course_id} 找到 → 200 + 课程
+    #[actix_web::test]
+    async fn get_course_detail_found() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state_with(sample_courses()))
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/courses/detail/2/1").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let course: Course = test::read_body_json(resp).await;
+        assert_eq!((course.teacher_id, course.id), (2, Some(1)));
+        assert_eq!(course.name, "actix");
+    }
+
+    // 11.2 测试：课程不存在 / 老师没有这门课 → 404；/courses/{teacher_id}/{name} 即使 name 是数字也走按老师查询
+    #[actix_web::test]
+    async fn get_course_detail_not_found() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state_with(sample_courses()))
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+
+        for uri in ["/courses/detail/1/99", "/courses/detail/3/1"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{}", uri);
+        }
+
+        for uri in ["/courses/1/rust", "/courses/1/1"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let courses: Vec<Course> = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(courses.len(), 1, "{}", uri);
+        }
+    }
+
+    // 11.3 测试：DTO 路由输出 camelCase 键，time 为 None 的课程不带 time 键
+    #[actix_web::test]
+    async fn courses_dto_uses_camel_case_and_omits_nulls() {
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state_with(sample_courses()))
+                .configure(crate::routers::course_routes),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/courses/dto/2").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            body,
+            serde_json::json!([{ "teacherId": 2, "id": 1, "name": "actix", "tags": [] }])
+        );
+
+        let req = test::TestRequest::get().uri("/courses/dto/1").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let course = &body[0];
+        assert!(course.get("teacher_id").is_none());
+        assert!(course["time"].is_string());
+    }
 }
@@ -19,20 +19,105 @@ use serde::{Deserialize, Serialize};
 // - `Deserialize` / `Serialize`：支持与 JSON 互转（用于 API 输入/输出）
 // - `Debug`：支持通过 `println!("{:?}", course)` 调试打印
 // - `Clone`：允许复制整个结构体（因为所有字段都实现了 Clone）
+// - `PartialEq`：允许用 `==` 比较两个课程（测试里断言导出/导入结果相等）
 //
 // 设计说明：
-// - `teacher_id` 是必填项（usize），表示所属教师
-// - `id` 是可选项（Option<usize>），因为新建课程时数据库尚未分配 ID
+// - `teacher_id` 是必填项（i32，与数据库版本的 INTEGER 一致），表示所属教师
+// - `id` 是可选项（Option<i32>），因为新建课程时数据库尚未分配 ID
 // - `name` 是课程名称，必填（String）
 // - `time` 是创建/更新时间，可为空（Option<NaiveDateTime>），兼容数据库 NULL
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Course {
-    pub teacher_id: usize,
-    pub id: Option<usize>,
+    pub teacher_id: i32,
+    pub id: Option<i32>,
     pub name: String,
     pub time: Option<NaiveDateTime>,
 }
 
+// === 对外展示用的课程 DTO ===
+//
+// Course 是内部存储模型，字段名和 JSON 一一对应（snake_case、null 原样输出）；
+// CourseDto 是给前端的外部表示，用 serde 的字段级属性定制 JSON 形状：
+// - `rename_all = "camelCase"`：teacher_id → teacherId
+// - `skip_serializing_if = "Option::is_none"`：id / time 为 None 时整个键省略，而不是输出 null
+// - `default`：反序列化时缺少 tags 键 → 用 Vec::default()（空数组），老客户端的 JSON 照样能解析
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseDto {
+    pub teacher_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+// 存储模型 → 外部表示：Course 目前没有标签，tags 给空数组
+impl From<Course> for CourseDto {
+    fn from(course: Course) -> Self {
+        CourseDto {
+            teacher_id: course.teacher_id,
+            id: course.id,
+            name: course.name,
+            time: course.time,
+            tags: Vec::new(),
+        }
+    }
+}
+
+// === 老师课程统计 ===
+//
+// GET /teachers/summary 的返回元素：每个老师有几门课
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TeacherSummary {
+    pub teacher_id: i32,
+    pub course_count: usize,
+}
+
+// === 最近课程的查询参数 ===
+//
+// `GET /courses/recent?limit=10`：省略时默认 10 条，最多 100 条
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct RecentQuery {
+    pub limit: Option<u32>,
+}
+
+pub const DEFAULT_RECENT_LIMIT: u32 = 10;
+pub const MAX_RECENT_LIMIT: u32 = 100;
+
+impl RecentQuery {
+    // 条数限制在 1..=MAX_RECENT_LIMIT
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_RECENT_LIMIT).clamp(1, MAX_RECENT_LIMIT)
+    }
+}
+
+// === 课程名称校验 ===
+//
+// 课程名称的最大长度（按字符数计算，中文一个字算 1 个字符）
+pub const MAX_COURSE_NAME_LEN: usize = 140;
+
+impl Course {
+    // 校验课程名称：
+    // - 去掉首尾空白后不能为空
+    // - 字符数不能超过 MAX_COURSE_NAME_LEN
+    // 返回 Err(原因)，由 handler 决定如何转成 HTTP 响应
+    pub fn validate_name(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("course name must not be empty".to_string());
+        }
+        if self.name.chars().count() > MAX_COURSE_NAME_LEN {
+            return Err(format!(
+                "course name must be at most {} characters",
+                MAX_COURSE_NAME_LEN
+            ));
+        }
+        Ok(())
+    }
+}
+
 
 // === 关于 From<web::Json<Course>> for Course 的说明 ===
 //
@@ -64,49 +149,70 @@ pub struct Course {
 // 最简洁、安全的实现如下（但一般没必要写）：
 impl From<web::Json<Course>> for Course {
     fn from(course: web::Json<Course>) -> Self {
-        Course {
-            teacher_id: course.teacher_id,   
-            id: course.id,                   
-            name: course.name.clone(),       
-            time: course.time               
-        }
+        // `web::Json<T>` 提供 `.into_inner()` 方法，安全地取出内部 T
+        // 直接把整个 Course 移出来 → 不需要逐字段 clone，语义清晰且零成本
+        course.into_inner()
     }
 }
 
-// === （可选）正确实现 From trait 的方式 ===
-//
-// 注意：此实现是冗余的，因为 `web::Json<Course>` 已经可以 `.into()` 转为 `Course`。
-// 仅在特殊场景（如泛型约束要求必须有 From 实现）才需要。
-// impl From<web::Json<Course>> for Course {
-//     fn from(course: web::Json<Course>) -> Self {
-//         // `web::Json<T>` 提供 `.into_inner()` 方法，安全地取出内部 T
-//         // 这是最推荐的方式，语义清晰且零成本
-//         course.into_inner()
-//     }
-// }
-
-
-// === 最佳实践建议（无需额外代码）===
-//
-// 在你的 handler 函数中，直接这样使用即可：
-//
-// async fn create_course(payload: web::Json<Course>) -> impl Responder {
-//     // 方式 1：使用 .into_inner()
-//     let course: Course = payload.into_inner();
-//
-//     // 方式 2：利用已有的 Into 实现
-//     // let course: Course = payload.into();
-//
-//     // 现在 course 是纯 Course 结构体，可存入数据库或处理
-//     HttpResponse::Ok().json(course)
-// }
-//
-// 因此，**本文件不需要任何 From 实现**，保持简洁即可。
-
 
 // === 总结 ===
 //
 // - 结构体 `Course` 已正确配置 serde 和调试支持。
 // - 字段设计合理，兼容数据库常见场景（ID 和时间可为空）。
-// - 无需手动实现 `From<web::Json<Course>>`，Actix Web 已提供更优方案。
-// - 避免重复造轮子，优先使用框架内置功能。
\ No newline at end of file
+// - `From<web::Json<Course>>` 只是 into_inner() 的薄封装，handler 里可以直接 `.into()`。
+// - 避免重复造轮子，优先使用框架内置功能。
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // web::Json<Course> → Course：所有字段原样保留，包括 Some 的 Option 字段
+    #[test]
+    fn from_json_keeps_all_fields() {
+        let time = chrono::DateTime::from_timestamp(1_700_000_000, 0).map(|t| t.naive_utc());
+        let original = Course {
+            teacher_id: 7,
+            id: Some(3),
+            name: "rust".into(),
+            time,
+        };
+
+        let course: Course = web::Json(original.clone()).into();
+        assert_eq!(course, original);
+        assert_eq!(course.id, Some(3));
+        assert_eq!(course.time, time);
+    }
+
+    // None 的 Option 字段同样保留为 None
+    #[test]
+    fn from_json_keeps_none_fields() {
+        let course: Course = web::Json(Course {
+            teacher_id: 1,
+            id: None,
+            name: "actix".into(),
+            time: None,
+        })
+        .into();
+        assert_eq!((course.id, course.time), (None, None));
+    }
+
+    // Course → CourseDto → JSON：键名是 camelCase，None 的 id / time 不出现
+    #[test]
+    fn course_dto_json_shape() {
+        let dto = CourseDto::from(Course {
+            teacher_id: 1,
+            id: None,
+            name: "rust".into(),
+            time: None,
+        });
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json, serde_json::json!({ "teacherId": 1, "name": "rust", "tags": [] }));
+
+        // 缺少 tags 键也能反序列化（serde(default)）
+        let parsed: CourseDto =
+            serde_json::from_str(r#"{"teacherId":2,"id":5,"name":"actix"}"#).unwrap();
+        assert_eq!((parsed.id, parsed.tags), (Some(5), Vec::<String>::new()));
+    }
+}
@@ -51,12 +51,31 @@ pub fn course_routes(cfg: &mut web::ServiceConfig) {
             // - 处理函数：`new_course`（必须是一个符合 Actix Web handler 签名的异步函数）
             //   通常用于创建新课程，请求体为 JSON 格式的 Course 数据
             .route("/", web::post().to(new_course))  
+
+            // 调试用：导出 / 导入整张课程表（JSON 数组）
+            // 单段路径，不会与下面的 /{user_id}/{name} 冲突
+            .route("/export", web::get().to(export_courses))
+            .route("/import", web::post().to(import_courses))
+            .route("/recent", web::get().to(get_recent_courses))
+
+            // GET /courses/dto/{teacher_id} → camelCase 的 CourseDto 列表
+            // 必须注册在 `/{user_id}/{name}` 之前，否则 "dto" 会被当成 user_id
+            .route("/dto/{teacher_id}", web::get().to(get_courses_for_teacher_dto))
             
             // 注册 GET /courses/{user_id} 路由
             // - 路径：`/{user_id}`（完整路径为 `/courses/{user_id}`）
             // - HTTP 方法：GET（通过 `web::get()` 指定）
-            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<usize>`）
+            // - 路径参数：`{user_id}` 会被自动提取，并传递给 handler（如通过 `web::Path<i32>`）
             // - 处理函数：`get_courses_for_teacher`，用于根据教师 ID 查询其所有课程
+            // 注册 GET /courses/detail/{teacher_id}/{course_id} → 单门课程详情，找不到返回 404
+            // - 三段路径，和下面两段的 `/{user_id}/{name}` 不重叠：课程名是纯数字也照样按老师查询
+            .route("/detail/{user_id}/{course_id}", web::get().to(get_course_detail))
+
             .route("/{user_id}/{name}", web::get().to(get_courses_for_teacher)),
     );
-}
\ No newline at end of file
+}
+
+// 注册老师相关路由：GET /teachers/summary → 每个老师的课程数
+pub fn teacher_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/teachers").route("/summary", web::get().to(get_teacher_summary)));
+}
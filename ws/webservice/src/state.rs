@@ -1,9 +1,70 @@
 // 引入标准库中的 `Mutex` 类型。 /mju:teks/
 // `Mutex`（互斥锁）是一种用于在多线程环境中安全地共享和修改数据的同步原语。
 // 它确保同一时间只有一个线程可以访问被它保护的数据，从而避免数据竞争（data race）。
+use std::collections::HashMap;
 use std::sync::Mutex;
 use super::models::Course;  //需要在 teacher-service.rs 声明下mod 这里才能调用 否则报错
 
+// 课程存储：课程表 + 按老师建立的索引
+//
+// 为什么要索引：按老师查课程原来是 iter().filter() 扫全表 → O(全部课程)；
+// 维护 teacher_id → 下标 的 HashMap 后，只需要访问这个老师自己的课程 → O(该老师的课程数)
+//
+// 两者放在同一个结构体、同一把锁里 → 任何时刻看到的索引都和课程表一致；
+// 字段私有，只能通过下面的方法修改，保证每次插入 / 整表替换都同步更新索引
+#[derive(Debug, Default)]
+pub struct CourseStore {
+    courses: Vec<Course>,                    // 按插入顺序保存（导出时保持原顺序）
+    by_teacher: HashMap<i32, Vec<usize>>,   // teacher_id → 在 courses 中的下标（升序）
+}
+
+impl CourseStore {
+    // 用已有课程建表（启动加载种子数据、整表导入时用）
+    pub fn new(courses: Vec<Course>) -> Self {
+        let mut store = CourseStore {
+            courses,
+            by_teacher: HashMap::new(),
+        };
+        store.rebuild_index();
+        store
+    }
+
+    // 全部课程（按插入顺序）
+    pub fn all(&self) -> &[Course] {
+        &self.courses
+    }
+
+    // 某个老师的全部课程（按插入顺序）；只访问索引里的下标，不扫全表
+    pub fn for_teacher(&self, teacher_id: i32) -> impl Iterator<Item = &Course> {
+        self.by_teacher
+            .get(&teacher_id)
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.courses[index])
+    }
+
+    // 插入：新课程在末尾，下标 = 插入前的长度 → 追加到该老师的下标列表即可，O(1)
+    pub fn push(&mut self, course: Course) {
+        self.by_teacher
+            .entry(course.teacher_id)
+            .or_default()
+            .push(self.courses.len());
+        self.courses.push(course);
+    }
+
+    // 整表替换
+    pub fn replace_all(&mut self, courses: Vec<Course>) {
+        *self = CourseStore::new(courses);
+    }
+
+    fn rebuild_index(&mut self) {
+        self.by_teacher.clear();
+        for (index, course) in self.courses.iter().enumerate() {
+            self.by_teacher.entry(course.teacher_id).or_default().push(index);
+        }
+    }
+}
+
 // 使用 `pub` 关键字声明一个公共的结构体 `AppState`。
 // `pub` 表示这个结构体可以在当前模块之外被其他模块或 crate 访问。
 // `AppState` 通常用于 Web 应用（如使用 Axum、Actix-web 等框架）中存储应用的全局状态。
@@ -34,5 +95,70 @@ pub struct AppState {
     //| `courses`      | 字段名，**课程列表**                  |
     //| `Mutex<...>`  | **互斥锁**，**同一时刻只允许一个线程访问内部数据** |
     //| `Vec<Course>` | **动态数组**，里面存 **Course 结构体实例** |
-    pub courses: Mutex<Vec<Course>>
+    // CourseStore 内部就是 Vec<Course>，另外维护了按老师的索引（见上面的 CourseStore）
+    pub courses: Mutex<CourseStore>
+}
+
+// 测试专用构造函数：字段全部填默认值（健康检查回 "OK"、计数 0、空课程表）
+// 测试里不再手写每个字段 → AppState 以后加字段，只需要改这一处
+#[cfg(test)]
+impl AppState {
+    pub fn for_test_inmem() -> Self {
+        AppState {
+            health_check_response: "OK".to_string(),
+            visit_count: Mutex::new(0),
+            courses: Mutex::new(CourseStore::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn course(teacher_id: i32, id: i32, name: &str) -> Course {
+        Course {
+            teacher_id,
+            id: Some(id),
+            name: name.into(),
+            time: None,
+        }
+    }
+
+    // 索引必须和逐个 filter 扫全表的结果完全一致
+    fn assert_index_consistent(store: &CourseStore) {
+        for teacher_id in 0..5 {
+            let indexed: Vec<&Course> = store.for_teacher(teacher_id).collect();
+            let scanned: Vec<&Course> = store
+                .all()
+                .iter()
+                .filter(|c| c.teacher_id == teacher_id)
+                .collect();
+            assert_eq!(indexed, scanned, "teacher {}", teacher_id);
+        }
+    }
+
+    #[test]
+    fn index_stays_consistent_after_inserts_and_replace() {
+        let mut store = CourseStore::default();
+        store.push(course(1, 1, "a"));
+        store.push(course(2, 1, "b"));
+        store.push(course(1, 2, "c"));
+        store.push(course(3, 1, "d"));
+        store.push(course(1, 3, "e"));
+        assert_index_consistent(&store);
+        assert_eq!(store.for_teacher(1).count(), 3);
+
+        let names: Vec<&str> = store.for_teacher(1).map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "c", "e"]);
+
+        // 整表替换（导入）→ 旧老师的下标不能残留，新课程要能查到
+        store.replace_all(vec![course(2, 1, "x"), course(4, 1, "y")]);
+        assert_index_consistent(&store);
+        assert_eq!(store.for_teacher(1).count(), 0);
+        assert_eq!(store.for_teacher(4).count(), 1);
+
+        store.push(course(2, 2, "f"));
+        assert_index_consistent(&store);
+    }
 }